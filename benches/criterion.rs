@@ -1,17 +1,24 @@
-use criterion::{criterion_group, criterion_main, Criterion, PlottingBackend};
+use criterion::measurement::WallTime;
+use criterion::{BenchmarkGroup, Criterion, PlottingBackend, criterion_group, criterion_main};
 
-use advent::solutions::{self, SolutionCollection};
-use advent::Input;
+use advent::grid::{Grid, Point};
+use advent::solutions::{self, IntoAnswer, SolutionCollection};
+use advent::{Answer, Input, ParseError, Solution};
+
+use advent::{HashMap, HashSet};
+
+mod before {
+    #[path = "year_2024_day_16.rs"]
+    pub mod day_16;
+    #[path = "year_2024_day_18.rs"]
+    pub mod day_18;
+}
 
 pub fn all_years(c: &mut Criterion) {
-    let input = Input::new();
-    do_bench(c, &input, 2019, solutions::days_2019());
-    do_bench(c, &input, 2020, solutions::days_2020());
-    do_bench(c, &input, 2021, solutions::days_2021());
-    do_bench(c, &input, 2022, solutions::days_2022());
-    do_bench(c, &input, 2023, solutions::days_2023());
-    do_bench(c, &input, 2024, solutions::days_2024());
-    do_bench(c, &input, 2025, solutions::days_2025());
+    let input = Input::from_env().expect("unable to open input store");
+    for (event, days) in solutions::events() {
+        do_bench(c, &input, event, days);
+    }
 }
 
 fn do_bench(c: &mut Criterion, input: &Input, year: u32, days: SolutionCollection) {
@@ -29,10 +36,164 @@ fn do_bench(c: &mut Criterion, input: &Input, year: u32, days: SolutionCollectio
     group.finish();
 }
 
+// compares the days that moved onto the search module against their code from before the move,
+// on generated inputs shaped like the real ones
+pub fn search(c: &mut Criterion) {
+    let days = solutions::events()
+        .into_iter()
+        .find(|(event, _)| *event == 2024)
+        .map(|(_, days)| days)
+        .expect("2024 solutions");
+    let day = |day| {
+        days.solutions()
+            .find(|s| s.day == day)
+            .expect("day registered in collection")
+    };
+
+    let mut group = c.benchmark_group("search");
+    bench_before_after(
+        &mut group,
+        "2024-16",
+        &reindeer_maze(141, 141),
+        day(16),
+        [
+            &|input| before::day_16::part_one(input).into_answer(),
+            &|input| before::day_16::part_two(input).into_answer(),
+        ],
+    );
+    bench_before_after(
+        &mut group,
+        "2024-18",
+        &falling_bytes(71, 71, 3450),
+        day(18),
+        [
+            &|input| before::day_18::part_one(input).into_answer(),
+            &|input| before::day_18::part_two(input).into_answer(),
+        ],
+    );
+    group.finish();
+}
+
+type BeforeFunc<'a> = &'a dyn Fn(&str) -> Result<Answer, ParseError>;
+
+fn bench_before_after(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    name: &str,
+    input: &str,
+    day: &Solution,
+    before: [BeforeFunc<'_>; 2],
+) {
+    for (part, (after, before)) in [&day.part_one, &day.part_two]
+        .into_iter()
+        .zip(before)
+        .enumerate()
+    {
+        // the old code is kept as it was, including its bugs, so its answers may be off
+        let part = part + 1;
+        let (before_answer, after_answer) = (before(input), after(input));
+        if before_answer != after_answer {
+            eprintln!(
+                "{} part {}: answers differ, {:?} before and {:?} after",
+                name, part, before_answer, after_answer
+            );
+        }
+
+        group.bench_function(format!("{}-{}-before", name, part), |b| {
+            b.iter(|| before(input))
+        });
+        group.bench_function(format!("{}-{}-after", name, part), |b| {
+            b.iter(|| after(input))
+        });
+    }
+}
+
+// fixed pseudo-random numbers so every run benches the same inputs
+struct XorShift(u32);
+
+impl XorShift {
+    fn next(&mut self) -> u32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        self.0
+    }
+}
+
+// a maze of corridors between the odd cells, carved as a spanning tree with some extra walls
+// knocked out so there are many paths of the same cost, like the real inputs
+fn reindeer_maze(width: usize, height: usize) -> String {
+    let mut rng = XorShift(0x2545f491);
+    let mut walls = Grid::new(width, height, true);
+    let start = Point::new(1, height as i32 - 2);
+    let end = Point::new(width as i32 - 2, 1);
+
+    walls[start] = false;
+    let mut stack = vec![start];
+    while let Some(&p) = stack.last() {
+        let next: Vec<_> = [(0, -2), (2, 0), (0, 2), (-2, 0)]
+            .into_iter()
+            .map(|(x, y)| p + Point::new(x, y))
+            .filter(|n| n.x > 0 && n.y > 0 && n.x < width as i32 - 1 && n.y < height as i32 - 1)
+            .filter(|n| walls[*n])
+            .collect();
+
+        if next.is_empty() {
+            stack.pop();
+            continue;
+        }
+
+        let n = next[rng.next() as usize % next.len()];
+        walls[Point::new((p.x + n.x) / 2, (p.y + n.y) / 2)] = false;
+        walls[n] = false;
+        stack.push(n);
+    }
+
+    for p in walls.points() {
+        let inner = p.x > 0 && p.y > 0 && p.x < width as i32 - 1 && p.y < height as i32 - 1;
+        if inner && (p.x + p.y) % 2 == 1 && rng.next().is_multiple_of(10) {
+            walls[p] = false;
+        }
+    }
+
+    let mut maze = String::with_capacity((width + 1) * height);
+    for y in 0..height as i32 {
+        for x in 0..width as i32 {
+            let p = Point::new(x, y);
+            maze.push(match p {
+                _ if p == start => 'S',
+                _ if p == end => 'E',
+                _ if walls[p] => '#',
+                _ => '.',
+            });
+        }
+        maze.push('\n');
+    }
+
+    maze
+}
+
+// every cell except the corners in a shuffled order, enough of them to block the exit
+fn falling_bytes(width: usize, height: usize, count: usize) -> String {
+    let mut rng = XorShift(0x2545f491);
+    let mut cells: Vec<_> = (0..width * height)
+        .map(|idx| (idx % width, idx / width))
+        .filter(|&cell| cell != (0, 0) && cell != (width - 1, height - 1))
+        .collect();
+    for idx in (1..cells.len()).rev() {
+        cells.swap(idx, rng.next() as usize % (idx + 1));
+    }
+
+    cells
+        .iter()
+        .take(count)
+        .map(|(x, y)| format!("{},{}\n", x, y))
+        .collect()
+}
+
 criterion_group! {
     name = benches;
     config = Criterion::default().plotting_backend(PlottingBackend::Plotters);
-    targets = all_years
+    targets = search, all_years
 }
 
 criterion_main!(benches);
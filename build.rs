@@ -0,0 +1,89 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+// Generates the `mod day_NN;` declarations and `days()` registration for every
+// `src/solutions/year_XXXX/day_NN.rs`, along with the `events()` registry.
+fn main() {
+    let manifest_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").unwrap());
+    let solutions = manifest_dir.join("src").join("solutions");
+
+    println!("cargo::rerun-if-changed=src/solutions");
+
+    let events = numbered_entries(&solutions, "year_", "");
+    let mut registry = String::new();
+
+    for &event in &events {
+        let year_dir = solutions.join(format!("year_{}", event));
+        let days = numbered_entries(&year_dir, "day_", ".rs");
+
+        let mut module = String::new();
+        for &day in &days {
+            let path = year_dir.join(format!("day_{:02}.rs", day));
+            let _ = writeln!(module, "#[path = {:?}]", path.display().to_string());
+            let _ = writeln!(module, "mod day_{:02};", day);
+        }
+
+        let _ = writeln!(
+            module,
+            "\npub fn days() -> crate::solutions::SolutionCollection {{"
+        );
+        let _ = writeln!(module, "    crate::solutions::SolutionCollection::new()");
+        for &day in &days {
+            // each day declares its parts with `solution!`, which defines `register`
+            let _ = writeln!(
+                module,
+                "        .add_solution(day_{day:02}::register({day}))"
+            );
+        }
+        let _ = writeln!(module, "}}");
+
+        let module_path = out_dir.join(format!("year_{}.rs", event));
+        std::fs::write(&module_path, module).unwrap();
+
+        // years without a module of their own for shared code get an inline one
+        let year_file = solutions.join(format!("year_{}.rs", event));
+        if year_file.exists() {
+            let _ = writeln!(registry, "#[path = {:?}]", year_file.display().to_string());
+            let _ = writeln!(registry, "mod year_{};", event);
+        } else {
+            let _ = writeln!(
+                registry,
+                "mod year_{} {{\n    include!({:?});\n}}",
+                event,
+                module_path.display().to_string()
+            );
+        }
+    }
+
+    let _ = writeln!(
+        registry,
+        "\npub fn events() -> Vec<(u32, SolutionCollection)> {{"
+    );
+    let _ = writeln!(registry, "    vec![");
+    for &event in &events {
+        let _ = writeln!(registry, "        ({event}, year_{event}::days()),");
+    }
+    let _ = writeln!(registry, "    ]");
+    let _ = writeln!(registry, "}}");
+
+    std::fs::write(out_dir.join("events.rs"), registry).unwrap();
+}
+
+fn numbered_entries(dir: &Path, prefix: &str, suffix: &str) -> Vec<u32> {
+    let mut entries: Vec<u32> = std::fs::read_dir(dir)
+        .unwrap()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir() == suffix.is_empty())
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            name.strip_prefix(prefix)?
+                .strip_suffix(suffix)?
+                .parse()
+                .ok()
+        })
+        .collect();
+
+    entries.sort_unstable();
+    entries
+}
@@ -0,0 +1,301 @@
+use std::collections::BTreeMap;
+
+use crate::Input;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejection {
+    pub answer: String,
+    pub hint: Option<Hint>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Blocked {
+    Accepted(String),
+    Rejected,
+    TooHigh(String),
+    TooLow(String),
+}
+
+impl std::fmt::Display for Blocked {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Blocked::Accepted(answer) => write!(f, "already accepted '{}'", answer),
+            Blocked::Rejected => write!(f, "answer was already rejected"),
+            Blocked::TooHigh(answer) => write!(f, "'{}' was already too high", answer),
+            Blocked::TooLow(answer) => write!(f, "'{}' was already too low", answer),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers {
+    entries: BTreeMap<(u32, u32), String>,
+    local: BTreeMap<(u32, u32), String>,
+    rejected: BTreeMap<(u32, u32), Vec<Rejection>>,
+}
+
+impl Answers {
+    pub fn get(&self, day: u32, part: u32) -> Option<&str> {
+        self.entries.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn set<S: Into<String>>(&mut self, day: u32, part: u32, answer: S) {
+        self.local.remove(&(day, part));
+        self.entries.insert((day, part), answer.into());
+    }
+
+    /// Answer computed locally that was never confirmed by the server, it does not block submitting.
+    pub fn local(&self, day: u32, part: u32) -> Option<&str> {
+        self.local.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn set_local<S: Into<String>>(&mut self, day: u32, part: u32, answer: S) {
+        self.local.insert((day, part), answer.into());
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u32, u32, &str)> {
+        self.entries
+            .iter()
+            .map(|(&(day, part), answer)| (day, part, answer.as_str()))
+    }
+
+    pub fn rejected(&self, day: u32, part: u32) -> &[Rejection] {
+        self.rejected
+            .get(&(day, part))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn reject<S: Into<String>>(&mut self, day: u32, part: u32, answer: S, hint: Option<Hint>) {
+        let answer = answer.into();
+        let rejected = self.rejected.entry((day, part)).or_default();
+
+        if let Some(existing) = rejected.iter_mut().find(|r| r.answer == answer) {
+            existing.hint = existing.hint.or(hint);
+        } else {
+            rejected.push(Rejection { answer, hint });
+        }
+    }
+
+    pub fn check(&self, day: u32, part: u32, answer: &str) -> Result<(), Blocked> {
+        if let Some(accepted) = self.get(day, part) {
+            return Err(Blocked::Accepted(accepted.to_string()));
+        }
+
+        let value = answer.trim().parse::<i128>().ok();
+
+        for rejection in self.rejected(day, part) {
+            if rejection.answer == answer {
+                return Err(Blocked::Rejected);
+            }
+
+            let (Some(value), Ok(rejected)) = (value, rejection.answer.trim().parse::<i128>())
+            else {
+                continue;
+            };
+
+            match rejection.hint {
+                Some(Hint::TooHigh) if value >= rejected => {
+                    return Err(Blocked::TooHigh(rejection.answer.clone()));
+                }
+                Some(Hint::TooLow) if value <= rejected => {
+                    return Err(Blocked::TooLow(rejection.answer.clone()));
+                }
+                _ => (),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn parse(text: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut answers = Answers::default();
+
+        for (idx, line) in text.lines().enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut fields = line.splitn(4, ' ');
+            let entry = (|| {
+                let day = fields.next()?.parse().ok()?;
+                let part = fields.next()?.parse().ok()?;
+                let kind = fields.next()?;
+                let answer = unescape(fields.next()?);
+                Some((day, part, kind, answer))
+            })();
+
+            match entry {
+                Some((day, part, "ok", answer)) => answers.set(day, part, answer),
+                Some((day, part, "local", answer)) => answers.set_local(day, part, answer),
+                Some((day, part, "wrong", answer)) => answers.reject(day, part, answer, None),
+                Some((day, part, "high", answer)) => {
+                    answers.reject(day, part, answer, Some(Hint::TooHigh))
+                }
+                Some((day, part, "low", answer)) => {
+                    answers.reject(day, part, answer, Some(Hint::TooLow))
+                }
+                _ => return Err(format!("malformed answer on line {}", idx + 1).into()),
+            }
+        }
+
+        Ok(answers)
+    }
+
+    fn serialize(&self) -> String {
+        let mut text = String::new();
+        for (day, part, answer) in self.iter() {
+            text.push_str(&format!("{} {} ok {}\n", day, part, escape(answer)));
+        }
+        for (&(day, part), answer) in self.local.iter() {
+            text.push_str(&format!("{} {} local {}\n", day, part, escape(answer)));
+        }
+        for (&(day, part), rejected) in self.rejected.iter() {
+            for rejection in rejected {
+                let kind = match rejection.hint {
+                    None => "wrong",
+                    Some(Hint::TooHigh) => "high",
+                    Some(Hint::TooLow) => "low",
+                };
+                text.push_str(&format!(
+                    "{} {} {} {}\n",
+                    day,
+                    part,
+                    kind,
+                    escape(&rejection.answer)
+                ));
+            }
+        }
+        text
+    }
+}
+
+fn escape(answer: &str) -> String {
+    answer
+        .replace('\\', "\\\\")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+fn unescape(answer: &str) -> String {
+    let mut result = String::with_capacity(answer.len());
+    let mut chars = answer.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') => result.push('\n'),
+                Some('r') => result.push('\r'),
+                Some(c) => result.push(c),
+                None => result.push('\\'),
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+pub struct AnswerStore {
+    input: Input,
+}
+
+impl AnswerStore {
+    pub fn new(input: Input) -> Self {
+        Self { input }
+    }
+
+    pub fn read(&self, event: u32) -> Result<Answers, Box<dyn std::error::Error>> {
+        match self.input.read_answers(event)? {
+            Some(text) => Answers::parse(&text),
+            None => Ok(Answers::default()),
+        }
+    }
+
+    pub fn save(&self, event: u32, answers: &Answers) -> Result<(), Box<dyn std::error::Error>> {
+        self.input.save_answers(event, &answers.serialize())
+    }
+
+    pub fn record(
+        &self,
+        event: u32,
+        day: u32,
+        part: u32,
+        answer: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut answers = self.read(event)?;
+        answers.set(day, part, answer);
+        self.save(event, &answers)
+    }
+
+    pub fn record_local(
+        &self,
+        event: u32,
+        day: u32,
+        part: u32,
+        answer: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut answers = self.read(event)?;
+        answers.set_local(day, part, answer);
+        self.save(event, &answers)
+    }
+
+    pub fn reject(
+        &self,
+        event: u32,
+        day: u32,
+        part: u32,
+        answer: &str,
+        hint: Option<Hint>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut answers = self.read(event)?;
+        answers.reject(day, part, answer, hint);
+        self.save(event, &answers)
+    }
+}
+
+#[test]
+fn test_answers() {
+    let mut answers = Answers::default();
+    answers.set(10, 2, "##..\n#..\\#");
+    answers.set(1, 1, "12 34");
+
+    answers.reject(1, 2, "100", Some(Hint::TooHigh));
+    answers.reject(1, 2, "40", Some(Hint::TooLow));
+    answers.reject(1, 2, "abc", None);
+
+    let text = answers.serialize();
+    assert_eq!(
+        text,
+        "1 1 ok 12 34\n10 2 ok ##..\\n#..\\\\#\n1 2 high 100\n1 2 low 40\n1 2 wrong abc\n"
+    );
+    assert_eq!(Answers::parse(&text).unwrap(), answers);
+    assert!(Answers::parse("1 x ok 12").is_err());
+    assert!(Answers::parse("1 1 12").is_err());
+
+    assert_eq!(
+        answers.check(1, 1, "1"),
+        Err(Blocked::Accepted("12 34".into()))
+    );
+    assert_eq!(answers.check(1, 2, "abc"), Err(Blocked::Rejected));
+    assert_eq!(
+        answers.check(1, 2, "150"),
+        Err(Blocked::TooHigh("100".into()))
+    );
+    assert_eq!(answers.check(1, 2, "30"), Err(Blocked::TooLow("40".into())));
+    assert_eq!(answers.check(1, 2, "75"), Ok(()));
+    assert_eq!(answers.check(2, 1, "75"), Ok(()));
+
+    answers.set_local(2, 1, "75");
+    assert_eq!(answers.check(2, 1, "75"), Ok(()));
+    let text = answers.serialize();
+    assert!(text.contains("\n2 1 local 75\n"));
+    assert_eq!(Answers::parse(&text).unwrap(), answers);
+    answers.set(2, 1, "76");
+    assert_eq!((answers.get(2, 1), answers.local(2, 1)), (Some("76"), None));
+}
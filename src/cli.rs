@@ -0,0 +1,808 @@
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use advent::input::StoreKind;
+
+use crate::output::Format;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Command {
+    Run,
+    Bench,
+    Fetch,
+    Submit,
+    New,
+    List,
+    Verify,
+    Test,
+    Compare,
+    Puzzle,
+    Rekey,
+    Help,
+}
+
+impl Command {
+    fn from_name(name: &str) -> Option<Self> {
+        let command = match name {
+            "run" => Command::Run,
+            "bench" => Command::Bench,
+            "fetch" => Command::Fetch,
+            "submit" => Command::Submit,
+            "new" => Command::New,
+            "list" => Command::List,
+            "verify" => Command::Verify,
+            "test" => Command::Test,
+            "compare" => Command::Compare,
+            "puzzle" => Command::Puzzle,
+            "rekey" => Command::Rekey,
+            "help" => Command::Help,
+            _ => return None,
+        };
+
+        Some(command)
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Command::Run => "run",
+            Command::Bench => "bench",
+            Command::Fetch => "fetch",
+            Command::Submit => "submit",
+            Command::New => "new",
+            Command::List => "list",
+            Command::Verify => "verify",
+            Command::Test => "test",
+            Command::Compare => "compare",
+            Command::Puzzle => "puzzle",
+            Command::Rekey => "rekey",
+            Command::Help => "help",
+        }
+    }
+
+    fn runs_solutions(&self) -> bool {
+        matches!(
+            self,
+            Command::Run | Command::Bench | Command::Submit | Command::Verify
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+}
+
+#[derive(Debug)]
+pub struct Args {
+    pub command: Command,
+    pub selection: Selection,
+    pub details: bool,
+    pub print_input: bool,
+    pub submit: bool,
+    pub format: Option<Format>,
+    pub update_answers: bool,
+    pub jobs: usize,
+    pub timeout: Option<Duration>,
+    pub samples: usize,
+    pub warmup: usize,
+    pub save_baseline: Option<String>,
+    pub baseline: Option<String>,
+    pub threshold: f64,
+    pub puzzles: bool,
+    pub refresh: bool,
+    pub input_dir: Option<PathBuf>,
+    pub input_store: Option<StoreKind>,
+    pub old_key: Option<PathBuf>,
+    pub new_key: Option<PathBuf>,
+    pub profile: Option<String>,
+    pub all_profiles: bool,
+    pub input_source: Option<InputSource>,
+}
+
+impl Args {
+    /// Only plain runs of the profile's own inputs are recorded in the metrics history, other
+    /// invocations are not measured the same way and would skew later comparisons.
+    pub fn records_history(&self) -> bool {
+        matches!(self.command, Command::Run | Command::Bench)
+            && !self.submit
+            && self.input_source.is_none()
+            && self.jobs == 1
+            && !self.all_profiles
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventFilter {
+    Default,
+    All,
+    Ranges(Vec<RangeInclusive<u32>>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DayFilter {
+    Day(u32),
+    Range(RangeInclusive<u32>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selection {
+    pub events: EventFilter,
+    pub days: Vec<DayFilter>,
+    pub part: Option<u32>,
+}
+
+impl Selection {
+    pub fn contains_event(&self, event: u32, default_event: u32) -> bool {
+        match &self.events {
+            EventFilter::Default => event == default_event,
+            EventFilter::All => true,
+            EventFilter::Ranges(ranges) => ranges.iter().any(|r| r.contains(&event)),
+        }
+    }
+
+    pub fn contains_day(&self, day: u32) -> bool {
+        self.days.is_empty()
+            || self.days.iter().any(|d| match d {
+                DayFilter::Day(d) => *d == day,
+                DayFilter::Range(r) => r.contains(&day),
+            })
+    }
+
+    pub fn explicit_days(&self) -> impl Iterator<Item = u32> + '_ {
+        self.days.iter().filter_map(|d| match d {
+            DayFilter::Day(d) => Some(*d),
+            DayFilter::Range(_) => None,
+        })
+    }
+
+    pub fn single_day(&self) -> Option<(u32, u32)> {
+        let EventFilter::Ranges(events) = &self.events else {
+            return None;
+        };
+
+        match (events.as_slice(), self.days.as_slice()) {
+            ([event], [DayFilter::Day(day)]) if event.start() == event.end() => {
+                Some((*event.start(), *day))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CliError(String);
+
+impl std::fmt::Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for CliError {}
+
+macro_rules! cli_error {
+    ($($arg:tt)*) => {
+        CliError(format!($($arg)*))
+    };
+}
+
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Args, CliError> {
+    let mut args = args.into_iter().peekable();
+
+    let command = match args.peek().and_then(|a| Command::from_name(a)) {
+        Some(command) => {
+            args.next();
+            command
+        }
+        None => Command::Run,
+    };
+
+    let mut parsed = Args {
+        command,
+        selection: Selection {
+            events: EventFilter::Default,
+            days: Vec::new(),
+            part: None,
+        },
+        details: false,
+        print_input: false,
+        submit: command == Command::Submit,
+        format: None,
+        update_answers: false,
+        jobs: 1,
+        timeout: None,
+        samples: if command == Command::Bench { 10 } else { 1 },
+        warmup: if command == Command::Bench { 1 } else { 0 },
+        save_baseline: None,
+        baseline: None,
+        threshold: 10.0,
+        puzzles: false,
+        refresh: false,
+        input_dir: None,
+        input_store: None,
+        old_key: None,
+        new_key: None,
+        profile: None,
+        all_profiles: false,
+        input_source: None,
+    };
+
+    let mut positional = Vec::new();
+
+    while let Some(arg) = args.next() {
+        if !arg.starts_with('-') {
+            positional.push(arg);
+            continue;
+        }
+
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value)),
+            _ => (arg.clone(), None),
+        };
+
+        if inline_value.is_some()
+            && !matches!(
+                flag.as_str(),
+                "--part"
+                    | "--format"
+                    | "--jobs"
+                    | "--timeout"
+                    | "--samples"
+                    | "--warmup"
+                    | "--save-baseline"
+                    | "--baseline"
+                    | "--threshold"
+                    | "--input-dir"
+                    | "--input-store"
+                    | "--old-key"
+                    | "--new-key"
+                    | "--profile"
+                    | "--input-file"
+            )
+        {
+            return Err(cli_error!("option '{}' does not take a value", flag));
+        }
+
+        let mut take_value = || -> Result<String, CliError> {
+            if let Some(value) = inline_value {
+                Ok(value.to_string())
+            } else {
+                args.next()
+                    .ok_or_else(|| cli_error!("option '{}' requires a value", flag))
+            }
+        };
+
+        match flag.as_str() {
+            "--help" | "-h" => parsed.command = Command::Help,
+            "--part" | "-p" => {
+                let value = take_value()?;
+                let part = match value.as_str() {
+                    "1" => 1,
+                    "2" => 2,
+                    _ => return Err(cli_error!("invalid part '{}', expected 1 or 2", value)),
+                };
+                parsed.selection.part = Some(part);
+            }
+            "--format" | "-f"
+                if matches!(command, Command::Run | Command::Bench | Command::Verify) =>
+            {
+                parsed.format = Format::parse(&take_value()?).map_err(CliError)?;
+            }
+            "--jobs" | "-j" if command.runs_solutions() || command == Command::Test => {
+                let value = take_value()?;
+                parsed.jobs = match value.parse() {
+                    Ok(0) | Err(_) => {
+                        return Err(cli_error!("invalid job count '{}'", value));
+                    }
+                    Ok(jobs) => jobs,
+                };
+            }
+            "--timeout" | "-t" if command.runs_solutions() || command == Command::Test => {
+                let value = take_value()?;
+                let timeout = value
+                    .parse::<f64>()
+                    .ok()
+                    .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+                    .filter(|timeout| !timeout.is_zero())
+                    .ok_or_else(|| cli_error!("invalid timeout '{}'", value))?;
+                parsed.timeout = Some(timeout);
+            }
+            "--samples" | "-n" if command.runs_solutions() => {
+                let value = take_value()?;
+                parsed.samples = match value.parse() {
+                    Ok(0) | Err(_) => {
+                        return Err(cli_error!("invalid sample count '{}'", value));
+                    }
+                    Ok(samples) => samples,
+                };
+            }
+            "--warmup" | "-w" if command.runs_solutions() => {
+                let value = take_value()?;
+                parsed.warmup = value
+                    .parse()
+                    .map_err(|_| cli_error!("invalid warmup count '{}'", value))?;
+            }
+            "--save-baseline" if command.runs_solutions() => {
+                parsed.save_baseline = Some(take_value()?);
+            }
+            "--baseline" | "-b" if command == Command::Compare => {
+                parsed.baseline = Some(take_value()?);
+            }
+            "--threshold" if command == Command::Compare => {
+                let value = take_value()?;
+                parsed.threshold = value
+                    .trim_end_matches('%')
+                    .parse()
+                    .ok()
+                    .filter(|t: &f64| *t >= 0.0)
+                    .ok_or_else(|| cli_error!("invalid threshold '{}'", value))?;
+            }
+            "--input-dir" => parsed.input_dir = Some(PathBuf::from(take_value()?)),
+            "--input-store" => {
+                parsed.input_store = Some(StoreKind::parse(&take_value()?).map_err(CliError)?);
+            }
+            "--details" | "-d" if command.runs_solutions() => parsed.details = true,
+            "--input" | "-i" if command.runs_solutions() => parsed.print_input = true,
+            "--submit" | "-s" if command == Command::Run => parsed.submit = true,
+            "--update" | "-u" if command == Command::Verify => parsed.update_answers = true,
+            "--puzzles" if command == Command::Fetch => parsed.puzzles = true,
+            "--refresh" if command == Command::Fetch => {
+                parsed.puzzles = true;
+                parsed.refresh = true;
+            }
+            "--profile" | "-P" => parsed.profile = Some(take_value()?),
+            "--all-profiles" if command.runs_solutions() || command == Command::Fetch => {
+                parsed.all_profiles = true;
+            }
+            "--input-file" if matches!(command, Command::Run | Command::Bench) => {
+                parsed.input_source = Some(InputSource::File(PathBuf::from(take_value()?)));
+            }
+            "--stdin" if matches!(command, Command::Run | Command::Bench) => {
+                parsed.input_source = Some(InputSource::Stdin);
+            }
+            "--old-key" if command == Command::Rekey => {
+                parsed.old_key = Some(PathBuf::from(take_value()?));
+            }
+            "--new-key" if command == Command::Rekey => {
+                parsed.new_key = Some(PathBuf::from(take_value()?));
+            }
+            _ => {
+                return Err(cli_error!(
+                    "unknown option '{}' for command '{}'",
+                    flag,
+                    command.name()
+                ));
+            }
+        }
+    }
+
+    if parsed.command == Command::Help {
+        return Ok(parsed);
+    }
+
+    if parsed.profile.is_some() && parsed.all_profiles {
+        return Err(cli_error!(
+            "'--profile' cannot be combined with '--all-profiles'"
+        ));
+    }
+
+    if command == Command::Rekey {
+        if !positional.is_empty() {
+            return Err(cli_error!("'rekey' does not accept events or days"));
+        }
+        if parsed.new_key.is_none() {
+            return Err(cli_error!("'rekey' requires '--new-key <PATH>'"));
+        }
+        if parsed.profile.is_some() {
+            return Err(cli_error!(
+                "'rekey' re-encrypts every profile, they all share the input key"
+            ));
+        }
+    }
+
+    let mut positional = positional.into_iter();
+
+    if let Some(events) = positional.next() {
+        parsed.selection.events = parse_events(&events)?;
+    }
+
+    for days in positional {
+        for day in days.split(',').filter(|d| !d.is_empty()) {
+            parsed.selection.days.push(parse_day_filter(day)?);
+        }
+    }
+
+    if matches!(command, Command::New | Command::Puzzle) && parsed.selection.single_day().is_none()
+    {
+        return Err(cli_error!(
+            "'{}' requires exactly one event and one day",
+            command.name()
+        ));
+    }
+
+    if parsed.input_source.is_some() {
+        if parsed.selection.single_day().is_none() {
+            return Err(cli_error!(
+                "'--input-file' and '--stdin' require exactly one event and one day"
+            ));
+        }
+        if parsed.submit || parsed.all_profiles {
+            return Err(cli_error!(
+                "'--input-file' and '--stdin' cannot be combined with '--submit' or '--all-profiles'"
+            ));
+        }
+    }
+
+    if parsed.format.is_some() && (parsed.submit || parsed.print_input) {
+        return Err(cli_error!(
+            "'--format' cannot be combined with '--submit' or '--input'"
+        ));
+    }
+
+    if parsed.save_baseline.is_some() && !parsed.records_history() {
+        return Err(cli_error!(
+            "'--save-baseline' requires 'run' or 'bench' of the profile inputs without '--submit', '--jobs', '--all-profiles', '--input-file' or '--stdin'"
+        ));
+    }
+
+    if command == Command::Fetch && parsed.selection.part.is_some() {
+        return Err(cli_error!("'--part' cannot be used with command 'fetch'"));
+    }
+
+    Ok(parsed)
+}
+
+fn parse_events(arg: &str) -> Result<EventFilter, CliError> {
+    if arg.eq_ignore_ascii_case("all") {
+        return Ok(EventFilter::All);
+    }
+
+    let mut ranges = Vec::new();
+    for event in arg.split(',').filter(|e| !e.is_empty()) {
+        let range = parse_range(event).ok_or_else(|| cli_error!("invalid event '{}'", event))?;
+
+        if *range.start() < 2015 {
+            return Err(cli_error!("invalid event '{}'", event));
+        }
+
+        ranges.push(range);
+    }
+
+    if ranges.is_empty() {
+        Err(cli_error!("invalid event '{}'", arg))
+    } else {
+        Ok(EventFilter::Ranges(ranges))
+    }
+}
+
+fn parse_day_filter(arg: &str) -> Result<DayFilter, CliError> {
+    let range = parse_range(arg).ok_or_else(|| cli_error!("invalid day '{}'", arg))?;
+
+    if *range.start() < 1 || *range.end() > 25 {
+        return Err(cli_error!("day '{}' out of range, expected 1-25", arg));
+    }
+
+    if range.start() == range.end() {
+        Ok(DayFilter::Day(*range.start()))
+    } else {
+        Ok(DayFilter::Range(range))
+    }
+}
+
+fn parse_range(arg: &str) -> Option<RangeInclusive<u32>> {
+    let (start, end) = match arg.split_once('-') {
+        Some((start, end)) => (start.parse().ok()?, end.parse().ok()?),
+        None => {
+            let n = arg.parse().ok()?;
+            (n, n)
+        }
+    };
+
+    if start > end { None } else { Some(start..=end) }
+}
+
+pub fn usage() -> &'static str {
+    "Usage: advent-of-code [COMMAND] [OPTIONS] [EVENT] [DAY]...
+
+Commands:
+
+	run
+		Execute solutions and display their answers, this is the default command.
+
+	bench
+		Execute solutions and display detailed performance metrics, defaults to 10 samples after 1 warmup run.
+
+	fetch
+		Download any missing inputs for the selected days. Requests go to 'https://adventofcode.com' unless the
+		'AOC_BASE_URL' environment variable names a different server.
+
+	puzzle <EVENT> <DAY>
+		Display a puzzle description along with its example inputs and answers from the local puzzle cache.
+
+	submit
+		Execute solutions and ask to submit the answer for each part.
+
+	new <EVENT> <DAY>
+		Create a new solution file from 'src/solutions/template.rs', it is registered automatically on the next build.
+		When the puzzle was fetched with '--puzzles' its examples are filled with the example input and answers.
+
+	list
+		List the available solutions for the selected events, and the days that have no solution yet.
+
+	compare
+		Compare the most recent recorded metrics of the selected parts against the previous run on the same host, or
+		against a named baseline, and report regressions. Every 'run' and 'bench' of the profile inputs is recorded in
+		'.bench-history.jsonl', unless it uses '--submit', '--jobs', '--all-profiles', '--input-file' or '--stdin'.
+
+	verify
+		Execute solutions and compare each answer against the stored accepted answers, exits with an error if any differ.
+
+	test
+		Execute solutions against the puzzle examples they provide and compare the answers to the expected ones, exits
+		with an error if any differ. Inputs are not needed. The same examples are also checked by 'cargo test'.
+
+	rekey --new-key <PATH>
+		Re-encrypt every encrypted input, puzzle and answer file of every profile with the key stored in the given file,
+		a new random key is written to the file first if it does not exist. Afterwards replace '.input-key' with the new
+		key.
+
+	help
+		Display this message.
+
+Arguments:
+
+	[EVENT]
+		Limit to solutions from specific years, defaults to most recent year, 'all' will select all events.
+		Accepts a single year, a range '2019-2021', or a list '2019,2022'.
+
+	[DAY]...
+		Only select the specified days within the choosen events.
+		Accepts single days, ranges '1-5', or lists '1-5,19'.
+
+Options:
+
+	--part <PART>	-p <PART>
+		Only execute part 1 or part 2 of each day.
+
+	--submit	-s
+		Ask to submit answer after each solution. Requires '.session-key' file containing an Advent of Code authentication cookie value in the working directory.
+		Answers that were already rejected, or that are ruled out by an earlier 'too high' or 'too low' response, are
+		not submitted again.
+
+	--puzzles
+		With 'fetch', also download the puzzle description of every day visible on the event calendar, or of the
+		selected days. Puzzles are stored next to the inputs and encrypted the same way.
+
+	--refresh
+		With 'fetch', download puzzle descriptions again even if they are already stored, to pick up part two once it is
+		unlocked. Implies '--puzzles'.
+
+	--details	-d
+		Display additional performance metrics.
+
+	--save-baseline <NAME>
+		Tag the recorded metrics of this run with a baseline name for later use with 'compare --baseline'.
+
+	--baseline <NAME>	-b <NAME>
+		With 'compare', compare against the most recent run tagged with the given baseline name.
+
+	--threshold <PERCENT>
+		With 'compare', the increase in duration, allocations or peak memory reported as a regression, defaults to 10%.
+
+	--update	-u
+		With 'verify', store the current answer for any part that does not have an accepted answer yet. These are kept
+		as local answers that later runs of 'verify' compare against, but that never block submitting. Unsolved and
+		not applicable parts are never stored.
+
+	--jobs <N>	-j <N>
+		Execute up to N parts at once on a pool of worker threads, results are still displayed in order.
+		Timings are measured per thread, but allocations and peak memory include every part running at the same time.
+
+	--samples <N>	-n <N>
+		Execute each part N times and report the min, median, mean, 95th percentile and standard deviation of the
+		duration. The reported metrics use the median duration, the mean of the counters and the highest peak memory.
+
+	--warmup <N>	-w <N>
+		Execute each part N additional times before sampling, the warmup runs are not measured.
+
+	--timeout <SECONDS>	-t <SECONDS>
+		Stop waiting for a part after the given number of wall-clock seconds and report it as a TIMEOUT, the remaining
+		parts still run. A part that timed out keeps running in the background until it finishes, parts measured in the
+		meantime are marked as concurrent and their metrics are not comparable. Panicking parts are always reported as a
+		PANIC without stopping the run.
+
+	--format <FORMAT>	-f <FORMAT>
+		Output format for results, one of 'text', 'json', 'csv' or 'tsv'. Machine readable formats emit one record per part
+		containing the year, day, stage ('part' or 'parse'), part, status ('ok', 'unsolved', 'not_applicable', 'error',
+		'panic' or 'timeout'), answer, duration in nanoseconds, allocations, peak memory, cycles, instructions, the sample
+		count and duration statistics, whether the part ran concurrently with other parts, any error message, the profile
+		name, and for 'verify' the check result ('match', 'mismatch', 'local_match', 'local_mismatch', 'missing' or 'recorded') and the
+		expected answer.
+		Days with a separate parse step emit an extra record for it with the 'parse' stage and no part.
+		The 'json' format writes one object per line.
+
+	--input	-i
+		Print the input for each day.
+
+	--input-file <PATH>
+		Execute the selected day with the contents of the given file instead of the stored input, for example a puzzle
+		example or a hand written edge case. Nothing is downloaded and the metrics are not recorded.
+
+	--stdin
+		Like '--input-file', but read the input from standard input.
+
+	--profile <NAME>	-P <NAME>
+		Use the inputs, accepted answers and session key of a named profile stored in 'profiles/<NAME>/', with the
+		session key in 'profiles/<NAME>/.session-key' and inputs in 'profiles/<NAME>/input/'. The profile 'default'
+		uses '.session-key' and the input directory.
+
+	--all-profiles
+		Execute or fetch every selected day once for each profile, including the default profile, to catch solutions
+		that only work for some inputs.
+
+	--input-dir <PATH>
+		Directory holding inputs, puzzles and accepted answers, defaults to the 'AOC_INPUT_DIR' environment variable or
+		'input'.
+
+	--old-key <PATH>
+		With 'rekey', read the current key from the given file instead of '.input-key' or 'AOC_INPUT_KEY'.
+
+	--new-key <PATH>
+		With 'rekey', the file holding the base64 encoded key to re-encrypt with.
+
+	--input-store <STORE>
+		How inputs are stored, one of 'encrypted', 'plain', 'archive' or 'memory', defaults to the 'AOC_INPUT_STORE'
+		environment variable, or 'encrypted' when an input key is available and 'plain' otherwise. The 'archive' store
+		keeps each year in a single file, encrypted when an input key is available, and 'memory' keeps nothing on disk.
+
+	--help	-h
+		Display this message.
+
+Examples:
+
+	advent-of-code 2021
+		Execute all solutions from 2021.
+
+	advent-of-code submit 2023 22 --part 2
+		Execute part 2 from day 22 of 2023, prompt to submit the solution.
+
+	advent-of-code run 2022 1-5,19
+		Execute days 1 through 5 and day 19 of 2022.
+
+	advent-of-code bench all
+		Execute all solutions with detailed metrics.
+
+	advent-of-code verify all
+		Check every solution against the accepted answers.
+
+	advent-of-code test all
+		Check every solution against its puzzle examples.
+
+	advent-of-code bench 2024 --save-baseline before && advent-of-code bench 2024 && advent-of-code compare 2024 -b before
+		Benchmark 2024 twice and report any part that got slower or uses more memory than the 'before' run.
+
+	advent-of-code fetch 2024 --puzzles && advent-of-code puzzle 2024 6
+		Store every visible 2024 puzzle, then read day 6 offline.
+
+	advent-of-code verify 2024 --all-profiles
+		Check every 2024 solution against the inputs and accepted answers of every profile.
+
+	printf '1000\\n2000\\n\\n3000\\n' | advent-of-code 2022 1 --stdin
+		Execute day 1 of 2022 with a hand written input.
+
+	advent-of-code run all --format csv
+		Execute all solutions and write the results as CSV.
+"
+}
+
+#[test]
+fn test_parse() {
+    let args = |s: &str| parse_args(s.split_whitespace().map(String::from));
+
+    let parsed = args("2022 1-5,19 --part 2").unwrap();
+    assert_eq!(parsed.command, Command::Run);
+    assert_eq!(
+        parsed.selection.events,
+        EventFilter::Ranges(vec![2022..=2022])
+    );
+    assert_eq!(
+        parsed.selection.days,
+        vec![DayFilter::Range(1..=5), DayFilter::Day(19)]
+    );
+    assert!(parsed.selection.contains_day(4));
+    assert!(!parsed.selection.contains_day(6));
+    assert_eq!(parsed.selection.part, Some(2));
+
+    let parsed = args("bench all -d").unwrap();
+    assert_eq!(parsed.command, Command::Bench);
+    assert_eq!(parsed.selection.events, EventFilter::All);
+    assert!(parsed.details);
+    assert_eq!((parsed.samples, parsed.warmup), (10, 1));
+
+    let parsed = args("run 2022 1 --samples 20 --warmup 0").unwrap();
+    assert_eq!((parsed.samples, parsed.warmup), (20, 0));
+    assert!(args("run 2022 1 --samples 0").is_err());
+
+    let parsed = args("run 2022 --format=tsv").unwrap();
+    assert_eq!(parsed.format, Some(Format::Tsv));
+    assert!(args("run 2022 --format xml").is_err());
+    assert_eq!(
+        args("verify 2022 -f json").unwrap().format,
+        Some(Format::Json)
+    );
+    assert_eq!(args("run all -j 8").unwrap().jobs, 8);
+    assert!(args("run all --jobs 0").is_err());
+    assert!(args("list all --jobs 2").is_err());
+    assert_eq!(args("test 2024 -j 2").unwrap().command, Command::Test);
+    assert!(args("test 2024 --samples 2").is_err());
+    assert_eq!(
+        args("run all --timeout 2.5").unwrap().timeout,
+        Some(Duration::from_millis(2500))
+    );
+    assert!(args("run all --timeout -1").is_err());
+    assert!(args("run all --timeout 0").is_err());
+    assert!(args("run 2022 -f json --submit").is_err());
+
+    let parsed = args("new 2025 13").unwrap();
+    assert_eq!(parsed.selection.single_day(), Some((2025, 13)));
+
+    assert!(args("run 2022 1x").is_err());
+    assert!(args("run 2022 30").is_err());
+    assert!(args("run 2022 5-1").is_err());
+    assert!(args("run --sbumit").is_err());
+    assert!(args("fetch 2022 --details").is_err());
+    assert!(args("run --part 3").is_err());
+    assert!(args("run --part").is_err());
+    assert!(args("new 2025").is_err());
+    assert!(args("verify 2022 --update").unwrap().update_answers);
+    let parsed = args("compare 2022 --baseline release --threshold 5%").unwrap();
+    assert_eq!(parsed.baseline.as_deref(), Some("release"));
+    assert_eq!(parsed.threshold, 5.0);
+    assert!(args("run 2022 --baseline release").is_err());
+    assert!(
+        args("bench 2022 --save-baseline before")
+            .unwrap()
+            .records_history()
+    );
+    assert!(!args("verify 2022").unwrap().records_history());
+    assert!(!args("run 2022 -j 4").unwrap().records_history());
+    assert!(args("run 2022 -j 4 --save-baseline before").is_err());
+    assert!(args("2022 1 --input-file example.txt --save-baseline x").is_err());
+    assert!(args("run 2022 --update").is_err());
+    assert!(args("fecth 2022").is_err());
+    let parsed = args("fetch 2024 --refresh").unwrap();
+    assert!(parsed.puzzles && parsed.refresh);
+    assert!(args("run 2024 --puzzles").is_err());
+    assert!(args("puzzle 2024").is_err());
+    let parsed = args("run 2024 --input-dir=/tmp/aoc --input-store archive").unwrap();
+    assert_eq!(parsed.input_dir, Some(PathBuf::from("/tmp/aoc")));
+    assert_eq!(parsed.input_store, Some(StoreKind::Archive));
+    assert!(args("run 2024 --input-store zip").is_err());
+    let parsed = args("rekey --new-key new.key").unwrap();
+    assert_eq!(parsed.new_key, Some(PathBuf::from("new.key")));
+    assert!(args("rekey").is_err());
+    assert!(args("rekey --new-key new.key -P alice").is_err());
+    let parsed = args("verify 2024 --all-profiles").unwrap();
+    assert!(parsed.all_profiles);
+    assert_eq!(
+        args("run 2024 -P alice").unwrap().profile.as_deref(),
+        Some("alice")
+    );
+    assert!(args("run 2024 -P alice --all-profiles").is_err());
+    assert!(args("puzzle 2024 1 --all-profiles").is_err());
+    let parsed = args("2022 1 --input-file example.txt").unwrap();
+    assert_eq!(
+        parsed.input_source,
+        Some(InputSource::File(PathBuf::from("example.txt")))
+    );
+    assert_eq!(
+        args("bench 2022 1 --stdin").unwrap().input_source,
+        Some(InputSource::Stdin)
+    );
+    assert!(args("2022 --stdin").is_err());
+    assert!(args("2022 1 --stdin --submit").is_err());
+    assert!(args("verify 2022 1 --stdin").is_err());
+    assert!(args("rekey 2022 --new-key new.key").is_err());
+}
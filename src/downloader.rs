@@ -1,36 +1,162 @@
-use std::{
-    cell::Cell,
-    time::{Duration, Instant},
-};
+use std::path::PathBuf;
+use std::time::Duration;
 
 use crate::Input;
+use crate::profile::Profile;
+use crate::rate_limit::RateLimit;
+use advent::Puzzle;
+use advent::answers::Hint;
 
 const USER_AGENT: &str = "aoc-submission-github/nickmass";
+const BASE_URL: &str = "https://adventofcode.com";
+const BASE_URL_ENV: &str = "AOC_BASE_URL";
 const REQUEST_DELAY: Duration = Duration::from_secs(15);
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmitResponse {
+    Correct,
+    Incorrect {
+        hint: Option<Hint>,
+        wait: Option<Duration>,
+    },
+    RateLimited(Duration),
+    AlreadyCompleted,
+    Unknown(String),
+}
+
+impl SubmitResponse {
+    fn parse(html: &str) -> Self {
+        let article = html
+            .split_once("<article>")
+            .and_then(|(_, article)| article.split_once("</article>"))
+            .map(|(article, _)| article)
+            .unwrap_or(html);
+        let text = advent::puzzle::html_text(article);
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        if text.contains("That's the right answer") {
+            SubmitResponse::Correct
+        } else if text.contains("That's not the right answer") {
+            let hint = if text.contains("too high") {
+                Some(Hint::TooHigh)
+            } else if text.contains("too low") {
+                Some(Hint::TooLow)
+            } else {
+                None
+            };
+
+            SubmitResponse::Incorrect {
+                hint,
+                wait: parse_wait(&text),
+            }
+        } else if text.contains("You gave an answer too recently") {
+            SubmitResponse::RateLimited(parse_wait(&text).unwrap_or(Duration::from_secs(60)))
+        } else if text.contains("Did you already complete it") {
+            SubmitResponse::AlreadyCompleted
+        } else {
+            SubmitResponse::Unknown(text)
+        }
+    }
+}
+
+impl std::fmt::Display for SubmitResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitResponse::Correct => write!(f, "Correct"),
+            SubmitResponse::Incorrect { hint, wait } => {
+                write!(f, "Incorrect")?;
+                match hint {
+                    Some(Hint::TooHigh) => write!(f, ", too high")?,
+                    Some(Hint::TooLow) => write!(f, ", too low")?,
+                    None => (),
+                }
+                if let Some(wait) = wait {
+                    write!(f, ", wait {}s before trying again", wait.as_secs())?;
+                }
+                Ok(())
+            }
+            SubmitResponse::RateLimited(wait) => {
+                write!(f, "Answered too recently, wait {}s", wait.as_secs())
+            }
+            SubmitResponse::AlreadyCompleted => write!(f, "Already completed"),
+            SubmitResponse::Unknown(text) => write!(f, "Unrecognized response: {}", text),
+        }
+    }
+}
+
+fn parse_wait(text: &str) -> Option<Duration> {
+    if let Some((_, rest)) = text.split_once("You have ") {
+        let (wait, _) = rest.split_once(" left to wait")?;
+        let mut secs = 0;
+        for unit in wait.split_whitespace() {
+            let (value, scale) = if let Some(value) = unit.strip_suffix('m') {
+                (value, 60)
+            } else if let Some(value) = unit.strip_suffix('s') {
+                (value, 1)
+            } else {
+                return None;
+            };
+            secs += value.parse::<u64>().ok()? * scale;
+        }
+        return Some(Duration::from_secs(secs));
+    }
+
+    let text = text.to_ascii_lowercase();
+    let (_, rest) = text.split_once("please wait ")?;
+    let mut words = rest.split_whitespace();
+    let count = match words.next()? {
+        "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        "ten" => 10,
+        n => n.parse().ok()?,
+    };
+    let scale = match words.next()? {
+        unit if unit.starts_with("minute") => 60,
+        unit if unit.starts_with("second") => 1,
+        _ => return None,
+    };
+
+    Some(Duration::from_secs(count * scale))
+}
+
 pub struct InputDownloader {
     input: Input,
     session_key: Option<String>,
+    session_key_path: PathBuf,
+    base_url: String,
     http_client: ureq::Agent,
-    last_request_time: Cell<Option<Instant>>,
+    rate_limit: RateLimit,
 }
 
 impl InputDownloader {
-    pub fn new() -> Self {
-        let session_key = std::fs::read_to_string(".session-key")
+    pub fn new(input: Input, profile: &Profile) -> Self {
+        let session_key_path = profile.session_key.as_path();
+        let session_key = std::fs::read_to_string(session_key_path)
             .ok()
             .map(|s| s.trim().to_string());
 
+        let base_url = std::env::var(BASE_URL_ENV)
+            .ok()
+            .filter(|url| !url.is_empty())
+            .unwrap_or_else(|| BASE_URL.to_string())
+            .trim_end_matches('/')
+            .to_string();
+
         let http_client = ureq::Agent::config_builder()
             .user_agent(USER_AGENT)
             .build()
             .into();
 
         Self {
-            input: Input::new(),
+            input,
             session_key,
+            session_key_path: session_key_path.to_path_buf(),
+            base_url,
             http_client,
-            last_request_time: Cell::new(None),
+            rate_limit: RateLimit::new(REQUEST_DELAY, profile.name.as_deref()),
         }
     }
 
@@ -42,18 +168,8 @@ impl InputDownloader {
         if let Some(input) = self.input.read(event, day)? {
             Ok(input)
         } else {
-            self.wait_if_needed();
             eprintln!("downloading {event} day {day}.");
-            let session_key = self
-                .session_key
-                .as_ref()
-                .ok_or("file '.session-key' not found")?;
-            let url = format!("https://adventofcode.com/{event}/day/{day}/input");
-            let auth = format!("session={session_key}");
-
-            self.update_request_time();
-            let res = self.http_client.get(&url).header("cookie", &auth).call()?;
-            let input = res.into_body().read_to_string()?;
+            let input = self.get(&format!("/{event}/day/{day}/input"))?;
 
             self.input.save(event, day, &input)?;
 
@@ -66,24 +182,45 @@ impl InputDownloader {
         }
     }
 
-    fn wait_if_needed(&self) {
-        if let Some(last_req) = self.last_request_time.get() {
-            let mut logged = false;
-            while last_req.elapsed() < REQUEST_DELAY {
-                if !logged {
-                    logged = true;
-                    let dur = REQUEST_DELAY
-                        .saturating_sub(last_req.elapsed())
-                        .max(Duration::from_secs(1));
-                    eprintln!("waiting {} seconds between requests...", dur.as_secs())
-                }
-                std::thread::sleep(Duration::from_secs(1));
-            }
+    pub fn download_puzzle(
+        &self,
+        event: u32,
+        day: u32,
+        refresh: bool,
+    ) -> Result<Puzzle, Box<dyn std::error::Error>> {
+        if !refresh && let Some(puzzle) = self.input.read_puzzle(event, day)? {
+            return Ok(puzzle);
         }
+
+        eprintln!("downloading {event} day {day} puzzle.");
+        let puzzle = Puzzle::new(self.get(&format!("/{event}/day/{day}"))?);
+        self.input.save_puzzle(event, day, &puzzle)?;
+
+        Ok(puzzle)
     }
 
-    fn update_request_time(&self) {
-        self.last_request_time.set(Some(Instant::now()));
+    pub fn visible_days(&self, event: u32) -> Result<Vec<u32>, Box<dyn std::error::Error>> {
+        let calendar = self.get(&format!("/{event}"))?;
+
+        Ok(advent::puzzle::calendar_days(&calendar, event))
+    }
+
+    fn get(&self, path: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let session_key = self
+            .session_key
+            .as_ref()
+            .ok_or_else(|| format!("file '{}' not found", self.session_key_path.display()))?;
+        let url = format!("{}{path}", self.base_url);
+        let auth = format!("session={session_key}");
+
+        let _request = self.rate_limit.begin(false)?;
+        let res = self.http_client.get(&url).header("cookie", &auth).call()?;
+
+        Ok(res.into_body().read_to_string()?)
+    }
+
+    pub fn submit_cooldown(&self) -> Option<Duration> {
+        self.rate_limit.remaining(true)
     }
 
     pub fn submit_answer<S: AsRef<str>>(
@@ -92,42 +229,78 @@ impl InputDownloader {
         event: u32,
         day: u32,
         part: u32,
-    ) -> Result<bool, Box<dyn std::error::Error>> {
-        self.wait_if_needed();
+    ) -> Result<SubmitResponse, Box<dyn std::error::Error>> {
         let session_key = self
             .session_key
             .as_ref()
-            .ok_or("file '.session-key' not found")?;
+            .ok_or_else(|| format!("file '{}' not found", self.session_key_path.display()))?;
 
-        let url = format!("https://adventofcode.com/{event}/day/{day}/answer");
+        let url = format!("{}/{event}/day/{day}/answer", self.base_url);
         let auth = format!("session={session_key}");
         let part = part.to_string();
 
-        self.update_request_time();
+        let request = self.rate_limit.begin(true)?;
         let res = self
             .http_client
             .post(&url)
             .header("cookie", &auth)
             .send_form([("level", part.as_ref()), ("answer", answer.as_ref())])?;
         let text = res.into_body().read_to_string()?;
+        let response = SubmitResponse::parse(&text);
 
-        let mut output = false;
-        for line in text.lines() {
-            if line.contains("<article>") {
-                output = true;
-            }
-            if output {
-                eprintln!("{line}");
-            }
-            if line.contains("</article>") {
-                output = false;
-            }
+        match response {
+            SubmitResponse::RateLimited(wait)
+            | SubmitResponse::Incorrect {
+                wait: Some(wait), ..
+            } => request.cooldown(wait)?,
+            _ => (),
         }
 
-        if text.contains("That's the right answer") {
-            Ok(true)
-        } else {
-            Ok(false)
-        }
+        Ok(response)
     }
 }
+
+#[test]
+fn test_submit_response() {
+    let response = |text: &str| {
+        SubmitResponse::parse(&format!(
+            "<main>\n<article><p>{}</p></article>\n</main>",
+            text
+        ))
+    };
+
+    assert_eq!(
+        response("That's the right answer!  You are <span>one gold star</span> closer."),
+        SubmitResponse::Correct
+    );
+    assert_eq!(
+        response(
+            "That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; please wait one minute before trying again."
+        ),
+        SubmitResponse::Incorrect {
+            hint: Some(Hint::TooHigh),
+            wait: Some(Duration::from_secs(60)),
+        }
+    );
+    assert_eq!(
+        response("That's not the right answer.  Please wait 5 minutes before trying again."),
+        SubmitResponse::Incorrect {
+            hint: None,
+            wait: Some(Duration::from_secs(300)),
+        }
+    );
+    assert_eq!(
+        response(
+            "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait."
+        ),
+        SubmitResponse::RateLimited(Duration::from_secs(65))
+    );
+    assert_eq!(
+        response("You don't seem to be solving the right level.  Did you already complete it?"),
+        SubmitResponse::AlreadyCompleted
+    );
+    assert!(matches!(
+        response("Something else"),
+        SubmitResponse::Unknown(_)
+    ));
+}
@@ -1,83 +1,273 @@
 use base64::prelude::*;
-use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_128_GCM};
+use ring::aead::{AES_128_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
 use ring::error::Unspecified;
+use ring::rand::{SecureRandom, SystemRandom};
+
+use crate::input::Entry;
 
 pub struct Encryption {
     key_bytes: Vec<u8>,
+    key_id: [u8; KEY_ID_LEN],
 }
 
 const KEYFILE_PATH: &str = ".input-key";
 const KEYFILE_ENV: &str = "AOC_INPUT_KEY";
 
-#[derive(Debug, Copy, Clone)]
-pub struct EncryptError;
+const MAGIC: &[u8] = b"AOCE";
+const VERSION: u8 = 1;
+const KEY_ID_LEN: usize = 4;
+const HEADER_LEN: usize = MAGIC.len() + 1 + KEY_ID_LEN + NONCE_LEN;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncryptError {
+    Unspecified,
+    InvalidKey(String),
+    WrongKey { expected: String, found: String },
+    UnsupportedVersion(u8),
+}
 
 impl From<Unspecified> for EncryptError {
     fn from(_value: Unspecified) -> Self {
-        EncryptError
+        EncryptError::Unspecified
     }
 }
 
 impl std::fmt::Display for EncryptError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Unspecified")
+        match self {
+            EncryptError::Unspecified => write!(f, "Unspecified"),
+            EncryptError::InvalidKey(reason) => write!(f, "invalid input key, {}", reason),
+            EncryptError::WrongKey { expected, found } => write!(
+                f,
+                "file was encrypted with key '{}' but the current key is '{}'",
+                found, expected
+            ),
+            EncryptError::UnsupportedVersion(version) => {
+                write!(f, "unsupported encrypted file version {}", version)
+            }
+        }
     }
 }
 
 impl std::error::Error for EncryptError {}
 
 impl Encryption {
-    pub fn new() -> Option<Self> {
-        let key_str = std::env::var(KEYFILE_ENV).ok().or(read_keyfile())?;
-        let key_bytes = BASE64_STANDARD.decode(key_str.trim()).unwrap();
-        let _key = UnboundKey::new(&AES_128_GCM, &key_bytes).unwrap();
+    pub fn new() -> Result<Option<Self>, EncryptError> {
+        let key_str = match std::env::var(KEYFILE_ENV).ok().or_else(read_keyfile) {
+            Some(key_str) => key_str,
+            None => return Ok(None),
+        };
+
+        Self::from_key(&key_str).map(Some)
+    }
+
+    pub fn from_key(key_str: &str) -> Result<Self, EncryptError> {
+        let key_bytes = BASE64_STANDARD
+            .decode(key_str.trim())
+            .map_err(|err| EncryptError::InvalidKey(format!("not valid base64: {}", err)))?;
+        UnboundKey::new(&AES_128_GCM, &key_bytes).map_err(|_| {
+            EncryptError::InvalidKey(format!(
+                "expected {} bytes but found {}",
+                AES_128_GCM.key_len(),
+                key_bytes.len()
+            ))
+        })?;
+
+        let digest = ring::digest::digest(&ring::digest::SHA256, &key_bytes);
+        let mut key_id = [0; KEY_ID_LEN];
+        key_id.copy_from_slice(&digest.as_ref()[..KEY_ID_LEN]);
 
-        Some(Self { key_bytes })
+        Ok(Self { key_bytes, key_id })
     }
 
-    pub fn encrypt_day(
+    pub fn generate_key() -> Result<String, EncryptError> {
+        let mut key_bytes = vec![0; AES_128_GCM.key_len()];
+        SystemRandom::new().fill(&mut key_bytes)?;
+
+        Ok(BASE64_STANDARD.encode(key_bytes))
+    }
+
+    pub fn key_id(&self) -> String {
+        hex(&self.key_id)
+    }
+
+    pub fn encrypt_entry(
         &self,
         event: u32,
-        day: u32,
-        mut data: Vec<u8>,
+        entry: Entry,
+        data: Vec<u8>,
     ) -> Result<Vec<u8>, EncryptError> {
-        let nonce = day_nonce(event, day);
+        self.encrypt(entry_context(event, entry), data)
+    }
+
+    pub fn decrypt_entry(
+        &self,
+        event: u32,
+        entry: Entry,
+        data: Vec<u8>,
+    ) -> Result<Vec<u8>, EncryptError> {
+        self.decrypt(entry_context(event, entry), data)
+    }
+
+    pub fn encrypt_archive(&self, event: u32, data: Vec<u8>) -> Result<Vec<u8>, EncryptError> {
+        self.encrypt(context(3, event, 0), data)
+    }
+
+    pub fn decrypt_archive(&self, event: u32, data: Vec<u8>) -> Result<Vec<u8>, EncryptError> {
+        self.decrypt(context(3, event, 0), data)
+    }
+
+    /// Files written before the versioned header reused a fixed nonce for every write.
+    pub fn is_legacy(data: &[u8]) -> bool {
+        !data.starts_with(MAGIC)
+    }
+
+    fn key(&self) -> LessSafeKey {
         let key =
             UnboundKey::new(&AES_128_GCM, &self.key_bytes).expect("key validated in constructor");
-        let key = LessSafeKey::new(key);
+        LessSafeKey::new(key)
+    }
+
+    fn encrypt(&self, context: [u8; NONCE_LEN], data: Vec<u8>) -> Result<Vec<u8>, EncryptError> {
+        let mut nonce = [0; NONCE_LEN];
+        SystemRandom::new().fill(&mut nonce)?;
+
+        let mut output = Vec::with_capacity(HEADER_LEN + data.len() + AES_128_GCM.tag_len());
+        output.extend_from_slice(MAGIC);
+        output.push(VERSION);
+        output.extend_from_slice(&self.key_id);
+        output.extend_from_slice(&nonce);
+
+        let aad = [&output[..], &context[..]].concat();
+        let mut data = data;
+        self.key().seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(aad),
+            &mut data,
+        )?;
+
+        output.extend_from_slice(&data);
+        Ok(output)
+    }
+
+    fn decrypt(&self, context: [u8; NONCE_LEN], data: Vec<u8>) -> Result<Vec<u8>, EncryptError> {
+        let Some(header) = data.strip_prefix(MAGIC) else {
+            return self.decrypt_legacy(context, data);
+        };
+        if header.len() < HEADER_LEN - MAGIC.len() {
+            return Err(EncryptError::Unspecified);
+        }
+        if header[0] != VERSION {
+            return Err(EncryptError::UnsupportedVersion(header[0]));
+        }
 
-        key.seal_in_place_append_tag(nonce, Aad::empty(), &mut data)?;
+        let key_id = &header[1..1 + KEY_ID_LEN];
+        if key_id != self.key_id {
+            return Err(EncryptError::WrongKey {
+                expected: self.key_id(),
+                found: hex(key_id),
+            });
+        }
+
+        let mut nonce = [0; NONCE_LEN];
+        nonce.copy_from_slice(&header[1 + KEY_ID_LEN..1 + KEY_ID_LEN + NONCE_LEN]);
+
+        let aad = [&data[..HEADER_LEN], &context[..]].concat();
+        let mut data = data[HEADER_LEN..].to_vec();
+        let len = self
+            .key()
+            .open_in_place(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(aad),
+                &mut data,
+            )?
+            .len();
+        data.truncate(len);
 
         Ok(data)
     }
 
-    pub fn decrypt_day(
+    fn decrypt_legacy(
         &self,
-        event: u32,
-        day: u32,
+        context: [u8; NONCE_LEN],
         mut data: Vec<u8>,
     ) -> Result<Vec<u8>, EncryptError> {
-        let nonce = day_nonce(event, day);
-        let key =
-            UnboundKey::new(&AES_128_GCM, &self.key_bytes).expect("key validated in constructor");
-        let key = LessSafeKey::new(key);
-
-        let plaintext = key.open_in_place(nonce, Aad::empty(), &mut data)?;
-        let len = plaintext.len();
+        let nonce = Nonce::assume_unique_for_key(context);
+        let len = self
+            .key()
+            .open_in_place(nonce, Aad::empty(), &mut data)?
+            .len();
         data.truncate(len);
 
         Ok(data)
     }
 }
 
-fn day_nonce(event: u32, day: u32) -> Nonce {
+fn entry_context(event: u32, entry: Entry) -> [u8; NONCE_LEN] {
+    match entry {
+        Entry::Input(day) => context(0, event, day),
+        Entry::Answers => context(1, event, 0),
+        Entry::Puzzle(day) => context(2, event, day),
+    }
+}
+
+fn context(kind: u8, event: u32, day: u32) -> [u8; NONCE_LEN] {
     let event = event.to_le_bytes();
     let day = day.to_le_bytes();
-    Nonce::assume_unique_for_key([
-        0, 0, 0, 0, event[0], event[1], event[2], event[3], day[0], day[1], day[2], day[3],
-    ])
+    [
+        kind, 0, 0, 0, event[0], event[1], event[2], event[3], day[0], day[1], day[2], day[3],
+    ]
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
 }
 
 fn read_keyfile() -> Option<String> {
     std::fs::read_to_string(KEYFILE_PATH).ok()
 }
+
+#[test]
+fn test_encryption() {
+    let enc = Encryption::from_key("AAECAwQFBgcICQoLDA0ODw==").unwrap();
+    let entry = Entry::Input(1);
+
+    let first = enc.encrypt_entry(2022, entry, b"1000\n".to_vec()).unwrap();
+    let second = enc.encrypt_entry(2022, entry, b"1000\n".to_vec()).unwrap();
+    assert_ne!(first, second);
+    assert!(first.starts_with(MAGIC));
+    assert_eq!(
+        enc.decrypt_entry(2022, entry, first.clone()).unwrap(),
+        b"1000\n"
+    );
+    assert!(
+        enc.decrypt_entry(2022, Entry::Input(2), first.clone())
+            .is_err()
+    );
+
+    let mut legacy = b"1000\n".to_vec();
+    enc.key()
+        .seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(context(0, 2022, 1)),
+            Aad::empty(),
+            &mut legacy,
+        )
+        .unwrap();
+    assert!(Encryption::is_legacy(&legacy) && !Encryption::is_legacy(&second));
+    assert_eq!(enc.decrypt_entry(2022, entry, legacy).unwrap(), b"1000\n");
+
+    let other = Encryption::from_key(&Encryption::generate_key().unwrap()).unwrap();
+    assert!(matches!(
+        other.decrypt_entry(2022, entry, first),
+        Err(EncryptError::WrongKey { .. })
+    ));
+
+    assert!(matches!(
+        Encryption::from_key("not base64!"),
+        Err(EncryptError::InvalidKey(_))
+    ));
+    assert!(matches!(
+        Encryption::from_key("AAEC"),
+        Err(EncryptError::InvalidKey(_))
+    ));
+}
@@ -0,0 +1,99 @@
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+    source_line: String,
+    hint: Option<&'static str>,
+}
+
+impl ParseError {
+    /// Error located at the start of `at`, which should be a slice of `input` such as one of
+    /// its lines or a field within a line. Slices from elsewhere are reported at the end of the input.
+    pub fn at(input: &str, at: &str, message: impl Into<String>) -> Self {
+        let start = input.as_ptr() as usize;
+        let offset = (at.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|&offset| offset <= input.len())
+            .unwrap_or(input.len());
+
+        Self::at_offset(input, offset, message)
+    }
+
+    /// Error for input that ended before the parser was done with it.
+    pub fn end(input: &str, message: impl Into<String>) -> Self {
+        Self::at_offset(input, input.trim_end().len(), message)
+    }
+
+    pub fn at_offset(input: &str, offset: usize, message: impl Into<String>) -> Self {
+        let offset = input.floor_char_boundary(offset.min(input.len()));
+        let line_start = input[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |idx| offset + idx);
+        let line = &input[line_start..line_end];
+
+        let hint = if line.ends_with('\r') {
+            Some("the input has CRLF line endings, convert them to LF")
+        } else if line_end == input.len() && !input.ends_with('\n') {
+            Some("the input does not end with a newline, it may be truncated")
+        } else {
+            None
+        };
+
+        Self {
+            line: input[..line_start].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            message: message.into(),
+            source_line: line.trim_end_matches('\r').to_string(),
+            hint,
+        }
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        writeln!(f, "  {}", self.source_line)?;
+        write!(f, "  {:>width$}", "^", width = self.column)?;
+        if let Some(hint) = self.hint {
+            write!(f, "\n{}", hint)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[test]
+fn test_parse_error() {
+    let input = "noop\naddx 1x\nnoop\n";
+    let line = input.lines().nth(1).unwrap();
+    let err = ParseError::at(input, &line[5..], "invalid number");
+    assert_eq!((err.line, err.column), (2, 6));
+    assert_eq!(
+        err.to_string(),
+        "line 2, column 6: invalid number\n  addx 1x\n       ^"
+    );
+
+    let err = ParseError::at(input, "elsewhere", "unexpected");
+    assert_eq!((err.line, err.column), (4, 1));
+
+    let input = "noop\r\naddx\r\n";
+    let line = input.lines().nth(1).unwrap();
+    let err = ParseError::at(input, line, "missing value");
+    assert_eq!((err.line, err.column), (2, 1));
+    assert!(
+        err.to_string()
+            .ends_with("CRLF line endings, convert them to LF")
+    );
+
+    let err = ParseError::end("noop\naddx", "missing value");
+    assert_eq!((err.line, err.column), (2, 5));
+    assert!(err.to_string().ends_with("it may be truncated"));
+}
@@ -0,0 +1,484 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// Integer coordinates that points, rectangles and transforms can be built from.
+pub trait Coord:
+    Copy + Ord + Default + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn to_f64(self) -> f64;
+
+    fn abs_diff(self, other: Self) -> Self {
+        if self > other {
+            self - other
+        } else {
+            other - self
+        }
+    }
+}
+
+macro_rules! impl_coord {
+    ($($type:ty)+) => {
+        $(
+            impl Coord for $type {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )+
+    };
+}
+
+impl_coord!(i8 u8 i16 u16 i32 u32 i64 u64 i128 u128 isize usize);
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point4<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+    pub w: T,
+}
+
+macro_rules! impl_point {
+    ($point:ident { $($field:ident),+ }) => {
+        impl<T> $point<T> {
+            pub const fn new($($field: T),+) -> Self {
+                $point { $($field),+ }
+            }
+
+            pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> $point<U> {
+                $point { $($field: f(self.$field)),+ }
+            }
+        }
+
+        impl<T: Coord> $point<T> {
+            pub fn manhattan(self, other: Self) -> T {
+                T::ZERO $(+ self.$field.abs_diff(other.$field))+
+            }
+
+            pub fn chebyshev(self, other: Self) -> T {
+                T::ZERO $(.max(self.$field.abs_diff(other.$field)))+
+            }
+
+            /// Squared straight line distance, for comparing distances without floats.
+            pub fn euclidean_squared(self, other: Self) -> T {
+                T::ZERO $(+ self.$field.abs_diff(other.$field) * self.$field.abs_diff(other.$field))+
+            }
+
+            pub fn euclidean(self, other: Self) -> f64 {
+                (0.0 $(+ self.$field.abs_diff(other.$field).to_f64().powi(2))+).sqrt()
+            }
+
+            /// Component-wise minimum, the corner of the box holding both points.
+            pub fn min_components(self, other: Self) -> Self {
+                $point { $($field: self.$field.min(other.$field)),+ }
+            }
+
+            pub fn max_components(self, other: Self) -> Self {
+                $point { $($field: self.$field.max(other.$field)),+ }
+            }
+        }
+
+        impl_point_op!($point { $($field),+ }, Sub, sub, -);
+        impl_point_op!($point { $($field),+ }, Add, add, +);
+        impl_point_op!($point { $($field),+ }, Mul, mul, *);
+        impl_point_op!($point { $($field),+ }, Div, div, /);
+        impl_point_op!($point { $($field),+ }, Rem, rem, %);
+
+        impl_point_op_assign!($point { $($field),+ }, SubAssign, sub_assign, -=);
+        impl_point_op_assign!($point { $($field),+ }, AddAssign, add_assign, +=);
+        impl_point_op_assign!($point { $($field),+ }, MulAssign, mul_assign, *=);
+        impl_point_op_assign!($point { $($field),+ }, DivAssign, div_assign, /=);
+        impl_point_op_assign!($point { $($field),+ }, RemAssign, rem_assign, %=);
+
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = Self;
+            fn neg(self) -> Self::Output {
+                $point { $($field: -self.$field),+ }
+            }
+        }
+    };
+}
+
+macro_rules! impl_point_op {
+    ($point:ident { $($field:ident),+ }, $name:ident, $func:ident, $op:tt) => {
+        impl<T: std::ops::$name<Output = T>> std::ops::$name for $point<T> {
+            type Output = Self;
+            fn $func(self, rhs: Self) -> Self::Output {
+                $point { $($field: self.$field $op rhs.$field),+ }
+            }
+        }
+
+        impl<T: std::ops::$name<Output = T> + Copy> std::ops::$name<T> for $point<T> {
+            type Output = Self;
+            fn $func(self, rhs: T) -> Self::Output {
+                $point { $($field: self.$field $op rhs),+ }
+            }
+        }
+    };
+}
+
+macro_rules! impl_point_op_assign {
+    ($point:ident { $($field:ident),+ }, $name:ident, $func:ident, $op:tt) => {
+        impl<T: std::ops::$name> std::ops::$name for $point<T> {
+            fn $func(&mut self, rhs: Self) {
+                $(self.$field $op rhs.$field;)+
+            }
+        }
+
+        impl<T: std::ops::$name + Copy> std::ops::$name<T> for $point<T> {
+            fn $func(&mut self, rhs: T) {
+                $(self.$field $op rhs;)+
+            }
+        }
+    };
+}
+
+impl_point!(Point2 { x, y });
+impl_point!(Point3 { x, y, z });
+impl_point!(Point4 { x, y, z, w });
+
+impl<T: Coord + Neg<Output = T>> Point2<T> {
+    /// Quarter turn clockwise around the origin, with `y` growing downwards like the puzzle
+    /// inputs.
+    pub fn rotate_right(self) -> Self {
+        Point2::new(-self.y, self.x)
+    }
+
+    pub fn rotate_left(self) -> Self {
+        Point2::new(self.y, -self.x)
+    }
+
+    /// Mirror across the `x` axis, top to bottom.
+    pub fn reflect_y(self) -> Self {
+        Point2::new(self.x, -self.y)
+    }
+
+    pub fn reflect_x(self) -> Self {
+        Point2::new(-self.x, self.y)
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Point2<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Point3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+/// One of the 8 ways to rotate and reflect a square, the reflection applies first.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Transform {
+    pub flipped: bool,
+    /// Quarter turns counter clockwise.
+    pub turns: u8,
+}
+
+impl Transform {
+    pub const IDENTITY: Transform = Transform::new(false, 0);
+
+    pub const ALL: [Transform; 8] = [
+        Transform::new(false, 0),
+        Transform::new(false, 1),
+        Transform::new(false, 2),
+        Transform::new(false, 3),
+        Transform::new(true, 0),
+        Transform::new(true, 1),
+        Transform::new(true, 2),
+        Transform::new(true, 3),
+    ];
+
+    pub const fn new(flipped: bool, turns: u8) -> Self {
+        Self {
+            flipped,
+            turns: turns % 4,
+        }
+    }
+
+    /// Transform around the origin.
+    pub fn apply<T: Coord + Neg<Output = T>>(self, p: Point2<T>) -> Point2<T> {
+        let mut p = if self.flipped { p.reflect_y() } else { p };
+        for _ in 0..self.turns {
+            p = p.rotate_left();
+        }
+
+        p
+    }
+
+    /// Transform a point of a `size` by `size` square with its top left corner at the origin,
+    /// such as a tile of the input, keeping it within the square.
+    pub fn apply_within<T: Coord>(self, p: Point2<T>, size: T) -> Point2<T> {
+        let last = size - T::ONE;
+        let mut p = if self.flipped {
+            Point2::new(p.x, last - p.y)
+        } else {
+            p
+        };
+        for _ in 0..self.turns {
+            p = Point2::new(p.y, last - p.x);
+        }
+
+        p
+    }
+}
+
+/// Axis aligned rectangle including both corners.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Rect<T> {
+    pub min: Point2<T>,
+    pub max: Point2<T>,
+}
+
+/// Axis aligned box including both corners.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Cuboid<T> {
+    pub min: Point3<T>,
+    pub max: Point3<T>,
+}
+
+macro_rules! impl_bounds {
+    ($bounds:ident, $point:ident { $($field:ident),+ }) => {
+        impl<T: Coord> $bounds<T> {
+            /// The bounds with `a` and `b` as opposite corners, in any order.
+            pub fn new(a: $point<T>, b: $point<T>) -> Self {
+                Self {
+                    min: a.min_components(b),
+                    max: a.max_components(b),
+                }
+            }
+
+            /// The smallest bounds holding every point, `None` when there are no points.
+            pub fn bounding(points: impl IntoIterator<Item = $point<T>>) -> Option<Self> {
+                let mut points = points.into_iter();
+                let first = points.next()?;
+
+                Some(points.fold(Self::new(first, first), |bounds, p| bounds.expand(p)))
+            }
+
+            pub fn expand(self, p: $point<T>) -> Self {
+                Self {
+                    min: self.min.min_components(p),
+                    max: self.max.max_components(p),
+                }
+            }
+
+            pub fn contains(&self, p: $point<T>) -> bool {
+                $(self.min.$field <= p.$field && p.$field <= self.max.$field)&&+
+            }
+
+            pub fn intersection(&self, other: &Self) -> Option<Self> {
+                let min = self.min.max_components(other.min);
+                let max = self.max.min_components(other.max);
+
+                ($(min.$field <= max.$field)&&+).then_some(Self { min, max })
+            }
+
+            pub fn intersects(&self, other: &Self) -> bool {
+                self.intersection(other).is_some()
+            }
+
+            /// The number of points of each axis.
+            pub fn size(&self) -> $point<T> {
+                $point { $($field: self.max.$field - self.min.$field + T::ONE),+ }
+            }
+        }
+    };
+}
+
+impl_bounds!(Rect, Point2 { x, y });
+impl_bounds!(Cuboid, Point3 { x, y, z });
+
+impl<T: Coord> Rect<T> {
+    pub fn area(&self) -> T {
+        let size = self.size();
+        size.x * size.y
+    }
+}
+
+impl<T: Coord> Cuboid<T> {
+    pub fn volume(&self) -> T {
+        let size = self.size();
+        size.x * size.y * size.z
+    }
+}
+
+/// Hexagon in axial coordinates, with east along `q` and the rows along `r`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Hex {
+    pub q: i32,
+    pub r: i32,
+}
+
+impl Hex {
+    pub const fn new(q: i32, r: i32) -> Self {
+        Self { q, r }
+    }
+
+    pub fn step(self, direction: HexDir) -> Self {
+        self + direction.offset()
+    }
+
+    pub fn neighbors(self) -> impl Iterator<Item = Self> {
+        HexDir::ALL.into_iter().map(move |d| self.step(d))
+    }
+
+    /// The implicit third cube coordinate, `q + r + s == 0`.
+    pub fn s(self) -> i32 {
+        -self.q - self.r
+    }
+
+    pub fn distance(self, other: Self) -> u32 {
+        let d = self - other;
+        (d.q.unsigned_abs() + d.r.unsigned_abs() + d.s().unsigned_abs()) / 2
+    }
+}
+
+impl Add for Hex {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Hex::new(self.q + rhs.q, self.r + rhs.r)
+    }
+}
+
+impl Sub for Hex {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Hex::new(self.q - rhs.q, self.r - rhs.r)
+    }
+}
+
+/// Directions between hexagons with pointy tops.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum HexDir {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+impl HexDir {
+    pub const ALL: [HexDir; 6] = [
+        HexDir::East,
+        HexDir::SouthEast,
+        HexDir::SouthWest,
+        HexDir::West,
+        HexDir::NorthWest,
+        HexDir::NorthEast,
+    ];
+
+    pub fn offset(self) -> Hex {
+        match self {
+            HexDir::East => Hex::new(1, 0),
+            HexDir::SouthEast => Hex::new(0, 1),
+            HexDir::SouthWest => Hex::new(-1, 1),
+            HexDir::West => Hex::new(-1, 0),
+            HexDir::NorthWest => Hex::new(0, -1),
+            HexDir::NorthEast => Hex::new(1, -1),
+        }
+    }
+
+    /// Splits the direction written as `e`, `se`, `sw`, `w`, `nw` or `ne` from the start of
+    /// `s`, returning the rest.
+    pub fn split_prefix(s: &str) -> Option<(Self, &str)> {
+        let dirs = [
+            ("e", HexDir::East),
+            ("se", HexDir::SouthEast),
+            ("sw", HexDir::SouthWest),
+            ("w", HexDir::West),
+            ("nw", HexDir::NorthWest),
+            ("ne", HexDir::NorthEast),
+        ];
+
+        dirs.into_iter()
+            .find_map(|(name, dir)| Some((dir, s.strip_prefix(name)?)))
+    }
+
+    /// Every direction of a path written without separators, such as `esenee`.
+    pub fn parse_path(mut s: &str) -> Option<Vec<Self>> {
+        let mut path = Vec::new();
+        while !s.is_empty() {
+            let (dir, rest) = Self::split_prefix(s)?;
+            path.push(dir);
+            s = rest;
+        }
+
+        Some(path)
+    }
+}
+
+#[test]
+fn test_geometry() {
+    let a = Point3::new(1, -2, 3);
+    let b = Point3::new(-1, 2, 3);
+    assert_eq!(a + b, Point3::new(0, 0, 6));
+    assert_eq!(a * 2, Point3::new(2, -4, 6));
+    assert_eq!(a.manhattan(b), 6);
+    assert_eq!(a.chebyshev(b), 4);
+    assert_eq!(Point2::new(0, 0).euclidean(Point2::new(3, 4)), 5.0);
+    assert_eq!(
+        Point2::new(0u32, 0).euclidean_squared(Point2::new(3, 4)),
+        25
+    );
+    assert_eq!(
+        Point4::new(1u8, 2, 3, 4).manhattan(Point4::new(4, 3, 2, 1)),
+        8
+    );
+
+    let p = Point2::new(2, 1);
+    assert_eq!(p.rotate_right(), Point2::new(-1, 2));
+    assert_eq!(p.rotate_right().rotate_left(), p);
+    let transformed: crate::HashSet<_> = Transform::ALL.iter().map(|t| t.apply(p)).collect();
+    assert_eq!(transformed.len(), 8);
+    assert_eq!(
+        Transform::new(false, 1).apply_within(Point2::new(0, 0), 3),
+        Point2::new(0, 2)
+    );
+    assert_eq!(
+        Transform::new(true, 0).apply_within(Point2::new(1, 0), 3u32),
+        Point2::new(1, 2)
+    );
+
+    let rect = Rect::new(Point2::new(3, 4), Point2::new(0, 0));
+    let other = Rect::new(Point2::new(2, 2), Point2::new(5, 5));
+    assert_eq!(rect.area(), 20);
+    assert_eq!(
+        rect.intersection(&other),
+        Some(Rect::new(Point2::new(2, 2), Point2::new(3, 4)))
+    );
+    assert!(!rect.intersects(&Rect::new(Point2::new(4, 0), Point2::new(4, 0))));
+    let cuboid = Cuboid::bounding([Point3::new(0, 0, 0), Point3::new(1, 2, 3)]).unwrap();
+    assert_eq!(cuboid.volume(), 24);
+    assert!(cuboid.contains(Point3::new(1, 1, 1)));
+
+    let path = HexDir::parse_path("nwwswee").unwrap();
+    let end = path.into_iter().fold(Hex::default(), Hex::step);
+    assert_eq!(end, Hex::default());
+    assert_eq!(Hex::new(0, 0).distance(Hex::new(2, -3)), 3);
+    assert_eq!(HexDir::parse_path("ex"), None);
+}
@@ -0,0 +1,516 @@
+use crate::geometry::Point2;
+use crate::{HashMap, ParseError};
+
+/// Grid positions, `x` grows to the right and `y` downwards.
+pub type Point = Point2<i32>;
+
+impl Point {
+    pub fn step(self, direction: Direction) -> Self {
+        self + direction.offset()
+    }
+
+    /// The 4 orthogonal neighbors, in the order of `Direction::ALL`.
+    pub fn neighbors(self) -> impl Iterator<Item = Self> {
+        Direction::ALL.into_iter().map(move |d| self.step(d))
+    }
+
+    /// The 8 orthogonal and diagonal neighbors.
+    pub fn neighbors_diagonal(self) -> impl Iterator<Item = Self> {
+        [
+            (-1, -1),
+            (0, -1),
+            (1, -1),
+            (-1, 0),
+            (1, 0),
+            (-1, 1),
+            (0, 1),
+            (1, 1),
+        ]
+        .into_iter()
+        .map(move |(x, y)| self + Point::new(x, y))
+    }
+}
+
+/// Directions on screen, `Up` decreases `y`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// Parses the arrows `^>v<` and the letters `URDL`.
+    pub fn from_char(c: char) -> Option<Self> {
+        let direction = match c {
+            '^' | 'U' => Direction::Up,
+            '>' | 'R' => Direction::Right,
+            'v' | 'D' => Direction::Down,
+            '<' | 'L' => Direction::Left,
+            _ => return None,
+        };
+
+        Some(direction)
+    }
+
+    pub fn offset(self) -> Point {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Right => Point::new(1, 0),
+            Direction::Down => Point::new(0, 1),
+            Direction::Left => Point::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+        }
+    }
+
+    pub fn reverse(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Right => Direction::Left,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+        }
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Direction::Up | Direction::Down)
+    }
+}
+
+/// Dense row-major grid with its origin at the top left.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "grid cells should match its size"
+        );
+
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Parses one cell per char of each line, failing at the first char `cell` rejects or
+    /// at the first line with a different width than the first.
+    pub fn parse(input: &str, cell: impl FnMut(char) -> Option<T>) -> Result<Self, ParseError> {
+        Self::parse_within(input, input, cell)
+    }
+
+    /// Parses like `Grid::parse`, with errors reported at their position in `source`, which
+    /// `input` should be a slice of.
+    pub fn parse_within(
+        source: &str,
+        input: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut width = None;
+        let mut height = 0;
+
+        // trailing spaces are cells, only the line breaks after the last row are dropped
+        for line in input.trim_end_matches(['\n', '\r']).lines() {
+            let line = line.trim_end_matches('\r');
+            let mut line_width = 0;
+            for (idx, c) in line.char_indices() {
+                let value = cell(c).ok_or_else(|| {
+                    ParseError::at(source, &line[idx..], format!("unexpected cell '{}'", c))
+                })?;
+                cells.push(value);
+                line_width += 1;
+            }
+
+            match width {
+                None => width = Some(line_width),
+                Some(width) if width != line_width => {
+                    return Err(ParseError::at(
+                        source,
+                        line,
+                        format!("expected {} cells but found {}", width, line_width),
+                    ));
+                }
+                Some(_) => (),
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            cells,
+            width: width.unwrap_or(0),
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        p.x >= 0 && p.y >= 0 && (p.x as usize) < self.width && (p.y as usize) < self.height
+    }
+
+    pub fn index(&self, p: Point) -> Option<usize> {
+        self.contains(p)
+            .then(|| p.y as usize * self.width + p.x as usize)
+    }
+
+    pub fn point(&self, index: usize) -> Point {
+        Point::new((index % self.width) as i32, (index / self.width) as i32)
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.index(p).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.index(p).map(|idx| &mut self.cells[idx])
+    }
+
+    /// Stores `value` at `p`, returning false when `p` is out of bounds.
+    pub fn set(&mut self, p: Point, value: T) -> bool {
+        match self.get_mut(p) {
+            Some(cell) => {
+                *cell = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Every point in row-major order.
+    pub fn points(&self) -> impl Iterator<Item = Point> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point::new(x as i32, y as i32)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// First point in row-major order whose cell matches.
+    pub fn position(&self, f: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.cells.iter().position(f).map(|idx| self.point(idx))
+    }
+
+    /// The orthogonal neighbors of `p` that are within the grid.
+    pub fn neighbors(&self, p: Point) -> impl Iterator<Item = Point> {
+        p.neighbors().filter(|n| self.contains(*n))
+    }
+
+    /// The orthogonal and diagonal neighbors of `p` that are within the grid.
+    pub fn neighbors_diagonal(&self, p: Point) -> impl Iterator<Item = Point> {
+        p.neighbors_diagonal().filter(|n| self.contains(*n))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |x, y| (y, x))
+    }
+
+    pub fn rotate_right(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |x, y| (y, self.height - 1 - x))
+    }
+
+    pub fn rotate_left(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |x, y| (self.width - 1 - y, x))
+    }
+
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.width, self.height, |x, y| (self.width - 1 - x, y))
+    }
+
+    pub fn flip_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.width, self.height, |x, y| (x, self.height - 1 - y))
+    }
+
+    // builds a `width` by `height` grid where the cell at x,y is taken from `source(x, y)`
+    fn remap(
+        &self,
+        width: usize,
+        height: usize,
+        source: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Self
+    where
+        T: Clone,
+    {
+        let mut cells = Vec::with_capacity(self.cells.len());
+        for y in 0..height {
+            for x in 0..width {
+                let (sx, sy) = source(x, y);
+                cells.push(self.cells[sy * self.width + sx].clone());
+            }
+        }
+
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Draws the grid one char per cell, for debug dumps of cells without a `Display`.
+    pub fn render(&self, mut f: impl FnMut(&T) -> char) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            out.extend(row.iter().map(&mut f));
+            out.push('\n');
+        }
+
+        out
+    }
+}
+
+impl<T> std::ops::Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        self.get(p).expect("point within grid")
+    }
+}
+
+impl<T> std::ops::IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        self.get_mut(p).expect("point within grid")
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Unbounded grid for puzzles that grow without limit, cells that were never set read as
+/// the default value.
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    default: T,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new(default: T) -> Self {
+        Self {
+            cells: HashMap::new(),
+            default,
+        }
+    }
+
+    /// Parses like `Grid::parse`, only cells that differ from `default` are stored.
+    pub fn parse(
+        input: &str,
+        default: T,
+        cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError>
+    where
+        T: PartialEq,
+    {
+        let grid = Grid::parse(input, cell)?;
+        let mut sparse = Self::new(default);
+        let points = grid.points();
+        for (p, value) in points.zip(grid.cells) {
+            sparse.set(p, value);
+        }
+
+        Ok(sparse)
+    }
+
+    pub fn get(&self, p: Point) -> &T {
+        self.cells.get(&p).unwrap_or(&self.default)
+    }
+
+    pub fn set(&mut self, p: Point, value: T)
+    where
+        T: PartialEq,
+    {
+        if value == self.default {
+            self.cells.remove(&p);
+        } else {
+            self.cells.insert(p, value);
+        }
+    }
+
+    /// Number of cells that differ from the default value.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(p, v)| (*p, v))
+    }
+
+    /// The smallest and largest corner of the area holding every set cell.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        let mut points = self.cells.keys();
+        let first = *points.next()?;
+
+        Some(points.fold((first, first), |(min, max), p| {
+            (
+                Point::new(min.x.min(p.x), min.y.min(p.y)),
+                Point::new(max.x.max(p.x), max.y.max(p.y)),
+            )
+        }))
+    }
+
+    /// Copies the area within `bounds` into a dense grid, with the min corner at 0,0.
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let Some((min, max)) = self.bounds() else {
+            return Grid::new(0, 0, self.default.clone());
+        };
+
+        let width = (max.x - min.x + 1) as usize;
+        let height = (max.y - min.y + 1) as usize;
+        let mut grid = Grid::new(width, height, self.default.clone());
+        for (p, value) in self.iter() {
+            grid[p - min] = value.clone();
+        }
+
+        grid
+    }
+}
+
+impl<T: std::fmt::Display + Clone> std::fmt::Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.to_grid().fmt(f)
+    }
+}
+
+#[test]
+fn test_grid() {
+    let input = "#..\n.#.\n";
+    let grid = Grid::parse(input, |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
+    .unwrap();
+
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid.get(Point::new(1, 1)), Some(&true));
+    assert_eq!(grid.get(Point::new(3, 0)), None);
+    assert_eq!(grid.neighbors(Point::new(0, 0)).count(), 2);
+    assert_eq!(grid.neighbors_diagonal(Point::new(0, 0)).count(), 3);
+
+    let render = |g: &Grid<bool>| g.render(|&c| if c { '#' } else { '.' });
+    assert_eq!(render(&grid.transpose()), "#.\n.#\n..\n");
+    assert_eq!(render(&grid.rotate_right()), ".#\n#.\n..\n");
+    assert_eq!(render(&grid.rotate_left()), "..\n.#\n#.\n");
+    assert_eq!(grid.rotate_right().rotate_left(), grid);
+
+    let err = Grid::parse("#..\n.x.\n", |c| (c != 'x').then_some(c)).unwrap_err();
+    assert_eq!((err.line, err.column), (2, 2));
+    let err = Grid::parse("#..\n.#\n", Some).unwrap_err();
+    assert_eq!((err.line, err.column), (2, 1));
+
+    let padded = Grid::parse("#  \n#  \r\n\n", Some).unwrap();
+    assert_eq!((padded.width(), padded.height()), (3, 2));
+    assert_eq!(padded.get(Point::new(2, 1)), Some(&' '));
+}
+
+#[test]
+fn test_sparse_grid() {
+    let mut grid = SparseGrid::parse(".#\n#.\n", '.', Some).unwrap();
+    assert_eq!(grid.len(), 2);
+
+    grid.set(Point::new(-1, 3), '#');
+    grid.set(Point::new(1, 0), '.');
+    assert_eq!(*grid.get(Point::new(-1, 3)), '#');
+    assert_eq!(grid.bounds(), Some((Point::new(-1, 1), Point::new(0, 3))));
+    assert_eq!(grid.to_string(), ".#\n..\n#.\n");
+
+    assert_eq!(Direction::Up.turn_right(), Direction::Right);
+    assert_eq!(Point::new(1, 1).step(Direction::Left), Point::new(0, 1));
+}
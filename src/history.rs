@@ -0,0 +1,412 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::Metrics;
+use crate::output::JsonStr;
+
+pub const HISTORY_PATH: &str = ".bench-history.jsonl";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub run: u64,
+    pub commit: String,
+    pub hostname: String,
+    pub baseline: Option<String>,
+    pub profile: Option<String>,
+    pub event: u32,
+    pub day: u32,
+    pub part: u32,
+    pub metrics: Metrics,
+}
+
+pub struct RunInfo {
+    pub run: u64,
+    pub commit: String,
+    pub hostname: String,
+    pub baseline: Option<String>,
+}
+
+impl RunInfo {
+    pub fn current(baseline: Option<String>) -> Self {
+        let run = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+
+        Self {
+            run,
+            commit: git_commit().unwrap_or_else(|| "unknown".to_string()),
+            hostname: hostname().unwrap_or_else(|| "unknown".to_string()),
+            baseline,
+        }
+    }
+
+    pub fn entry(
+        &self,
+        profile: Option<&str>,
+        event: u32,
+        day: u32,
+        part: u32,
+        metrics: Metrics,
+    ) -> Entry {
+        Entry {
+            run: self.run,
+            commit: self.commit.clone(),
+            hostname: self.hostname.clone(),
+            baseline: self.baseline.clone(),
+            profile: profile.map(str::to_string),
+            event,
+            day,
+            part,
+            metrics,
+        }
+    }
+}
+
+fn git_commit() -> Option<String> {
+    let output = std::process::Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let mut commit = String::from_utf8(output.stdout).ok()?.trim().to_string();
+
+    let status = std::process::Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .output()
+        .ok()?;
+    if !status.stdout.is_empty() {
+        commit.push_str("-dirty");
+    }
+
+    Some(commit)
+}
+
+fn hostname() -> Option<String> {
+    std::env::var("HOSTNAME")
+        .ok()
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .or_else(|| {
+            let output = std::process::Command::new("hostname").output().ok()?;
+            String::from_utf8(output.stdout).ok()
+        })
+        .map(|h| h.trim().to_string())
+        .filter(|h| !h.is_empty())
+}
+
+impl Entry {
+    fn to_json(&self) -> String {
+        let optional = |value: Option<&str>| {
+            value
+                .map(|v| JsonStr(v).to_string())
+                .unwrap_or_else(|| "null".to_string())
+        };
+
+        format!(
+            "{{\"run\":{},\"commit\":{},\"hostname\":{},\"baseline\":{},\"profile\":{},\"year\":{},\"day\":{},\"part\":{},\"duration_ns\":{},\"allocations\":{},\"peak_memory\":{},\"cycles\":{},\"instructions\":{},\"concurrent\":{}}}",
+            self.run,
+            JsonStr(&self.commit),
+            JsonStr(&self.hostname),
+            optional(self.baseline.as_deref()),
+            optional(self.profile.as_deref()),
+            self.event,
+            self.day,
+            self.part,
+            self.metrics.duration.as_nanos() as u64,
+            self.metrics.allocations,
+            self.metrics.peak_memory,
+            self.metrics.cycles,
+            self.metrics.instructions,
+            self.metrics.concurrent,
+        )
+    }
+
+    fn from_json(line: &str) -> Option<Self> {
+        let fields = parse_object(line)?;
+        let str_field = |name: &str| match fields.get(name)? {
+            Value::Str(s) => Some(s.clone()),
+            _ => None,
+        };
+        let num_field = |name: &str| match fields.get(name)? {
+            Value::Number(n) => Some(*n),
+            _ => None,
+        };
+        let bool_field = |name: &str| match fields.get(name)? {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        };
+
+        let metrics = Metrics {
+            duration: Duration::from_nanos(num_field("duration_ns")?),
+            allocations: num_field("allocations")?,
+            peak_memory: num_field("peak_memory")? as usize,
+            cycles: num_field("cycles")?,
+            instructions: num_field("instructions")?,
+            concurrent: bool_field("concurrent").unwrap_or(false),
+        };
+
+        Some(Entry {
+            run: num_field("run")?,
+            commit: str_field("commit")?,
+            hostname: str_field("hostname")?,
+            baseline: str_field("baseline"),
+            profile: str_field("profile"),
+            event: num_field("year")? as u32,
+            day: num_field("day")? as u32,
+            part: num_field("part")? as u32,
+            metrics,
+        })
+    }
+}
+
+pub fn append<P: AsRef<Path>>(path: P, entries: &[Entry]) -> std::io::Result<()> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+
+    let mut text = String::new();
+    for entry in entries {
+        text.push_str(&entry.to_json());
+        text.push('\n');
+    }
+
+    file.write_all(text.as_bytes())
+}
+
+pub fn read<P: AsRef<Path>>(path: P) -> std::io::Result<Vec<Entry>> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+
+    let mut entries = Vec::new();
+    for (idx, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match Entry::from_json(line) {
+            Some(entry) => entries.push(entry),
+            None => eprintln!("skipping malformed history entry on line {}", idx + 1),
+        }
+    }
+
+    Ok(entries)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Str(String),
+    Number(u64),
+    Bool(bool),
+    Null,
+}
+
+fn parse_object(line: &str) -> Option<BTreeMap<String, Value>> {
+    let mut chars = line.trim().chars().peekable();
+    let mut fields = BTreeMap::new();
+
+    if chars.next()? != '{' {
+        return None;
+    }
+
+    loop {
+        match chars.next()? {
+            '}' => break,
+            ',' => continue,
+            '"' => {
+                let key = parse_string(&mut chars)?;
+                if chars.next()? != ':' {
+                    return None;
+                }
+                let value = match chars.peek()? {
+                    '"' => {
+                        chars.next();
+                        Value::Str(parse_string(&mut chars)?)
+                    }
+                    't' | 'f' | 'n' => {
+                        let mut word = String::new();
+                        while let Some(c) = chars.next_if(|c| c.is_ascii_alphabetic()) {
+                            word.push(c);
+                        }
+                        match word.as_str() {
+                            "true" => Value::Bool(true),
+                            "false" => Value::Bool(false),
+                            "null" => Value::Null,
+                            _ => return None,
+                        }
+                    }
+                    _ => {
+                        let mut number = String::new();
+                        while let Some(c) = chars.next_if(|c| c.is_ascii_digit()) {
+                            number.push(c);
+                        }
+                        Value::Number(number.parse().ok()?)
+                    }
+                };
+                fields.insert(key, value);
+            }
+            _ => return None,
+        }
+    }
+
+    Some(fields)
+}
+
+fn parse_string(chars: &mut std::iter::Peekable<std::str::Chars>) -> Option<String> {
+    let mut s = String::new();
+    loop {
+        match chars.next()? {
+            '"' => return Some(s),
+            '\\' => match chars.next()? {
+                'n' => s.push('\n'),
+                'r' => s.push('\r'),
+                't' => s.push('\t'),
+                'u' => {
+                    let code: String = (0..4).filter_map(|_| chars.next()).collect();
+                    s.push(char::from_u32(u32::from_str_radix(&code, 16).ok()?)?);
+                }
+                c => s.push(c),
+            },
+            c => s.push(c),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Measure {
+    Duration,
+    Allocations,
+    PeakMemory,
+}
+
+impl Measure {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Measure::Duration => "duration",
+            Measure::Allocations => "allocations",
+            Measure::PeakMemory => "peak memory",
+        }
+    }
+
+    fn value(&self, metrics: &Metrics) -> f64 {
+        match self {
+            Measure::Duration => metrics.duration.as_nanos() as f64,
+            Measure::Allocations => metrics.allocations as f64,
+            Measure::PeakMemory => metrics.peak_memory as f64,
+        }
+    }
+}
+
+pub struct Comparison<'a> {
+    pub previous: &'a Entry,
+    pub current: &'a Entry,
+    pub regressions: Vec<(Measure, f64)>,
+}
+
+pub fn change(measure: Measure, previous: &Metrics, current: &Metrics) -> f64 {
+    let previous = measure.value(previous);
+    let current = measure.value(current);
+
+    if previous == 0.0 {
+        if current == 0.0 { 0.0 } else { f64::INFINITY }
+    } else {
+        (current - previous) / previous * 100.0
+    }
+}
+
+pub fn compare<'a>(
+    entries: &'a [Entry],
+    profile: Option<&str>,
+    event: u32,
+    day: u32,
+    part: u32,
+    baseline: Option<&str>,
+    threshold: f64,
+) -> Option<Comparison<'a>> {
+    let matching = entries
+        .iter()
+        .filter(|e| e.profile.as_deref() == profile)
+        .filter(|e| e.event == event && e.day == day && e.part == part);
+
+    let current = matching.clone().max_by_key(|e| e.run)?;
+
+    let previous = matching
+        .filter(|e| {
+            e.run < current.run
+                && e.hostname == current.hostname
+                && e.metrics.concurrent == current.metrics.concurrent
+        })
+        .filter(|e| baseline.is_none() || e.baseline.as_deref() == baseline)
+        .max_by_key(|e| e.run)?;
+
+    let regressions = [Measure::Duration, Measure::Allocations, Measure::PeakMemory]
+        .into_iter()
+        .map(|m| (m, change(m, &previous.metrics, &current.metrics)))
+        .filter(|(_, change)| *change > threshold)
+        .collect();
+
+    Some(Comparison {
+        previous,
+        current,
+        regressions,
+    })
+}
+
+#[test]
+fn test_history() {
+    let info = RunInfo {
+        run: 10,
+        commit: "abc123".to_string(),
+        hostname: "host \"one\"".to_string(),
+        baseline: None,
+    };
+
+    let metrics = Metrics {
+        duration: Duration::from_micros(100),
+        allocations: 10,
+        peak_memory: 1000,
+        ..Default::default()
+    };
+
+    let first = info.entry(None, 2022, 1, 2, metrics);
+    assert_eq!(Entry::from_json(&first.to_json()), Some(first.clone()));
+
+    let second = Entry {
+        run: 20,
+        baseline: Some("release".to_string()),
+        metrics: Metrics {
+            duration: Duration::from_micros(150),
+            allocations: 10,
+            peak_memory: 1050,
+            ..metrics
+        },
+        ..first.clone()
+    };
+    assert_eq!(Entry::from_json(&second.to_json()), Some(second.clone()));
+
+    let other_profile = info.entry(Some("alice"), 2022, 1, 2, metrics);
+    assert_eq!(
+        Entry::from_json(&other_profile.to_json()),
+        Some(other_profile.clone())
+    );
+
+    let entries = vec![first, second, other_profile];
+    let comparison = compare(&entries, None, 2022, 1, 2, None, 10.0).unwrap();
+    assert_eq!(comparison.previous.run, 10);
+    assert_eq!(comparison.regressions.len(), 1);
+    assert_eq!(comparison.regressions[0].0, Measure::Duration);
+
+    assert!(compare(&entries, None, 2022, 1, 2, Some("release"), 10.0).is_none());
+    assert!(compare(&entries, None, 2022, 1, 1, None, 10.0).is_none());
+    assert!(compare(&entries, Some("alice"), 2022, 1, 2, None, 10.0).is_none());
+}
@@ -1,39 +1,511 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::Puzzle;
 use crate::encryption::Encryption;
 
-pub struct Input {
+const STORE_ENV: &str = "AOC_INPUT_STORE";
+const ROOT_ENV: &str = "AOC_INPUT_DIR";
+const DEFAULT_ROOT: &str = "input";
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Entry {
+    Input(u32),
+    Puzzle(u32),
+    Answers,
+}
+
+impl Entry {
+    fn name(&self) -> String {
+        match self {
+            Entry::Input(day) => format!("day_{:02}", day),
+            Entry::Puzzle(day) => format!("day_{:02}.puzzle", day),
+            Entry::Answers => "answers".to_string(),
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        if name == "answers" {
+            return Some(Entry::Answers);
+        }
+
+        let day = name.strip_prefix("day_")?;
+        match day.strip_suffix(".puzzle") {
+            Some(day) => Some(Entry::Puzzle(day.parse().ok()?)),
+            None => Some(Entry::Input(day.parse().ok()?)),
+        }
+    }
+
+    fn plain_extension(&self) -> &'static str {
+        match self {
+            Entry::Puzzle(_) => "html",
+            Entry::Input(_) | Entry::Answers => "txt",
+        }
+    }
+}
+
+pub trait InputStore: Send + Sync {
+    fn read(&self, event: u32, entry: Entry)
+    -> Result<Option<Vec<u8>>, Box<dyn std::error::Error>>;
+
+    fn write(
+        &self,
+        event: u32,
+        entry: Entry,
+        data: &[u8],
+    ) -> Result<(), Box<dyn std::error::Error>>;
+}
+
+fn write_file(path: &Path, data: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
+    std::fs::create_dir_all(path.parent().expect("input path should have parent"))?;
+    std::fs::write(path, data)?;
+
+    Ok(())
+}
+
+fn read_file(path: &Path) -> Result<Option<Vec<u8>>, Box<dyn std::error::Error>> {
+    match std::fs::read(path) {
+        Ok(data) => Ok(Some(data)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+pub struct PlainDirStore {
+    root: PathBuf,
+}
+
+impl PlainDirStore {
+    pub fn new<P: Into<PathBuf>>(root: P) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path(&self, event: u32, entry: Entry) -> PathBuf {
+        self.root.join(format!("year_{}", event)).join(format!(
+            "{}.{}",
+            entry.name(),
+            entry.plain_extension()
+        ))
+    }
+}
+
+impl InputStore for PlainDirStore {
+    fn read(
+        &self,
+        event: u32,
+        entry: Entry,
+    ) -> Result<Option<Vec<u8>>, Box<dyn std::error::Error>> {
+        read_file(&self.path(event, entry))
+    }
+
+    fn write(
+        &self,
+        event: u32,
+        entry: Entry,
+        data: &[u8],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        write_file(&self.path(event, entry), data)
+    }
+}
+
+pub struct EncryptedDirStore {
+    plain: PlainDirStore,
+    encryption: Encryption,
+}
+
+impl EncryptedDirStore {
+    pub fn new<P: Into<PathBuf>>(root: P, encryption: Encryption) -> Self {
+        Self {
+            plain: PlainDirStore::new(root),
+            encryption,
+        }
+    }
+
+    fn path(&self, event: u32, entry: Entry) -> PathBuf {
+        self.plain
+            .root
+            .join(format!("year_{}", event))
+            .join(format!("{}.aes", entry.name()))
+    }
+}
+
+impl InputStore for EncryptedDirStore {
+    fn read(
+        &self,
+        event: u32,
+        entry: Entry,
+    ) -> Result<Option<Vec<u8>>, Box<dyn std::error::Error>> {
+        let Some(data) = read_file(&self.path(event, entry))? else {
+            return self.plain.read(event, entry);
+        };
+
+        let legacy = Encryption::is_legacy(&data);
+        let data = self.encryption.decrypt_entry(event, entry, data)?;
+
+        // the answers are rewritten on every submit, replace the fixed nonce before that happens
+        if legacy && entry == Entry::Answers {
+            self.write(event, entry, &data)?;
+        }
+
+        Ok(Some(data))
+    }
+
+    fn write(
+        &self,
+        event: u32,
+        entry: Entry,
+        data: &[u8],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let data = self.encryption.encrypt_entry(event, entry, data.to_vec())?;
+        write_file(&self.path(event, entry), &data)
+    }
+}
+
+const ARCHIVE_MAGIC: &[u8] = b"advent-archive 1\n";
+
+pub struct ArchiveStore {
+    root: PathBuf,
     encryption: Option<Encryption>,
+    lock: Mutex<()>,
 }
 
-impl Input {
-    pub fn new() -> Self {
+impl ArchiveStore {
+    pub fn new<P: Into<PathBuf>>(root: P, encryption: Option<Encryption>) -> Self {
         Self {
-            encryption: Encryption::new(),
+            root: root.into(),
+            encryption,
+            lock: Mutex::new(()),
         }
     }
 
-    pub fn read(&self, event: u32, day: u32) -> Result<Option<String>, Box<dyn std::error::Error>> {
-        if let Some(enc) = self.encryption.as_ref() {
-            let path = std::path::PathBuf::from(format!("input/year_{}/day_{:02}.aes", event, day));
+    fn path(&self, event: u32) -> PathBuf {
+        let extension = if self.encryption.is_some() {
+            "aes"
+        } else {
+            "archive"
+        };
+        self.root.join(format!("year_{}.{}", event, extension))
+    }
+
+    fn load(&self, event: u32) -> Result<BTreeMap<Entry, Vec<u8>>, Box<dyn std::error::Error>> {
+        let Some(data) = read_file(&self.path(event))? else {
+            return Ok(BTreeMap::new());
+        };
 
-            if path.exists() {
-                let data = std::fs::read(path)?;
+        let data = match self.encryption.as_ref() {
+            Some(enc) if Encryption::is_legacy(&data) => {
+                let data = enc.decrypt_archive(event, data)?;
+                write_file(
+                    &self.path(event),
+                    &enc.encrypt_archive(event, data.clone())?,
+                )?;
+                data
+            }
+            Some(enc) => enc.decrypt_archive(event, data)?,
+            None => data,
+        };
 
-                let bytes = enc.decrypt_day(event, day, data)?;
-                let input = String::from_utf8(bytes)?;
+        parse_archive(&data).ok_or_else(|| format!("malformed archive for {}", event).into())
+    }
 
-                return Ok(Some(input));
+    fn store(
+        &self,
+        event: u32,
+        entries: &BTreeMap<Entry, Vec<u8>>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let data = serialize_archive(entries);
+        let data = match self.encryption.as_ref() {
+            Some(enc) => enc.encrypt_archive(event, data)?,
+            None => data,
+        };
+
+        write_file(&self.path(event), &data)
+    }
+}
+
+fn parse_archive(mut data: &[u8]) -> Option<BTreeMap<Entry, Vec<u8>>> {
+    data = data.strip_prefix(ARCHIVE_MAGIC)?;
+    let mut entries = BTreeMap::new();
+
+    while !data.is_empty() {
+        let header_end = data.iter().position(|&b| b == b'\n')?;
+        let header = std::str::from_utf8(&data[..header_end]).ok()?;
+        let (name, len) = header.split_once(' ')?;
+        let len: usize = len.parse().ok()?;
+
+        data = &data[header_end + 1..];
+        if data.len() < len {
+            return None;
+        }
+
+        entries.insert(Entry::from_name(name)?, data[..len].to_vec());
+        data = &data[len..];
+    }
+
+    Some(entries)
+}
+
+fn serialize_archive(entries: &BTreeMap<Entry, Vec<u8>>) -> Vec<u8> {
+    let mut data = ARCHIVE_MAGIC.to_vec();
+    for (entry, bytes) in entries {
+        data.extend_from_slice(format!("{} {}\n", entry.name(), bytes.len()).as_bytes());
+        data.extend_from_slice(bytes);
+    }
+    data
+}
+
+impl InputStore for ArchiveStore {
+    fn read(
+        &self,
+        event: u32,
+        entry: Entry,
+    ) -> Result<Option<Vec<u8>>, Box<dyn std::error::Error>> {
+        let _lock = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(self.load(event)?.remove(&entry))
+    }
+
+    fn write(
+        &self,
+        event: u32,
+        entry: Entry,
+        data: &[u8],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let _lock = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut entries = self.load(event)?;
+        entries.insert(entry, data.to_vec());
+        self.store(event, &entries)
+    }
+}
+
+#[derive(Default)]
+pub struct MemoryStore {
+    entries: Mutex<BTreeMap<(u32, Entry), Vec<u8>>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl InputStore for MemoryStore {
+    fn read(
+        &self,
+        event: u32,
+        entry: Entry,
+    ) -> Result<Option<Vec<u8>>, Box<dyn std::error::Error>> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(entries.get(&(event, entry)).cloned())
+    }
+
+    fn write(
+        &self,
+        event: u32,
+        entry: Entry,
+        data: &[u8],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.insert((event, entry), data.to_vec());
+        Ok(())
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StoreKind {
+    Encrypted,
+    Plain,
+    Archive,
+    Memory,
+}
+
+impl StoreKind {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "encrypted" => Ok(StoreKind::Encrypted),
+            "plain" => Ok(StoreKind::Plain),
+            "archive" => Ok(StoreKind::Archive),
+            "memory" => Ok(StoreKind::Memory),
+            _ => Err(format!(
+                "invalid input store '{}', expected 'encrypted', 'plain', 'archive' or 'memory'",
+                name
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoreConfig {
+    pub kind: Option<StoreKind>,
+    pub root: PathBuf,
+}
+
+impl StoreConfig {
+    pub fn from_env() -> Result<Self, Box<dyn std::error::Error>> {
+        let kind = match std::env::var(STORE_ENV) {
+            Ok(kind) if !kind.is_empty() => Some(StoreKind::parse(&kind)?),
+            _ => None,
+        };
+
+        let root = match std::env::var_os(ROOT_ENV) {
+            Some(root) if !root.is_empty() => PathBuf::from(root),
+            _ => default_root(),
+        };
+
+        Ok(Self { kind, root })
+    }
+
+    pub fn open(&self) -> Result<Arc<dyn InputStore>, Box<dyn std::error::Error>> {
+        let encryption = Encryption::new()?;
+
+        let store: Arc<dyn InputStore> = match (self.kind, encryption) {
+            (Some(StoreKind::Encrypted), None) => {
+                return Err("the encrypted input store requires an input key".into());
+            }
+            (Some(StoreKind::Encrypted) | None, Some(enc)) => {
+                Arc::new(EncryptedDirStore::new(&self.root, enc))
             }
+            (Some(StoreKind::Plain), _) | (None, None) => Arc::new(PlainDirStore::new(&self.root)),
+            (Some(StoreKind::Archive), enc) => Arc::new(ArchiveStore::new(&self.root, enc)),
+            (Some(StoreKind::Memory), _) => Arc::new(MemoryStore::new()),
+        };
+
+        Ok(store)
+    }
+}
+
+enum Encrypted {
+    Entry(u32, Entry),
+    Archive(u32),
+}
+
+fn encrypted_files(root: &Path) -> Result<Vec<(PathBuf, Encrypted)>, Box<dyn std::error::Error>> {
+    let mut files = Vec::new();
+
+    for dir_entry in std::fs::read_dir(root)? {
+        let path = dir_entry?.path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        let Some(event) = name.strip_prefix("year_") else {
+            continue;
+        };
+
+        if let Some(event) = event.strip_suffix(".aes") {
+            if let Ok(event) = event.parse() {
+                files.push((path.clone(), Encrypted::Archive(event)));
+            }
+            continue;
         }
 
-        let path = std::path::PathBuf::from(format!("input/year_{}/day_{:02}.txt", event, day));
+        let Ok(event) = event.parse() else {
+            continue;
+        };
+        if !path.is_dir() {
+            continue;
+        }
 
-        if path.exists() {
-            let input = std::fs::read_to_string(&path)?;
+        for dir_entry in std::fs::read_dir(&path)? {
+            let path = dir_entry?.path();
+            let entry = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.strip_suffix(".aes"))
+                .and_then(Entry::from_name);
 
-            Ok(Some(input))
-        } else {
-            Ok(None)
+            if let Some(entry) = entry {
+                files.push((path, Encrypted::Entry(event, entry)));
+            }
+        }
+    }
+
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(files)
+}
+
+/// Re-encrypts the encrypted files under every root that exists. Nothing is written until every
+/// file is decrypted with `old`, so a failure leaves all of them on the old key.
+pub fn rekey(
+    roots: &[&Path],
+    old: &Encryption,
+    new: &Encryption,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let mut files = Vec::new();
+    for root in roots.iter().filter(|root| root.is_dir()) {
+        files.extend(encrypted_files(root)?);
+    }
+    let mut rekeyed = Vec::with_capacity(files.len());
+
+    for (path, kind) in files {
+        let data = std::fs::read(&path)?;
+        let data = match kind {
+            Encrypted::Entry(event, entry) => old
+                .decrypt_entry(event, entry, data)
+                .and_then(|data| new.encrypt_entry(event, entry, data)),
+            Encrypted::Archive(event) => old
+                .decrypt_archive(event, data)
+                .and_then(|data| new.encrypt_archive(event, data)),
         }
+        .map_err(|err| format!("unable to rekey '{}'. {}", path.display(), err))?;
+
+        rekeyed.push((path, data));
+    }
+
+    for (path, data) in rekeyed.iter() {
+        let tmp_path = path.with_extension("aes.tmp");
+        std::fs::write(&tmp_path, data)?;
+        std::fs::rename(&tmp_path, path)?;
+    }
+
+    Ok(rekeyed.len())
+}
+
+/// The `input` directory in the working directory, or next to the manifest when only that one exists.
+pub fn default_root() -> PathBuf {
+    let root = PathBuf::from(DEFAULT_ROOT);
+    if root.exists() {
+        return root;
+    }
+
+    let manifest_root = Path::new(env!("CARGO_MANIFEST_DIR")).join(DEFAULT_ROOT);
+    if manifest_root.exists() {
+        manifest_root
+    } else {
+        root
+    }
+}
+
+#[derive(Clone)]
+pub struct Input {
+    store: Arc<dyn InputStore>,
+}
+
+impl Input {
+    pub fn from_env() -> Result<Self, Box<dyn std::error::Error>> {
+        Self::open(&StoreConfig::from_env()?)
+    }
+
+    pub fn open(config: &StoreConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self::with_store(config.open()?))
+    }
+
+    pub fn with_store(store: Arc<dyn InputStore>) -> Self {
+        Self { store }
+    }
+
+    fn read_text(
+        &self,
+        event: u32,
+        entry: Entry,
+    ) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        match self.store.read(event, entry)? {
+            Some(data) => Ok(Some(String::from_utf8(data)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn read(&self, event: u32, day: u32) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        self.read_text(event, Entry::Input(day))
     }
 
     pub fn save(
@@ -42,23 +514,118 @@ impl Input {
         day: u32,
         input: &str,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        let (bytes, path) = if let Some(enc) = self.encryption.as_ref() {
-            let path = std::path::PathBuf::from(format!("input/year_{}/day_{:02}.aes", event, day));
+        self.store.write(event, Entry::Input(day), input.as_bytes())
+    }
 
-            let data = input.as_bytes().to_vec();
-            let data = enc.encrypt_day(event, day, data)?;
+    pub fn read_puzzle(
+        &self,
+        event: u32,
+        day: u32,
+    ) -> Result<Option<Puzzle>, Box<dyn std::error::Error>> {
+        Ok(self.read_text(event, Entry::Puzzle(day))?.map(Puzzle::new))
+    }
 
-            (data, path)
-        } else {
-            let path = std::path::PathBuf::from(format!("input/year_{}/day_{:02}.txt", event, day));
+    pub fn save_puzzle(
+        &self,
+        event: u32,
+        day: u32,
+        puzzle: &Puzzle,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.store
+            .write(event, Entry::Puzzle(day), puzzle.html().as_bytes())
+    }
 
-            let data = input.as_bytes().to_vec();
-            (data, path)
-        };
+    pub fn read_answers(&self, event: u32) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        self.read_text(event, Entry::Answers)
+    }
 
-        std::fs::create_dir_all(path.parent().expect("input path should have parent"))?;
-        std::fs::write(&path, &bytes)?;
+    pub fn save_answers(&self, event: u32, text: &str) -> Result<(), Box<dyn std::error::Error>> {
+        self.store.write(event, Entry::Answers, text.as_bytes())
+    }
+}
 
-        Ok(())
+#[test]
+fn test_input_stores() {
+    let root = std::env::temp_dir().join(format!("advent-input-test-{}", std::process::id()));
+
+    let stores: Vec<Arc<dyn InputStore>> = vec![
+        Arc::new(MemoryStore::new()),
+        Arc::new(PlainDirStore::new(root.join("plain"))),
+        Arc::new(ArchiveStore::new(root.join("archive"), None)),
+    ];
+
+    for store in stores {
+        let input = Input::with_store(store);
+        assert_eq!(input.read(2022, 1).unwrap(), None);
+
+        input.save(2022, 1, "1000\n2000\n").unwrap();
+        input.save(2022, 12, "abc\n").unwrap();
+        input.save_answers(2022, "1 1 ok 3000\n").unwrap();
+        input.save(2022, 1, "1000\n").unwrap();
+
+        assert_eq!(input.read(2022, 1).unwrap().as_deref(), Some("1000\n"));
+        assert_eq!(input.read(2022, 12).unwrap().as_deref(), Some("abc\n"));
+        assert_eq!(input.read(2023, 1).unwrap(), None);
+        assert_eq!(
+            input.read_answers(2022).unwrap().as_deref(),
+            Some("1 1 ok 3000\n")
+        );
     }
+
+    let old = Encryption::from_key("AAECAwQFBgcICQoLDA0ODw==").unwrap();
+    let new = Encryption::from_key("Dw4NDAsKCQgHBgUEAwIBAA==").unwrap();
+    let encrypted_root = root.join("encrypted");
+    let encrypted = EncryptedDirStore::new(&encrypted_root, old);
+    encrypted.write(2022, Entry::Input(1), b"1000\n").unwrap();
+    encrypted
+        .write(2022, Entry::Answers, b"1 1 ok 1000\n")
+        .unwrap();
+    let archive = ArchiveStore::new(&encrypted_root, Some(encrypted.encryption));
+    archive.write(2023, Entry::Puzzle(5), b"<article>").unwrap();
+
+    let old = archive.encryption.unwrap();
+
+    let legacy_path = encrypted_root.join("year_2021").join("answers.aes");
+    let mut legacy = b"1 1 ok 7\n".to_vec();
+    let key = ring::aead::UnboundKey::new(
+        &ring::aead::AES_128_GCM,
+        &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    )
+    .unwrap();
+    ring::aead::LessSafeKey::new(key)
+        .seal_in_place_append_tag(
+            ring::aead::Nonce::assume_unique_for_key([1, 0, 0, 0, 0xe5, 0x07, 0, 0, 0, 0, 0, 0]),
+            ring::aead::Aad::empty(),
+            &mut legacy,
+        )
+        .unwrap();
+    write_file(&legacy_path, &legacy).unwrap();
+
+    let encrypted = EncryptedDirStore::new(&encrypted_root, old);
+    assert_eq!(
+        encrypted.read(2021, Entry::Answers).unwrap().as_deref(),
+        Some(&b"1 1 ok 7\n"[..])
+    );
+    assert!(!Encryption::is_legacy(
+        &std::fs::read(&legacy_path).unwrap()
+    ));
+    let old = encrypted.encryption;
+
+    let missing_root = root.join("missing");
+    let roots = [encrypted_root.as_path(), missing_root.as_path()];
+    assert_eq!(rekey(&roots, &old, &new).unwrap(), 4);
+    assert!(rekey(&roots, &old, &new).is_err());
+
+    let encrypted = EncryptedDirStore::new(&encrypted_root, new);
+    assert_eq!(
+        encrypted.read(2022, Entry::Input(1)).unwrap().as_deref(),
+        Some(&b"1000\n"[..])
+    );
+    let archive = ArchiveStore::new(&encrypted_root, Some(encrypted.encryption));
+    assert_eq!(
+        archive.read(2023, Entry::Puzzle(5)).unwrap().as_deref(),
+        Some(&b"<article>"[..])
+    );
+
+    let _ = std::fs::remove_dir_all(root);
 }
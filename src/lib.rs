@@ -1,9 +1,29 @@
 pub use ahash::{AHashMap as HashMap, AHashSet as HashSet, AHasher as Hasher};
 
 pub mod solutions;
-pub use solutions::{Solution, SolutionFunc};
+pub use solutions::{Answer, Example, Solution, SolutionFunc};
+pub(crate) use solutions::solution;
 
 pub mod input;
-pub use input::Input;
+pub use input::{Input, InputStore};
+
+pub mod puzzle;
+pub use puzzle::Puzzle;
+
+pub mod answers;
+pub use answers::AnswerStore;
 
 pub mod encryption;
+
+pub mod geometry;
+
+pub mod grid;
+
+pub mod math;
+
+pub mod parse;
+
+pub mod search;
+
+pub mod error;
+pub use error::ParseError;
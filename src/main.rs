@@ -1,196 +1,1091 @@
 use ahash::AHashMap as HashMap;
 
 use std::io::Write;
+use std::sync::Arc;
 use std::time::Duration;
 
-use advent::solutions::{self, Solution, SolutionCollection};
-pub use advent::Input;
+use advent::Puzzle;
+use advent::answers::Answers;
+use advent::encryption::Encryption;
+use advent::input::StoreConfig;
+use advent::solutions::{self, Answer, Solution, SolutionCollection, SolutionFunc};
+pub use advent::{AnswerStore, Input};
 
+mod cli;
 mod downloader;
+mod history;
+mod output;
+mod profile;
 mod profiler;
+mod rate_limit;
+mod runner;
 
-use downloader::InputDownloader;
-pub use profiler::{Metrics, Profiler};
+use cli::{Command, Selection};
+
+use downloader::{InputDownloader, SubmitResponse};
+use output::{Check, CheckStatus, Format, Record, Stage};
+use profile::Profile;
+pub use profiler::{Metrics, Profiler, Stats};
+use runner::{Executor, Failure, PartResult, RunOptions, Task, TaskFunc};
 
 const DEFAULT_EVENT: u32 = 2025;
 
-enum EventSelection {
-    Specific(u32),
-    All,
+fn main() {
+    let events: HashMap<_, _> = solutions::events().into_iter().collect();
+    let events: &'static HashMap<u32, SolutionCollection> = Box::leak(Box::new(events));
+
+    let args = match cli::parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            eprintln!("run 'advent-of-code --help' for usage");
+            std::process::exit(2)
+        }
+    };
+
+    if args.command == Command::Help {
+        println!("{}", cli::usage());
+        return;
+    }
+
+    if args.command == Command::New {
+        let (event, day) = args
+            .selection
+            .single_day()
+            .expect("new command validated to have single day");
+        let profiles = select_profiles(&args);
+        let puzzle = match profiles[0]
+            .input()
+            .and_then(|input| input.read_puzzle(event, day))
+        {
+            Ok(puzzle) => puzzle,
+            Err(err) => {
+                eprintln!(
+                    "unable to read cached puzzle, the test will be left empty. {}",
+                    err
+                );
+                None
+            }
+        };
+        if let Err(err) = new_day(event, day, puzzle.as_ref()) {
+            eprintln!("unable to create {} day {}. {}", event, day, err);
+            std::process::exit(1)
+        }
+        return;
+    }
+
+    if args.command == Command::Puzzle {
+        let (event, day) = args
+            .selection
+            .single_day()
+            .expect("puzzle command validated to have single day");
+        let profiles = select_profiles(&args);
+        if let Err(err) = show_puzzle(&open_input(&profiles[0]), event, day, args.selection.part) {
+            eprintln!("unable to show {} day {}. {}", event, day, err);
+            std::process::exit(1)
+        }
+        return;
+    }
+
+    if args.command == Command::Rekey {
+        if let Err(err) = rekey_inputs(&args) {
+            eprintln!("unable to rekey inputs. {}", err);
+            std::process::exit(1)
+        }
+        return;
+    }
+
+    let selected = match select_events(events, &args.selection) {
+        Ok(selected) => selected,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            std::process::exit(2)
+        }
+    };
+
+    match args.command {
+        Command::List => list_events(events, &selected, &args.selection),
+        Command::Compare => compare_events(&selected, &args),
+        Command::Fetch => fetch_events(&selected, &args),
+        Command::Test => {
+            runner::install_panic_hook();
+            if !test_events(&selected, &args) {
+                std::process::exit(1)
+            }
+        }
+        Command::Run | Command::Bench | Command::Submit | Command::Verify => {
+            let profiles = select_profiles(&args);
+            let run_info = history::RunInfo::current(args.save_baseline.clone());
+            let custom_input = args.input_source.as_ref().map(read_input_source);
+
+            runner::install_panic_hook();
+
+            if let Some(header) = args.format.and_then(|f| f.header()) {
+                println!("{}", header);
+            }
+
+            let mut unsuccessful = Vec::new();
+            for (idx, profile) in profiles.iter().enumerate() {
+                if profiles.len() > 1 && args.format.is_none() {
+                    if idx > 0 {
+                        println!();
+                        println!();
+                    }
+                    println!("Profile {}", profile.label());
+                    println!();
+                }
+
+                if !run_profile(&args, &selected, profile, &run_info, custom_input.clone()) {
+                    unsuccessful.push(profile.label());
+                }
+            }
+
+            if profiles.len() > 1 && !unsuccessful.is_empty() {
+                eprintln!();
+                eprintln!("failures in profiles: {}", unsuccessful.join(", "));
+            }
+
+            if !unsuccessful.is_empty() {
+                std::process::exit(1)
+            }
+        }
+        Command::New | Command::Puzzle | Command::Rekey | Command::Help => {
+            unreachable!("handled before event selection")
+        }
+    }
 }
 
-fn main() {
-    let events: HashMap<_, _> = [
-        (2019, solutions::days_2019()),
-        (2020, solutions::days_2020()),
-        (2021, solutions::days_2021()),
-        (2022, solutions::days_2022()),
-        (2023, solutions::days_2023()),
-        (2024, solutions::days_2024()),
-        (2025, solutions::days_2025()),
-    ]
-    .into_iter()
-    .collect();
-
-    let mut args: Vec<_> = std::env::args().skip(1).collect();
-
-    let mut submission = false;
-    let mut details = false;
-    let mut print_input = false;
-
-    for arg in args.iter() {
-        match arg.as_str() {
-            "--submit" | "-s" => submission = true,
-            "--details" | "-d" => details = true,
-            "--input" | "-i" => print_input = true,
-            "--help" | "-h" => {
-                usage();
-                return;
+fn run_profile(
+    args: &cli::Args,
+    selected: &[(u32, Vec<&'static Solution>)],
+    profile: &Profile,
+    run_info: &history::RunInfo,
+    custom_input: Option<Arc<str>>,
+) -> bool {
+    let input = open_input(profile);
+    let downloader = InputDownloader::new(input.clone(), profile);
+
+    let mut context = Context {
+        downloader,
+        details: args.details || args.command == Command::Bench,
+        submission: args.submit,
+        print_input: args.print_input,
+        part_filter: args.selection.part,
+        format: args.format,
+        answer_store: AnswerStore::new(input),
+        failed: 0,
+        profile,
+        run_info,
+        custom_input,
+        record_history: args.records_history(),
+        history: Vec::new(),
+        verify: (args.command == Command::Verify).then(|| Verification {
+            update: args.update_answers,
+            ..Default::default()
+        }),
+    };
+
+    let inputs = load_inputs(&context, selected);
+    let tasks = build_tasks(&context, selected, &inputs);
+
+    std::thread::scope(|scope| {
+        let mut executor = Executor::new(
+            scope,
+            &tasks,
+            args.jobs,
+            RunOptions {
+                timeout: args.timeout,
+                samples: args.samples,
+                warmup: args.warmup,
+            },
+        );
+
+        if let [(event, days)] = selected {
+            run_event(&mut context, &mut executor, *event, days, &inputs[0]);
+        } else {
+            let mut overall_duration = Duration::ZERO;
+
+            for ((event, days), inputs) in selected.iter().zip(&inputs) {
+                overall_duration += run_event(&mut context, &mut executor, *event, days, inputs);
+                if context.format.is_none() {
+                    println!();
+                    println!();
+                }
+            }
+
+            if context.format.is_none() {
+                println!("Overall duration{:>26}", profiler::Time(overall_duration))
             }
-            _ => (),
+        }
+    });
+
+    if args.jobs > 1 && context.format.is_none() && context.verify.is_none() {
+        println!();
+        println!(
+            "* measured while running {} jobs in parallel, allocations and peak memory include other parts",
+            args.jobs
+        );
+    } else if runner::measured_with_runaway() && context.verify.is_none() {
+        let note = "* measured while a part that timed out was still running, the metrics are not comparable";
+        if context.format.is_none() {
+            println!();
+            println!("{}", note);
+        } else {
+            eprintln!("{}", note.trim_start_matches("* "));
         }
     }
 
-    args.retain(|arg| !arg.starts_with("-"));
+    if context.record_history
+        && let Err(err) = history::append(history::HISTORY_PATH, &context.history)
+    {
+        eprintln!(
+            "unable to record metrics in '{}'. {:?}",
+            history::HISTORY_PATH,
+            err
+        );
+    }
 
-    let event = if let Some(arg) = args.first() {
-        if arg.to_lowercase() == "all" {
-            EventSelection::All
+    if let Some(verification) = &context.verify {
+        let summary = format!(
+            "{} matched, {} mismatched, {} without accepted answer, {} unsolved",
+            verification.matched,
+            verification.mismatched,
+            verification.missing,
+            verification.unsolved
+        );
+        if context.format.is_none() {
+            println!();
+            println!("{}", summary);
         } else {
-            match arg.parse() {
-                Ok(event) => {
-                    if events.contains_key(&event) {
-                        EventSelection::Specific(event)
-                    } else {
-                        eprintln!("event '{}' not found", event);
-                        std::process::exit(1)
+            eprintln!("{}", summary);
+        }
+    }
+
+    if context.failed > 0 {
+        let parts: usize = tasks.iter().map(|task| task.func.parts()).sum();
+        if context.format.is_none() {
+            println!();
+            println!("{} of {} parts failed", context.failed, parts);
+        } else {
+            eprintln!("{} of {} parts failed", context.failed, parts);
+        }
+    }
+
+    let mismatched = context.verify.as_ref().map_or(0, |v| v.mismatched);
+    context.failed == 0 && mismatched == 0
+}
+
+fn select_events(
+    events: &'static HashMap<u32, SolutionCollection>,
+    selection: &Selection,
+) -> Result<Vec<(u32, Vec<&'static Solution>)>, String> {
+    if let cli::EventFilter::Ranges(ranges) = &selection.events {
+        for range in ranges {
+            if range.start() == range.end() && !events.contains_key(range.start()) {
+                return Err(format!("event '{}' not found", range.start()));
+            }
+        }
+    }
+
+    let mut selected: Vec<_> = events
+        .iter()
+        .filter(|(event, _)| selection.contains_event(**event, DEFAULT_EVENT))
+        .map(|(&event, days)| {
+            let days: Vec<_> = days
+                .solutions()
+                .filter(|s| selection.contains_day(s.day))
+                .collect();
+            (event, days)
+        })
+        .collect();
+    selected.sort_by_key(|e| e.0);
+
+    if selected.is_empty() {
+        return Err("no events match the selection".to_string());
+    }
+
+    // events without any of the selected days are skipped, so `all 21` runs the events that have one
+    if !selection.days.is_empty() {
+        selected.retain(|(_, days)| !days.is_empty());
+    }
+
+    if selected.is_empty() {
+        let days: Vec<_> = selection.explicit_days().map(|d| d.to_string()).collect();
+        return Err(if days.is_empty() {
+            "no selected event has a solution for the selected days".to_string()
+        } else {
+            format!(
+                "no selected event has a solution for day '{}'",
+                days.join(", ")
+            )
+        });
+    }
+
+    Ok(selected)
+}
+
+fn list_events(
+    events: &HashMap<u32, SolutionCollection>,
+    selected: &[(u32, Vec<&Solution>)],
+    selection: &Selection,
+) {
+    for (event, days) in selected {
+        let days: Vec<_> = days.iter().map(|s| s.day.to_string()).collect();
+        let missing: Vec<_> = events[event]
+            .missing_days(*event)
+            .into_iter()
+            .filter(|day| selection.contains_day(*day))
+            .map(|day| day.to_string())
+            .collect();
+
+        if missing.is_empty() {
+            println!("{}: {}", event, days.join(", "));
+        } else {
+            println!(
+                "{}: {} (missing {})",
+                event,
+                days.join(", "),
+                missing.join(", ")
+            );
+        }
+    }
+}
+
+fn test_events(selected: &[(u32, Vec<&'static Solution>)], args: &cli::Args) -> bool {
+    let mut tasks = Vec::new();
+    let mut expected = Vec::new();
+    let mut without_examples = 0;
+
+    for (event, days) in selected {
+        for day in days {
+            let day: &'static Solution = day;
+            if day.examples.is_empty() {
+                without_examples += 1;
+            }
+
+            for (idx, example) in day.examples.iter().enumerate() {
+                let parts: Vec<_> = selected_parts(args.selection.part, day)
+                    .filter_map(|(part, _)| Some((part, day.example_part(example, part)?)))
+                    .collect();
+                let selected = |part: u32| parts.iter().any(|(p, _)| *p == part);
+
+                if let Some(parsed) = day.example_parsed(example)
+                    && !parts.is_empty()
+                {
+                    tasks.push(Task {
+                        func: TaskFunc::Parsed {
+                            parse: &parsed.parse,
+                            part_one: selected(1).then_some(&parsed.part_one),
+                            part_two: selected(2).then_some(&parsed.part_two),
+                        },
+                        input: Arc::from(example.input),
+                    });
+                } else {
+                    for (_, (func, _)) in &parts {
+                        tasks.push(Task {
+                            func: TaskFunc::Whole(func),
+                            input: Arc::from(example.input),
+                        });
                     }
                 }
-                Err(_err) => {
-                    eprintln!("invalid event '{}'", arg);
-                    std::process::exit(1)
+
+                for (part, (_, answer)) in parts {
+                    expected.push((*event, day.day, part, idx + 1, answer));
                 }
             }
         }
-    } else {
-        EventSelection::Specific(DEFAULT_EVENT)
+    }
+
+    let mut passed = 0;
+    let mut failed = 0;
+
+    std::thread::scope(|scope| {
+        let mut executor = Executor::new(
+            scope,
+            &tasks,
+            args.jobs,
+            RunOptions {
+                timeout: args.timeout,
+                samples: 1,
+                warmup: 0,
+            },
+        );
+
+        let mut current_event = None;
+        for (event, day, part, example, expected) in expected {
+            if current_event != Some(event) {
+                if current_event.is_some() {
+                    println!();
+                }
+                println!("Advent of Code - {}", event);
+                println!();
+                current_event = Some(event);
+            }
+
+            let Some(result) = executor.next_result() else {
+                eprintln!(
+                    "example for '{}' day '{}' part '{}' did not complete",
+                    event, day, part
+                );
+                std::process::exit(1)
+            };
+
+            let status = match &result.answer {
+                Ok(answer) if answer == expected => {
+                    passed += 1;
+                    "ok".to_string()
+                }
+                Ok(_) if expected.to_string().contains('\n') => {
+                    failed += 1;
+                    format!("MISMATCH, expected:\n{}", expected)
+                }
+                Ok(_) => {
+                    failed += 1;
+                    format!("MISMATCH, expected {}", expected)
+                }
+                Err(failure) => {
+                    failed += 1;
+                    failure.to_string()
+                }
+            };
+
+            let answer = match &result.answer {
+                Ok(answer) => answer.to_string(),
+                Err(failure) => failure.status().to_string(),
+            };
+
+            if answer.len() <= 25 && !answer.contains('\n') {
+                println!(
+                    "{:>2}-{}:{:>25}  example {}, {}",
+                    day, part, answer, example, status
+                );
+            } else {
+                println!("{:>2}-{}:  example {}, {}", day, part, example, status);
+                println!("{}", answer);
+            }
+        }
+    });
+
+    println!();
+    println!(
+        "{} passed, {} failed, {} days without examples",
+        passed, failed, without_examples
+    );
+
+    failed == 0
+}
+
+fn compare_events(selected: &[(u32, Vec<&Solution>)], args: &cli::Args) {
+    let entries = match history::read(history::HISTORY_PATH) {
+        Ok(entries) => entries,
+        Err(err) => {
+            eprintln!(
+                "unable to read metrics history '{}'. {:?}",
+                history::HISTORY_PATH,
+                err
+            );
+            std::process::exit(1)
+        }
     };
 
-    let day_filter: Vec<_> = args
-        .into_iter()
-        .skip(1)
-        .filter_map(|arg| arg.parse().ok())
-        .collect();
+    let mut compared = 0;
+    let mut regressed = 0;
 
-    let downloader = InputDownloader::new();
-    let profiler = Profiler::new();
+    for (event, days) in selected {
+        println!("Advent of Code - {}", event);
+        println!();
 
-    let mut context = Context {
-        downloader,
-        profiler,
-        details,
-        submission,
-        print_input,
+        for day in days {
+            let parse = day.parsed.as_ref().map(|_| 0);
+            let parts = selected_parts(args.selection.part, day).map(|(part, _)| part);
+            for part in parse.into_iter().chain(parts) {
+                let Some(comparison) = history::compare(
+                    &entries,
+                    args.profile.as_deref().filter(|p| *p != "default"),
+                    *event,
+                    day.day,
+                    part,
+                    args.baseline.as_deref(),
+                    args.threshold,
+                ) else {
+                    println!(
+                        "{:>2}-{}:{:>25}",
+                        day.day,
+                        part_label(part),
+                        "no previous run"
+                    );
+                    continue;
+                };
+
+                let previous = &comparison.previous.metrics;
+                let current = &comparison.current.metrics;
+
+                print!(
+                    "{:>2}-{}:{:>12} ->{:>9}{:>9.1}%{:>10} ->{:>7} allocs.{:>10} ->{:>9} mem.",
+                    day.day,
+                    part_label(part),
+                    profiler::Time(previous.duration),
+                    profiler::Time(current.duration),
+                    history::change(history::Measure::Duration, previous, current),
+                    previous.allocations,
+                    current.allocations,
+                    profiler::Bytes(previous.peak_memory),
+                    profiler::Bytes(current.peak_memory),
+                );
+
+                compared += 1;
+                if comparison.regressions.is_empty() {
+                    println!();
+                } else {
+                    regressed += 1;
+                    let measures: Vec<_> = comparison
+                        .regressions
+                        .iter()
+                        .map(|(measure, change)| format!("{} +{:.1}%", measure.name(), change))
+                        .collect();
+                    println!("  REGRESSION {}", measures.join(", "));
+                }
+            }
+        }
+
+        println!();
+    }
+
+    println!(
+        "{} parts compared, {} regressed by more than {}%",
+        compared, regressed, args.threshold
+    );
+
+    if regressed > 0 {
+        std::process::exit(1)
+    }
+}
+
+fn select_profiles(args: &cli::Args) -> Vec<Profile> {
+    let base = StoreConfig::from_env().map(|config| StoreConfig {
+        kind: args.input_store.or(config.kind),
+        root: args.input_dir.clone().unwrap_or(config.root),
+    });
+
+    let profiles = base.and_then(|base| {
+        // the profiles share the input key, so rekey moves all of them to the new one
+        if args.all_profiles || args.command == Command::Rekey {
+            Profile::all(&base)
+        } else if let Some(name) = args.profile.as_deref() {
+            Profile::named(name, &base).map(|profile| vec![profile])
+        } else {
+            Ok(vec![Profile::default(base)])
+        }
+    });
+
+    match profiles {
+        Ok(profiles) => profiles,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            std::process::exit(2)
+        }
+    }
+}
+
+fn open_input(profile: &Profile) -> Input {
+    match profile.input() {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!(
+                "error: unable to open input store for profile '{}'. {}",
+                profile.label(),
+                err
+            );
+            std::process::exit(2)
+        }
+    }
+}
+
+fn rekey_inputs(args: &cli::Args) -> Result<(), Box<dyn std::error::Error>> {
+    let old = match args.old_key.as_ref() {
+        Some(path) => Encryption::from_key(&std::fs::read_to_string(path)?)?,
+        None => {
+            Encryption::new()?.ok_or("no input key found in '.input-key' or 'AOC_INPUT_KEY'")?
+        }
     };
 
-    match event {
-        EventSelection::Specific(event) => {
-            if let Some(days) = events.get(&event) {
-                run_event(&mut context, event, days, &day_filter);
-            } else {
-                eprintln!("event '{}' not configured", event);
-                std::process::exit(1)
+    let new_path = args
+        .new_key
+        .as_ref()
+        .expect("rekey validated to have new key");
+    if !new_path.exists() {
+        std::fs::write(new_path, Encryption::generate_key()? + "\n")?;
+        println!("generated new key in '{}'", new_path.display());
+    }
+    let new = Encryption::from_key(&std::fs::read_to_string(new_path)?)?;
+
+    let profiles = select_profiles(args);
+    let roots: Vec<_> = profiles.iter().map(|p| p.store.root.as_path()).collect();
+    let count = advent::input::rekey(&roots, &old, &new)?;
+    println!(
+        "re-encrypted {} files from key '{}' to key '{}'",
+        count,
+        old.key_id(),
+        new.key_id()
+    );
+    println!(
+        "replace '.input-key' with '{}' to use the new key",
+        new_path.display()
+    );
+
+    Ok(())
+}
+
+fn fetch_events(selected: &[(u32, Vec<&Solution>)], args: &cli::Args) {
+    let mut failed = false;
+
+    for profile in select_profiles(args) {
+        if fetch_profile(selected, args, &profile) {
+            failed = true;
+        }
+    }
+
+    if failed {
+        std::process::exit(1)
+    }
+}
+
+fn fetch_profile(selected: &[(u32, Vec<&Solution>)], args: &cli::Args, profile: &Profile) -> bool {
+    let downloader = InputDownloader::new(open_input(profile), profile);
+    let mut failed = false;
+
+    for (event, days) in selected {
+        for day in days {
+            if let Err(error) = downloader.download_input_if_absent(*event, day.day) {
+                eprintln!(
+                    "unable to get input for '{}' day '{}'. {:?}",
+                    event, day.day, error
+                );
+                failed = true;
             }
         }
-        EventSelection::All => {
-            let mut events: Vec<_> = events.into_iter().collect();
-            events.sort_by_key(|e| e.0);
 
-            let mut overall_duration = Duration::ZERO;
+        if !args.puzzles {
+            continue;
+        }
 
-            for event in &events {
-                overall_duration += run_event(&mut context, event.0, &event.1, &day_filter);
-                println!();
-                println!();
+        let puzzle_days = if args.selection.days.is_empty() {
+            match downloader.visible_days(*event) {
+                Ok(days) => days,
+                Err(error) => {
+                    eprintln!("unable to get calendar for '{}'. {:?}", event, error);
+                    failed = true;
+                    continue;
+                }
+            }
+        } else {
+            days.iter().map(|d| d.day).collect()
+        };
+
+        for day in puzzle_days {
+            if let Err(error) = downloader.download_puzzle(*event, day, args.refresh) {
+                eprintln!(
+                    "unable to get puzzle for '{}' day '{}'. {:?}",
+                    event, day, error
+                );
+                failed = true;
+            }
+        }
+    }
+
+    failed
+}
+
+fn show_puzzle(
+    input: &Input,
+    event: u32,
+    day: u32,
+    part: Option<u32>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let puzzle = input.read_puzzle(event, day)?.ok_or_else(|| {
+        format!(
+            "puzzle not found, run 'advent-of-code fetch {} {} --puzzles' first",
+            event, day
+        )
+    })?;
+
+    println!("{}", puzzle.text().trim_end());
+
+    let accepted = puzzle.accepted_answers();
+    for (idx, desc) in puzzle.parts().iter().enumerate() {
+        let part_num = idx as u32 + 1;
+        if part.is_some_and(|p| p != part_num) {
+            continue;
+        }
+
+        println!();
+        println!("Part {}", part_num);
+        for (example_idx, example) in desc.examples.iter().enumerate() {
+            println!("  Example {}:", example_idx + 1);
+            for line in example.lines() {
+                println!("    {}", line);
             }
+        }
+        if let Some(answer) = desc.example_answer.as_deref() {
+            println!("  Example answer: {}", answer);
+        }
+        if let Some(answer) = accepted.get(idx) {
+            println!("  Accepted answer: {}", answer);
+        }
+    }
+
+    Ok(())
+}
+
+fn new_day(
+    event: u32,
+    day: u32,
+    puzzle: Option<&Puzzle>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::path::PathBuf::from(format!("src/solutions/year_{}/day_{:02}.rs", event, day));
+
+    if path.exists() {
+        return Err(format!("file '{}' already exists", path.display()).into());
+    }
+
+    let mut template = std::fs::read_to_string("src/solutions/template.rs")?;
+    if let Some(puzzle) = puzzle {
+        template = fill_template(&template, puzzle);
+    }
+    std::fs::create_dir_all(path.parent().expect("solution path should have parent"))?;
+    std::fs::write(&path, template)?;
+
+    println!("created '{}'", path.display());
+    if puzzle.is_some() {
+        println!("filled the examples with the one from the cached puzzle");
+    } else {
+        println!(
+            "run 'advent-of-code fetch {} --puzzles' first to fill the examples with the one from the puzzle",
+            event
+        );
+    }
+    println!("it will be registered the next time the project is built");
+
+    Ok(())
+}
 
-            println!("Overall duration{:>26}", profiler::Time(overall_duration))
+/// Replaces the empty example input in the template with the first example of the puzzle,
+/// and the expected answers with the example answers found so far. Part two gets an
+/// example of its own when its first example differs from the one in part one.
+fn fill_template(template: &str, puzzle: &Puzzle) -> String {
+    let parts = puzzle.parts();
+    let Some(example) = parts.first().and_then(|p| p.examples.first()) else {
+        return template.to_string();
+    };
+
+    let mut inputs = format!("let input = {};", raw_string(example));
+    let mut examples = vec!["Example::new(input)".to_string()];
+
+    for (idx, (part, name)) in parts.iter().zip(["part_one", "part_two"]).enumerate() {
+        let Some(answer) = part.example_answer.as_deref() else {
+            continue;
+        };
+        let expected = if answer.parse::<i64>().is_ok() {
+            answer.to_string()
+        } else {
+            format!("{:?}", answer)
+        };
+
+        match part.examples.first() {
+            Some(own) if idx > 0 && own.trim_end() != example.trim_end() => {
+                inputs.push_str(&format!("\n    let input_two = {};", raw_string(own)));
+                examples.push(format!("Example::new(input_two).{}({})", name, expected));
+            }
+            _ => examples[0].push_str(&format!(".{}({})", name, expected)),
         }
     }
+
+    let examples = match examples.as_slice() {
+        [example] => format!("vec![{}]", example),
+        examples => format!("vec![\n        {},\n    ]", examples.join(",\n        ")),
+    };
+
+    template.replace("let input = r#\"\"#;", &inputs).replace(
+        "vec![Example::new(input).part_one(0).part_two(0)]",
+        &examples,
+    )
+}
+
+fn raw_string(text: &str) -> String {
+    let text = text.trim_end_matches('\n');
+    let hashes = (1..)
+        .map(|n| "#".repeat(n))
+        .find(|hashes| !text.contains(&format!("\"{}", hashes)))
+        .expect("some number of hashes terminates the raw string");
+
+    format!("r{0}\"{1}\"{0}", hashes, text)
 }
 
-struct Context {
+struct Context<'a> {
     downloader: InputDownloader,
-    profiler: Profiler,
+    profile: &'a Profile,
     details: bool,
     submission: bool,
     print_input: bool,
+    part_filter: Option<u32>,
+    format: Option<Format>,
+    answer_store: AnswerStore,
+    failed: u32,
+    run_info: &'a history::RunInfo,
+    custom_input: Option<Arc<str>>,
+    record_history: bool,
+    history: Vec<history::Entry>,
+    verify: Option<Verification>,
+}
+
+#[derive(Default)]
+struct Verification {
+    update: bool,
+    expected: HashMap<u32, Answers>,
+    matched: u32,
+    mismatched: u32,
+    missing: u32,
+    unsolved: u32,
+}
+
+fn read_input_source(source: &cli::InputSource) -> Arc<str> {
+    let input = match source {
+        cli::InputSource::File(path) => std::fs::read_to_string(path)
+            .map_err(|err| format!("unable to read input file '{}'. {}", path.display(), err)),
+        cli::InputSource::Stdin => std::io::read_to_string(std::io::stdin())
+            .map_err(|err| format!("unable to read input from stdin. {}", err)),
+    };
+
+    match input {
+        Ok(input) => input.into(),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1)
+        }
+    }
+}
+
+fn load_inputs(ctx: &Context, selected: &[(u32, Vec<&Solution>)]) -> Vec<Vec<Arc<str>>> {
+    if let Some(input) = ctx.custom_input.as_ref() {
+        return selected
+            .iter()
+            .map(|(_, days)| days.iter().map(|_| input.clone()).collect())
+            .collect();
+    }
+
+    selected
+        .iter()
+        .map(|(event, days)| {
+            days.iter()
+                .map(
+                    |day| match ctx.downloader.download_input_if_absent(*event, day.day) {
+                        Ok(input) => input.into(),
+                        Err(error) => {
+                            eprintln!(
+                                "unable to get input for '{}' day '{}'. {:?}",
+                                event, day.day, error
+                            );
+                            std::process::exit(1)
+                        }
+                    },
+                )
+                .collect()
+        })
+        .collect()
+}
+
+fn selected_parts(
+    part_filter: Option<u32>,
+    day: &Solution,
+) -> impl Iterator<Item = (u32, &SolutionFunc)> {
+    [(1, &day.part_one), (2, &day.part_two)]
+        .into_iter()
+        .filter(move |(part, _)| part_filter.is_none_or(|p| p == *part))
+}
+
+fn build_tasks(
+    ctx: &Context,
+    selected: &[(u32, Vec<&'static Solution>)],
+    inputs: &[Vec<Arc<str>>],
+) -> Vec<Task> {
+    let mut tasks = Vec::new();
+
+    for ((_, days), inputs) in selected.iter().zip(inputs) {
+        for (day, input) in days.iter().zip(inputs) {
+            let day: &'static Solution = day;
+            if let Some(parsed) = &day.parsed {
+                // both parts run on a single parse of the input
+                let part = |part: u32| ctx.part_filter.is_none_or(|p| p == part);
+                tasks.push(Task {
+                    func: TaskFunc::Parsed {
+                        parse: &parsed.parse,
+                        part_one: part(1).then_some(&parsed.part_one),
+                        part_two: part(2).then_some(&parsed.part_two),
+                    },
+                    input: input.clone(),
+                });
+                continue;
+            }
+
+            for (_, func) in selected_parts(ctx.part_filter, day) {
+                tasks.push(Task {
+                    func: TaskFunc::Whole(func),
+                    input: input.clone(),
+                });
+            }
+        }
+    }
+
+    tasks
 }
 
 fn run_event(
     ctx: &mut Context,
+    executor: &mut Executor,
     event: u32,
-    days: &SolutionCollection,
-    day_filter: &[u32],
+    days: &[&Solution],
+    inputs: &[Arc<str>],
 ) -> Duration {
-    println!("Advent of Code - {}", event);
-    println!();
+    if ctx.format.is_none() {
+        println!("Advent of Code - {}", event);
+        println!();
+    }
 
     let mut total_duration = Duration::ZERO;
 
-    for day in days
-        .solutions()
-        .filter(|s| day_filter.is_empty() || day_filter.contains(&s.day))
-    {
-        total_duration += run_day(ctx, event, day)
+    for (day, input) in days.iter().zip(inputs) {
+        total_duration += run_day(ctx, executor, event, day, input)
+    }
+
+    if ctx.format.is_none() {
+        println!("Total duration{:>28}", profiler::Time(total_duration));
     }
-    println!("Total duration{:>28}", profiler::Time(total_duration));
 
     total_duration
 }
 
-fn run_day(ctx: &mut Context, event: u32, day: &Solution) -> Duration {
-    match ctx.downloader.download_input_if_absent(event, day.day) {
-        Ok(input) => {
-            if ctx.print_input {
-                println!();
-                println!("{input}");
-                println!();
-            }
-
-            ctx.profiler.start();
-            let part_one = (day.part_one)(&input);
-            let part_one_metrics = ctx.profiler.stop();
-            let part_one = part_one.to_string();
-
-            print_line(ctx, event, day.day, 1, &part_one, &part_one_metrics);
+fn run_day(
+    ctx: &mut Context,
+    executor: &mut Executor,
+    event: u32,
+    day: &Solution,
+    input: &str,
+) -> Duration {
+    if ctx.print_input {
+        println!();
+        println!("{input}");
+        println!();
+    }
 
-            ctx.profiler.start();
-            let part_two = (day.part_two)(&input);
-            let part_two_metrics = ctx.profiler.stop();
-            let part_two = part_two.to_string();
+    let mut duration = Duration::ZERO;
+    let mut answers = Vec::with_capacity(2);
 
-            print_line(ctx, event, day.day, 2, &part_two, &part_two_metrics);
+    for (part, _) in selected_parts(ctx.part_filter, day) {
+        let Some(PartResult {
+            answer,
+            metrics,
+            stats,
+            parse,
+        }) = executor.next_result()
+        else {
+            eprintln!(
+                "solution for '{}' day '{}' part '{}' did not complete",
+                event, day.day, part
+            );
+            std::process::exit(1)
+        };
 
-            if ctx.submission {
-                submit_day_part(ctx, event, day, 1, &part_one);
-                submit_day_part(ctx, event, day, 2, &part_two);
+        // the parse step comes with the first selected part
+        if let Some(parse) = parse {
+            if ctx.verify.is_none() {
+                print_line(
+                    ctx,
+                    event,
+                    day.day,
+                    Stage::Parse,
+                    Ok(&Answer::Text(String::new())),
+                    &parse.metrics,
+                    parse.stats.as_ref(),
+                );
             }
 
-            part_one_metrics.duration + part_two_metrics.duration
+            duration += parse.metrics.duration;
+            record_history(ctx, event, day.day, 0, parse.metrics);
         }
-        Err(error) => {
-            eprintln!(
-                "unable to get input for '{}' day '{}'. {:?}",
-                event, day.day, error
+
+        if ctx.verify.is_some() {
+            verify_line(
+                ctx,
+                event,
+                day.day,
+                part,
+                answer.as_ref(),
+                &metrics,
+                stats.as_ref(),
+            );
+        } else {
+            print_line(
+                ctx,
+                event,
+                day.day,
+                Stage::Part(part),
+                answer.as_ref(),
+                &metrics,
+                stats.as_ref(),
             );
-            std::process::exit(1)
+        }
+
+        duration += metrics.duration;
+
+        match answer {
+            Ok(answer) => {
+                // unsolved and not applicable parts are neither recorded nor submitted
+                if answer.value().is_some() {
+                    record_history(ctx, event, day.day, part, metrics);
+                    answers.push((part, answer.submission()));
+                }
+            }
+            Err(_) => ctx.failed += 1,
+        }
+    }
+
+    if ctx.submission {
+        for (part, answer) in answers {
+            submit_day_part(ctx, event, day, part, answer.as_deref());
         }
     }
+
+    duration
 }
 
-fn submit_day_part(ctx: &Context, event: u32, day: &Solution, part: u32, answer: &str) {
-    print!("Submit part {}? [ycN] ", part);
+fn record_history(ctx: &mut Context, event: u32, day: u32, part: u32, metrics: Metrics) {
+    if !ctx.record_history {
+        return;
+    }
+
+    let entry = ctx
+        .run_info
+        .entry(ctx.profile.name.as_deref(), event, day, part, metrics);
+    ctx.history.push(entry);
+}
+
+/// `answer` is `None` for art, which can only be submitted by entering the letters it shows.
+fn submit_day_part(ctx: &Context, event: u32, day: &Solution, part: u32, answer: Option<&str>) {
+    let choices = if answer.is_some() {
+        "[ycN]"
+    } else {
+        "(the answer is drawn, enter its letters with 'c') [cN]"
+    };
+    if let Some(wait) = ctx.downloader.submit_cooldown() {
+        print!(
+            "Submit part {}? (next submission in {}s) {} ",
+            part,
+            wait.as_secs_f64().ceil(),
+            choices
+        );
+    } else {
+        print!("Submit part {}? {} ", part, choices);
+    }
     std::io::stdout().flush().unwrap();
     let mut buffer = String::new();
     std::io::stdin().read_line(&mut buffer).unwrap();
@@ -204,91 +1099,322 @@ fn submit_day_part(ctx: &Context, event: u32, day: &Solution, part: u32, answer:
 
         Some(buffer.trim())
     } else if entry == "y" {
-        Some(answer)
+        answer
     } else {
         None
     };
 
-    if let Some(answer) = answer {
-        let res = ctx.downloader.submit_answer(answer, event, day.day, part);
+    let Some(answer) = answer else {
+        return;
+    };
 
-        match res {
-            Ok(true) => println!("Correct"),
-            Ok(false) => println!("Incorrect"),
-            Err(err) => {
-                eprintln!(
-                    "unable to submit answer for '{}' day '{}' part '{}'. {:?}",
-                    event, day.day, part, err
-                );
+    match ctx.answer_store.read(event) {
+        Ok(answers) => {
+            if let Err(blocked) = answers.check(day.day, part, answer) {
+                println!("Not submitted, {}", blocked);
+                return;
             }
         }
+        Err(err) => {
+            eprintln!(
+                "unable to read answers for '{}', submitting without checking. {:?}",
+                event, err
+            );
+        }
+    }
+
+    let res = ctx.downloader.submit_answer(answer, event, day.day, part);
+
+    let stored = match res {
+        Ok(response) => {
+            println!("{}", response);
+            match response {
+                SubmitResponse::Correct => ctx.answer_store.record(event, day.day, part, answer),
+                SubmitResponse::Incorrect { hint, .. } => {
+                    ctx.answer_store.reject(event, day.day, part, answer, hint)
+                }
+                _ => Ok(()),
+            }
+        }
+        Err(err) => {
+            eprintln!(
+                "unable to submit answer for '{}' day '{}' part '{}'. {:?}",
+                event, day.day, part, err
+            );
+            Ok(())
+        }
+    };
+
+    if let Err(err) = stored {
+        eprintln!(
+            "unable to store answer for '{}' day '{}' part '{}'. {:?}",
+            event, day.day, part, err
+        );
     }
 }
 
-fn print_line<S: AsRef<str>>(
-    ctx: &Context,
-    _event: u32,
+fn verify_line(
+    ctx: &mut Context,
+    event: u32,
     day: u32,
     part: u32,
-    answer: S,
+    answer: Result<&Answer, &Failure>,
     metrics: &Metrics,
+    stats: Option<&Stats>,
 ) {
-    let answer = answer.as_ref();
-    if answer.len() <= 25 {
-        println!(
-            "{:>2}-{}:{:>25}{}",
+    let Some(verification) = ctx.verify.as_mut() else {
+        return;
+    };
+
+    if matches!(answer, Ok(Answer::Unsolved)) {
+        verification.unsolved += 1;
+    }
+
+    let value = answer.ok().and_then(Answer::value);
+    let check = value
+        .as_deref()
+        .map(|value| check_answer(ctx, event, day, part, value));
+
+    if let Some(format) = ctx.format {
+        let record = Record {
+            event,
             day,
-            part,
+            stage: Stage::Part(part),
             answer,
-            metrics.display(ctx.details)
-        );
+            metrics,
+            stats,
+            profile: ctx.profile.label(),
+            check: check.as_ref().map(|(status, expected)| Check {
+                status: *status,
+                expected: expected.as_deref(),
+            }),
+        };
+        println!("{}", format.record(&record));
+        return;
+    }
+
+    let (answer, value, (status, expected)) = match (answer, value, check) {
+        (Err(failure), _, _) => {
+            println!("{:>2}-{}:{:>25}  {}", day, part, failure.status(), failure);
+            return;
+        }
+        (Ok(answer), Some(value), Some(check)) => (answer, value, check),
+        (Ok(answer), _, _) => {
+            println!("{:>2}-{}:{:>25}", day, part, answer);
+            return;
+        }
+    };
+
+    let local = if matches!(status, CheckStatus::LocalMismatch) {
+        " (local answer)"
     } else {
-        println!(
-            "{:>2}-{}:{:>25}{}",
-            day,
-            part,
-            "",
-            metrics.display(ctx.details)
-        );
+        ""
+    };
+    let status = match (status, expected) {
+        (CheckStatus::Mismatch | CheckStatus::LocalMismatch, Some(expected))
+            if expected.contains('\n') =>
+        {
+            format!("MISMATCH, expected{}:\n{}", local, expected)
+        }
+        (CheckStatus::Mismatch | CheckStatus::LocalMismatch, expected) => {
+            format!(
+                "MISMATCH, expected {}{}",
+                expected.unwrap_or_default(),
+                local
+            )
+        }
+        (CheckStatus::Match, _) => "ok".to_string(),
+        (CheckStatus::LocalMatch, _) => "ok, local answer not yet accepted".to_string(),
+        (CheckStatus::Missing, _) => "no accepted answer".to_string(),
+        (CheckStatus::Recorded, _) => "recorded as local answer".to_string(),
+    };
+
+    if value.len() <= 25 && !value.contains('\n') {
+        println!("{:>2}-{}:{:>25}  {}", day, part, value, status);
+    } else {
+        println!("{:>2}-{}:{:>25}  {}", day, part, "", status);
         println!("{}", answer);
     }
 }
 
-fn usage() {
-    let usage = "Usage: advent-of-code [OPTIONS] [EVENT] [DAY]...
+fn check_answer(
+    ctx: &mut Context,
+    event: u32,
+    day: u32,
+    part: u32,
+    answer: &str,
+) -> (CheckStatus, Option<String>) {
+    let Some(verification) = ctx.verify.as_mut() else {
+        return (CheckStatus::Missing, None);
+    };
 
-Arguments:
+    if !verification.expected.contains_key(&event) {
+        let answers = match ctx.answer_store.read(event) {
+            Ok(answers) => answers,
+            Err(error) => {
+                eprintln!("unable to read answers for '{}'. {:?}", event, error);
+                std::process::exit(1)
+            }
+        };
+        verification.expected.insert(event, answers);
+    }
 
-	[EVENT]
-		Limit to solutions from a speific year, defaults to most recent year, 'all' will execute all events.
+    let answers = verification.expected.get(&event);
+    let accepted = answers.and_then(|a| a.get(day, part));
+    let local = answers.and_then(|a| a.local(day, part));
 
-	[DAY]...
-		Only execute specified days within the choosen event.
+    match (accepted, local) {
+        (Some(expected), _) if expected == answer => {
+            verification.matched += 1;
+            (CheckStatus::Match, Some(expected.to_string()))
+        }
+        (Some(expected), _) => {
+            verification.mismatched += 1;
+            (CheckStatus::Mismatch, Some(expected.to_string()))
+        }
+        (None, Some(expected)) if expected == answer => {
+            verification.matched += 1;
+            (CheckStatus::LocalMatch, Some(expected.to_string()))
+        }
+        (None, Some(expected)) => {
+            verification.mismatched += 1;
+            (CheckStatus::LocalMismatch, Some(expected.to_string()))
+        }
+        (None, None) if verification.update => {
+            verification.missing += 1;
+            match ctx.answer_store.record_local(event, day, part, answer) {
+                Ok(()) => {
+                    if let Some(answers) = verification.expected.get_mut(&event) {
+                        answers.set_local(day, part, answer);
+                    }
+                    (CheckStatus::Recorded, None)
+                }
+                Err(error) => {
+                    eprintln!(
+                        "unable to record answer for '{}' day '{}' part '{}'. {:?}",
+                        event, day, part, error
+                    );
+                    (CheckStatus::Missing, None)
+                }
+            }
+        }
+        (None, None) => {
+            verification.missing += 1;
+            (CheckStatus::Missing, None)
+        }
+    }
+}
 
-Options:
+fn print_line(
+    ctx: &Context,
+    event: u32,
+    day: u32,
+    stage: Stage,
+    answer: Result<&Answer, &Failure>,
+    metrics: &Metrics,
+    stats: Option<&Stats>,
+) {
+    if let Some(format) = ctx.format {
+        let record = Record {
+            event,
+            day,
+            stage,
+            answer,
+            metrics,
+            stats,
+            profile: ctx.profile.label(),
+            check: None,
+        };
+        println!("{}", format.record(&record));
+        return;
+    }
 
-	--submit	-s
-		Ask to submit answer after each solution. Requires '.session-key' file containing an Advent of Code authentication cookie value in the working directory.
+    let metrics = metrics.display(ctx.details);
+    let part = match stage {
+        Stage::Parse => 0,
+        Stage::Part(part) => part,
+    };
 
-	--details	-d
-		Display additional performance metrics.
-		
-	--input    -i
-	   Print the input for each day
+    match answer {
+        Ok(_) if stage == Stage::Parse => {
+            println!("{:>2}-{}:{:>25}{}", day, part_label(part), "parse", metrics);
+        }
+        Ok(answer @ Answer::Art(_)) => {
+            println!("{:>2}-{}:{:>25}{}", day, part, "", metrics);
+            println!("{}", answer);
+        }
+        Ok(answer) => {
+            let answer = answer.to_string();
+            if answer.len() <= 25 {
+                println!("{:>2}-{}:{:>25}{}", day, part, answer, metrics);
+            } else {
+                println!("{:>2}-{}:{:>25}{}", day, part, "", metrics);
+                println!("{}", answer);
+            }
+        }
+        Err(failure) => {
+            println!("{:>2}-{}:{:>25}{}", day, part, failure.status(), metrics);
+            println!("{}", failure);
+        }
+    }
 
-	--help	-h
-		Display this message.
+    if let Some(stats) = stats {
+        println!("{:>30}{}", "", stats);
+    }
+}
+
+fn part_label(part: u32) -> String {
+    if part == 0 {
+        "P".to_string()
+    } else {
+        part.to_string()
+    }
+}
 
-Examples:
+#[test]
+fn test_fill_template() {
+    let template = r##"pub fn examples() -> Vec<Example> {
+    let input = r#""#;
 
-	advent-of-code 2021
-		Execute all solutions from 2021.
+    vec![Example::new(input).part_one(0).part_two(0)]
+}
+"##;
+    let puzzle = Puzzle::new(
+        "<article><p>For example:</p><pre><code>a\"#b\n1\n</code></pre>\
+         <p>Total <code><em>142</em></code>.</p></article>",
+    );
+
+    let filled = fill_template(template, &puzzle);
+    assert!(filled.contains("let input = r##\"a\"#b\n1\"##;"));
+    assert!(filled.contains("vec![Example::new(input).part_one(142)]"));
+
+    assert_eq!(template, fill_template(template, &Puzzle::new("")));
 
-	advent-of-code -s 2023 22
-		Execute parts 1 and 2 from day 22 of 2023, prompt to submit the solution after each part.
+    let puzzle = Puzzle::new(
+        "<article><pre><code>1abc2\n</code></pre><p><code><em>142</em></code></p></article>\
+         <article><pre><code>two1nine\n</code></pre><p><code><em>281</em></code></p></article>",
+    );
+    let filled = fill_template(template, &puzzle);
+    assert!(filled.contains("let input = r#\"1abc2\"#;\n    let input_two = r#\"two1nine\"#;\n"));
+    assert!(filled.contains(
+        "vec![\n        Example::new(input).part_one(142),\n        Example::new(input_two).part_two(281),\n    ]"
+    ));
+}
+
+#[test]
+fn test_select_events() {
+    let events: HashMap<_, _> = solutions::events().into_iter().collect();
+    let events: &'static HashMap<u32, SolutionCollection> = Box::leak(Box::new(events));
+    let select = |args: &[&str]| {
+        let args = cli::parse_args(args.iter().map(|a| a.to_string())).unwrap();
+        select_events(events, &args.selection)
+            .map(|selected| selected.iter().map(|(event, _)| *event).collect::<Vec<_>>())
+    };
 
-	advent-of-code -d all
-		Execute all solutions with detailed metrics.
-";
-    println!("{}", usage);
+    let with_day_21 = select(&["all", "21"]).unwrap();
+    assert!(with_day_21.contains(&2023) && !with_day_21.contains(&2024));
+    assert!(!select(&["all", "13"]).unwrap().contains(&2025));
+    assert_eq!(select(&["2024", "1,21"]), Ok(vec![2024]));
+    assert!(select(&["2024", "21"]).is_err());
+    assert!(select(&["2025", "13-20"]).is_err());
 }
@@ -0,0 +1,239 @@
+use std::ops::{Div, Mul};
+
+/// Integers with a greatest common divisor, signed types use the magnitude of their values.
+pub trait Integer: Copy + Eq + Mul<Output = Self> + Div<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn gcd(self, other: Self) -> Self;
+}
+
+macro_rules! impl_unsigned {
+    ($($type:ty)+) => {
+        $(
+            impl Integer for $type {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn gcd(self, other: Self) -> Self {
+                    let (mut a, mut b) = (self, other);
+                    if a == 0 || b == 0 {
+                        return a | b;
+                    }
+
+                    let shift = (a | b).trailing_zeros();
+                    a >>= a.trailing_zeros();
+                    loop {
+                        b >>= b.trailing_zeros();
+                        if a > b {
+                            std::mem::swap(&mut a, &mut b);
+                        }
+                        b -= a;
+                        if b == 0 {
+                            return a << shift;
+                        }
+                    }
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_signed {
+    ($($type:ty)+) => {
+        $(
+            impl Integer for $type {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn gcd(self, other: Self) -> Self {
+                    self.unsigned_abs().gcd(other.unsigned_abs()) as Self
+                }
+            }
+        )+
+    };
+}
+
+impl_unsigned!(u8 u16 u32 u64 u128 usize);
+impl_signed!(i8 i16 i32 i64 i128 isize);
+
+/// Binary (Stein's) greatest common divisor, `gcd(0, n)` is `n`.
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    a.gcd(b)
+}
+
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    if a == T::ZERO || b == T::ZERO {
+        T::ZERO
+    } else {
+        a / a.gcd(b) * b
+    }
+}
+
+/// Least common multiple of every value, `1` when empty.
+pub fn lcm_iter<T: Integer>(iter: impl IntoIterator<Item = T>) -> T {
+    iter.into_iter().fold(T::ONE, lcm)
+}
+
+/// Returns `(g, x, y)` where `g` is the gcd of `a` and `b` and `a * x + b * y == g`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `a * b` mod `m` without overflowing, the result is always in `0..m`.
+pub fn mul_mod(a: i64, b: i64, m: i64) -> i64 {
+    (a as i128 * b as i128).rem_euclid(m as i128) as i64
+}
+
+pub fn mod_pow(base: i64, mut exp: u64, m: i64) -> i64 {
+    let mut base = base.rem_euclid(m);
+    let mut result = 1 % m;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+
+    result
+}
+
+/// The `x` in `0..m` where `a * x` is `1` mod `m`, if `a` and `m` are coprime.
+pub fn mod_inv(a: i64, m: i64) -> Option<i64> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// Chinese remainder theorem, finds the smallest `x` that is congruent to every `(residue, modulus)`
+/// pair and returns it along with the combined modulus. The moduli do not need to be coprime, `None`
+/// when the congruences conflict or the combined modulus overflows.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    congruences
+        .into_iter()
+        .try_fold((0, 1), |(r1, m1), (r2, m2)| {
+            let r2 = r2.rem_euclid(m2);
+            let g = gcd(m1, m2);
+            let diff = r2 - r1;
+            if diff % g != 0 {
+                return None;
+            }
+
+            let m2g = m2 / g;
+            let modulus = m1.checked_mul(m2g)?;
+            let t = mul_mod((diff / g).rem_euclid(m2g), mod_inv(m1 / g, m2g)?, m2g);
+            Some(((mul_mod(m1, t, modulus) + r1) % modulus, modulus))
+        })
+}
+
+/// The map `x -> a * x + b` mod `modulus`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Affine {
+    pub a: i64,
+    pub b: i64,
+    pub modulus: i64,
+}
+
+impl Affine {
+    pub fn new(a: i64, b: i64, modulus: i64) -> Self {
+        Self {
+            a: a.rem_euclid(modulus),
+            b: b.rem_euclid(modulus),
+            modulus,
+        }
+    }
+
+    pub fn identity(modulus: i64) -> Self {
+        Self::new(1, 0, modulus)
+    }
+
+    pub fn apply(&self, x: i64) -> i64 {
+        (mul_mod(self.a, x, self.modulus) + self.b) % self.modulus
+    }
+
+    /// The map that applies `self` and then `next`.
+    pub fn then(&self, next: &Self) -> Self {
+        Self {
+            a: mul_mod(next.a, self.a, self.modulus),
+            b: (mul_mod(next.a, self.b, self.modulus) + next.b) % self.modulus,
+            modulus: self.modulus,
+        }
+    }
+
+    /// The map that applies `self` `n` times.
+    pub fn pow(&self, mut n: u64) -> Self {
+        let mut result = Self::identity(self.modulus);
+        let mut base = *self;
+
+        while n > 0 {
+            if n & 1 == 1 {
+                result = result.then(&base);
+            }
+            base = base.then(&base);
+            n >>= 1;
+        }
+
+        result
+    }
+
+    /// The map that undoes `self`, if `a` is invertible mod `modulus`.
+    pub fn invert(&self) -> Option<Self> {
+        let a = mod_inv(self.a, self.modulus)?;
+        Some(Self::new(
+            a,
+            -mul_mod(a, self.b, self.modulus),
+            self.modulus,
+        ))
+    }
+}
+
+#[test]
+fn test_math() {
+    assert_eq!(gcd(48u64, 18), 6);
+    assert_eq!(gcd(0u32, 7), 7);
+    assert_eq!(gcd(-12i32, 18), 6);
+    assert_eq!(lcm(4usize, 6), 12);
+    assert_eq!(lcm_iter([3u64, 4, 10]), 60);
+    assert_eq!(lcm_iter(Vec::<u64>::new()), 1);
+
+    let (g, x, y) = extended_gcd(240, 46);
+    assert_eq!((g, 240 * x + 46 * y), (2, 2));
+
+    assert_eq!(mod_pow(3, 200, 50), 1);
+    assert_eq!(mod_inv(3, 11), Some(4));
+    assert_eq!(mod_inv(4, 10), None);
+    let big = 119315717514047;
+    assert_eq!(mul_mod(mod_inv(big - 2, big).unwrap(), big - 2, big), 1);
+
+    assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+    assert_eq!(crt([(1, 4), (3, 6)]), Some((9, 12)));
+    assert_eq!(crt([(1, 4), (2, 6)]), None);
+    let (p, q) = (3037000493, 3037000453);
+    assert_eq!(
+        crt([(p - 1, p), (q - 2, q)]),
+        Some((8531618982451131566, 9223371873002223329))
+    );
+
+    let f = Affine::new(3, 4, 11);
+    let g = Affine::new(-2, 1, 11);
+    assert_eq!(f.then(&g).apply(5), g.apply(f.apply(5)));
+    assert_eq!(f.pow(3).apply(2), f.apply(f.apply(f.apply(2))));
+    assert_eq!(f.invert().unwrap().apply(f.apply(7)), 7);
+    assert_eq!(f.pow(0), Affine::identity(11));
+}
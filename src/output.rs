@@ -0,0 +1,350 @@
+use std::fmt::Write;
+
+use advent::Answer;
+
+use crate::Metrics;
+use crate::profiler::Stats;
+use crate::runner::Failure;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Csv,
+    Tsv,
+}
+
+impl Format {
+    pub fn parse(s: &str) -> Result<Option<Self>, String> {
+        match s {
+            "text" => Ok(None),
+            "json" => Ok(Some(Format::Json)),
+            "csv" => Ok(Some(Format::Csv)),
+            "tsv" => Ok(Some(Format::Tsv)),
+            _ => Err(format!(
+                "invalid format '{}', expected text, json, csv or tsv",
+                s
+            )),
+        }
+    }
+
+    pub fn header(&self) -> Option<String> {
+        match self {
+            Format::Json => None,
+            Format::Csv => Some(FIELDS.join(",")),
+            Format::Tsv => Some(FIELDS.join("\t")),
+        }
+    }
+
+    pub fn record(&self, record: &Record) -> String {
+        let Record {
+            event,
+            day,
+            stage,
+            answer,
+            metrics,
+            stats,
+            profile,
+            check,
+        } = record;
+
+        let stats = stats.copied().unwrap_or(Stats {
+            samples: 1,
+            min: metrics.duration,
+            median: metrics.duration,
+            mean: metrics.duration,
+            p95: metrics.duration,
+            std_dev: std::time::Duration::ZERO,
+        });
+
+        let (status, answer, error) = match answer {
+            Ok(answer) => (answer.status(), answer.value(), String::new()),
+            Err(Failure::Panic(message)) => ("panic", None, message.clone()),
+            Err(failure @ Failure::Timeout(_)) => ("timeout", None, failure.to_string()),
+            Err(failure @ Failure::Error(_)) => ("error", None, failure.to_string()),
+        };
+
+        let (stage, part) = match stage {
+            Stage::Parse => ("parse", Value::Null),
+            Stage::Part(part) => ("part", Value::Number(*part as u64)),
+        };
+
+        let values = [
+            Value::Number(*event as u64),
+            Value::Number(*day as u64),
+            Value::Str(stage),
+            part,
+            Value::Str(status),
+            Value::Str(answer.as_deref().unwrap_or_default()),
+            Value::Number(metrics.duration.as_nanos() as u64),
+            Value::Number(metrics.allocations),
+            Value::Number(metrics.peak_memory as u64),
+            Value::Number(metrics.cycles),
+            Value::Number(metrics.instructions),
+            Value::Number(stats.samples as u64),
+            Value::Number(stats.min.as_nanos() as u64),
+            Value::Number(stats.median.as_nanos() as u64),
+            Value::Number(stats.mean.as_nanos() as u64),
+            Value::Number(stats.p95.as_nanos() as u64),
+            Value::Number(stats.std_dev.as_nanos() as u64),
+            Value::Bool(metrics.concurrent),
+            Value::Str(&error),
+            Value::Str(profile),
+            check.map_or(Value::Null, |c| Value::Str(c.status.name())),
+            check
+                .and_then(|c| c.expected)
+                .map_or(Value::Null, Value::Str),
+        ];
+
+        let mut out = String::new();
+        for (idx, (name, value)) in FIELDS.iter().zip(values).enumerate() {
+            let _ = match self {
+                Format::Json => {
+                    let separator = if idx == 0 { '{' } else { ',' };
+                    match value {
+                        Value::Str(s) => write!(out, "{}\"{}\":{}", separator, name, JsonStr(s)),
+                        Value::Number(n) => write!(out, "{}\"{}\":{}", separator, name, n),
+                        Value::Bool(b) => write!(out, "{}\"{}\":{}", separator, name, b),
+                        Value::Null => write!(out, "{}\"{}\":null", separator, name),
+                    }
+                }
+                Format::Csv => {
+                    if idx > 0 {
+                        out.push(',');
+                    }
+                    match value {
+                        Value::Str(s) => write!(out, "{}", CsvStr(s)),
+                        Value::Number(n) => write!(out, "{}", n),
+                        Value::Bool(b) => write!(out, "{}", b),
+                        Value::Null => Ok(()),
+                    }
+                }
+                Format::Tsv => {
+                    if idx > 0 {
+                        out.push('\t');
+                    }
+                    match value {
+                        Value::Str(s) => write!(out, "{}", TsvStr(s)),
+                        Value::Number(n) => write!(out, "{}", n),
+                        Value::Bool(b) => write!(out, "{}", b),
+                        Value::Null => Ok(()),
+                    }
+                }
+            };
+        }
+
+        if *self == Format::Json {
+            out.push('}');
+        }
+
+        out
+    }
+}
+
+pub struct Record<'a> {
+    pub event: u32,
+    pub day: u32,
+    pub stage: Stage,
+    pub answer: Result<&'a Answer, &'a Failure>,
+    pub metrics: &'a Metrics,
+    pub stats: Option<&'a Stats>,
+    pub profile: &'a str,
+    pub check: Option<Check<'a>>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Stage {
+    Parse,
+    Part(u32),
+}
+
+/// Outcome of comparing an answer against the answer store with `verify`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Check<'a> {
+    pub status: CheckStatus,
+    pub expected: Option<&'a str>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CheckStatus {
+    Match,
+    Mismatch,
+    /// Compared against an answer recorded by `verify --update`, not one accepted by the server
+    LocalMatch,
+    LocalMismatch,
+    Missing,
+    Recorded,
+}
+
+impl CheckStatus {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CheckStatus::Match => "match",
+            CheckStatus::Mismatch => "mismatch",
+            CheckStatus::LocalMatch => "local_match",
+            CheckStatus::LocalMismatch => "local_mismatch",
+            CheckStatus::Missing => "missing",
+            CheckStatus::Recorded => "recorded",
+        }
+    }
+}
+
+enum Value<'a> {
+    Str(&'a str),
+    Number(u64),
+    Bool(bool),
+    Null,
+}
+
+const FIELDS: [&str; 22] = [
+    "year",
+    "day",
+    "stage",
+    "part",
+    "status",
+    "answer",
+    "duration_ns",
+    "allocations",
+    "peak_memory",
+    "cycles",
+    "instructions",
+    "samples",
+    "min_ns",
+    "median_ns",
+    "mean_ns",
+    "p95_ns",
+    "std_dev_ns",
+    "concurrent",
+    "error",
+    "profile",
+    "check",
+    "expected",
+];
+
+pub struct JsonStr<'a>(pub &'a str);
+
+impl std::fmt::Display for JsonStr<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_char('"')?;
+        for c in self.0.chars() {
+            match c {
+                '"' => f.write_str("\\\"")?,
+                '\\' => f.write_str("\\\\")?,
+                '\n' => f.write_str("\\n")?,
+                '\r' => f.write_str("\\r")?,
+                '\t' => f.write_str("\\t")?,
+                c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+                c => f.write_char(c)?,
+            }
+        }
+        f.write_char('"')
+    }
+}
+
+struct CsvStr<'a>(&'a str);
+
+impl std::fmt::Display for CsvStr<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.contains([',', '"', '\n', '\r']) {
+            write!(f, "\"{}\"", self.0.replace('"', "\"\""))
+        } else {
+            f.write_str(self.0)
+        }
+    }
+}
+
+struct TsvStr<'a>(&'a str);
+
+impl std::fmt::Display for TsvStr<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '\\' => f.write_str("\\\\")?,
+                '\t' => f.write_str("\\t")?,
+                '\n' => f.write_str("\\n")?,
+                '\r' => f.write_str("\\r")?,
+                c => f.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_records() {
+    let metrics = Metrics {
+        instructions: 5,
+        cycles: 4,
+        duration: std::time::Duration::from_micros(1),
+        allocations: 2,
+        peak_memory: 3,
+        concurrent: false,
+    };
+    let answer = Answer::Art("#.\"#\n,.#".to_string());
+    let record = Record {
+        event: 2022,
+        day: 10,
+        stage: Stage::Part(2),
+        answer: Ok(&answer),
+        metrics: &metrics,
+        stats: None,
+        profile: "default",
+        check: None,
+    };
+
+    assert_eq!(
+        Format::Json.record(&record),
+        r##"{"year":2022,"day":10,"stage":"part","part":2,"status":"ok","answer":"#.\"#\n,.#","duration_ns":1000,"allocations":2,"peak_memory":3,"cycles":4,"instructions":5,"samples":1,"min_ns":1000,"median_ns":1000,"mean_ns":1000,"p95_ns":1000,"std_dev_ns":0,"concurrent":false,"error":"","profile":"default","check":null,"expected":null}"##
+    );
+    assert_eq!(
+        Format::Csv.record(&record),
+        "2022,10,part,2,ok,\"#.\"\"#\n,.#\",1000,2,3,4,5,1,1000,1000,1000,1000,0,false,,default,,"
+    );
+    assert_eq!(
+        Format::Tsv.record(&record),
+        "2022\t10\tpart\t2\tok\t#.\"#\\n,.#\t1000\t2\t3\t4\t5\t1\t1000\t1000\t1000\t1000\t0\tfalse\t\tdefault\t\t"
+    );
+
+    let failure = Failure::Panic("boom, at src/x.rs:1".to_string());
+    let record = Record {
+        answer: Err(&failure),
+        profile: "alice",
+        ..record
+    };
+    assert_eq!(
+        Format::Csv.record(&record),
+        "2022,10,part,2,panic,,1000,2,3,4,5,1,1000,1000,1000,1000,0,false,\"boom, at src/x.rs:1\",alice,,"
+    );
+
+    let record = Record {
+        answer: Ok(&Answer::Unsolved),
+        ..record
+    };
+    assert_eq!(
+        Format::Csv.record(&record),
+        "2022,10,part,2,unsolved,,1000,2,3,4,5,1,1000,1000,1000,1000,0,false,,alice,,"
+    );
+
+    let parsed = Answer::Text(String::new());
+    let record = Record {
+        stage: Stage::Parse,
+        answer: Ok(&parsed),
+        ..record
+    };
+    assert!(
+        Format::Json
+            .record(&record)
+            .starts_with(r#"{"year":2022,"day":10,"stage":"parse","part":null,"status":"ok""#)
+    );
+
+    let answer = Answer::Integer(12);
+    let record = Record {
+        stage: Stage::Part(1),
+        answer: Ok(&answer),
+        check: Some(Check {
+            status: CheckStatus::Mismatch,
+            expected: Some("13"),
+        }),
+        ..record
+    };
+    assert!(Format::Csv.record(&record).ends_with(",alice,mismatch,13"));
+}
@@ -0,0 +1,319 @@
+use std::str::FromStr;
+
+use crate::ParseError;
+use crate::grid::Grid;
+
+/// Integers that can be read from the input, `SIGNED` types also accept a leading `-`.
+pub trait Int: FromStr + Copy {
+    const SIGNED: bool;
+}
+
+macro_rules! impl_int {
+    ($signed:literal => $($type:ty)+) => {
+        $(
+            impl Int for $type {
+                const SIGNED: bool = $signed;
+            }
+        )+
+    };
+}
+
+impl_int!(false => u8 u16 u32 u64 u128 usize);
+impl_int!(true => i8 i16 i32 i64 i128 isize);
+
+/// Every integer in `s`, any other chars separate them. Numbers that do not fit in `T` are
+/// skipped, and for signed types a `-` between two numbers is read as the sign of the second.
+pub fn ints<T: Int>(s: &str) -> impl Iterator<Item = T> + '_ {
+    let bytes = s.as_bytes();
+    let mut idx = 0;
+
+    std::iter::from_fn(move || {
+        while idx < bytes.len() {
+            let start = idx;
+            let negative = T::SIGNED
+                && bytes[idx] == b'-'
+                && bytes.get(idx + 1).is_some_and(u8::is_ascii_digit);
+            idx += 1;
+
+            if negative || bytes[start].is_ascii_digit() {
+                while bytes.get(idx).is_some_and(u8::is_ascii_digit) {
+                    idx += 1;
+                }
+                if let Ok(n) = s[start..idx].parse() {
+                    return Some(n);
+                }
+            }
+        }
+
+        None
+    })
+}
+
+/// Parses every line of `input` with `line`, which has to consume all of it.
+pub fn lines<'a, T>(
+    input: &'a str,
+    line: impl FnMut(&mut Parser<'a>) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    Parser::within(input, input.trim_end()).parse_lines(line)
+}
+
+/// Cursor over a slice of the input, errors are reported at their position in the whole input.
+#[derive(Debug, Copy, Clone)]
+pub struct Parser<'a> {
+    input: &'a str,
+    rest: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self { input, rest: input }
+    }
+
+    /// Parser over `at`, which should be a slice of `input` such as one of its lines.
+    pub fn within(input: &'a str, at: &'a str) -> Self {
+        Self { input, rest: at }
+    }
+
+    /// The input that has not been consumed yet.
+    pub fn rest(&self) -> &'a str {
+        self.rest
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest.is_empty()
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.rest.chars().next()
+    }
+
+    /// Error at the current position.
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::at(self.input, self.rest, message)
+    }
+
+    /// Fails unless all of the input has been consumed.
+    pub fn end(&self) -> Result<(), ParseError> {
+        if self.rest.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error(format!("unexpected '{}'", self.rest)))
+        }
+    }
+
+    fn advance(&mut self, len: usize) -> &'a str {
+        let (taken, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        taken
+    }
+
+    pub fn char(&mut self) -> Result<char, ParseError> {
+        let c = self
+            .peek()
+            .ok_or_else(|| self.error("expected a character"))?;
+        self.advance(c.len_utf8());
+        Ok(c)
+    }
+
+    pub fn token(&mut self, token: &str) -> Result<(), ParseError> {
+        if self.try_token(token) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected '{}'", token)))
+        }
+    }
+
+    /// Consumes `token` if the input starts with it.
+    pub fn try_token(&mut self, token: &str) -> bool {
+        match self.rest.strip_prefix(token) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn int<T: Int>(&mut self) -> Result<T, ParseError> {
+        let bytes = self.rest.as_bytes();
+        let sign = usize::from(T::SIGNED && bytes.first() == Some(&b'-'));
+        let digits = bytes[sign..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        if digits == 0 {
+            return Err(self.error("expected a number"));
+        }
+
+        let number = &self.rest[..sign + digits];
+        let n = number
+            .parse()
+            .map_err(|_| self.error(format!("number '{}' is out of range", number)))?;
+        self.advance(number.len());
+        Ok(n)
+    }
+
+    pub fn take_while(&mut self, mut f: impl FnMut(char) -> bool) -> &'a str {
+        let len = self.rest.find(|c| !f(c)).unwrap_or(self.rest.len());
+        self.advance(len)
+    }
+
+    pub fn take_until(&mut self, mut f: impl FnMut(char) -> bool) -> &'a str {
+        self.take_while(|c| !f(c))
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    /// Parses `item` until it fails, leaving the parser just after the last item that succeeded.
+    pub fn many<T>(&mut self, mut item: impl FnMut(&mut Self) -> Result<T, ParseError>) -> Vec<T> {
+        let mut items = Vec::new();
+        loop {
+            let mut attempt = *self;
+            match item(&mut attempt) {
+                Ok(value) if attempt.rest.len() < self.rest.len() => {
+                    *self = attempt;
+                    items.push(value);
+                }
+                _ => return items,
+            }
+        }
+    }
+
+    /// One or more of `item` separated by `separator`.
+    pub fn separated<T>(
+        &mut self,
+        separator: &str,
+        mut item: impl FnMut(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        let mut items = vec![item(self)?];
+        while self.try_token(separator) {
+            items.push(item(self)?);
+        }
+
+        Ok(items)
+    }
+
+    /// The rest of the current line, moving past its line ending.
+    pub fn line(&mut self) -> Option<Self> {
+        if self.rest.is_empty() {
+            return None;
+        }
+
+        let (line, rest) = self.rest.split_once('\n').unwrap_or((self.rest, ""));
+        self.rest = rest;
+        Some(Self::within(self.input, line.trim_end_matches('\r')))
+    }
+
+    pub fn lines(mut self) -> impl Iterator<Item = Self> {
+        std::iter::from_fn(move || self.line())
+    }
+
+    /// Parses each of the remaining lines with `line`, which has to consume all of it.
+    pub fn parse_lines<T>(
+        self,
+        mut line: impl FnMut(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        self.lines()
+            .map(|mut parser| {
+                let value = line(&mut parser)?;
+                parser.end()?;
+                Ok(value)
+            })
+            .collect()
+    }
+
+    /// Everything up to the next blank line, moving past it.
+    pub fn section(&mut self) -> Option<Self> {
+        let rest = self.rest.trim_start_matches(['\r', '\n']);
+        if rest.is_empty() {
+            self.rest = rest;
+            return None;
+        }
+
+        let end = rest
+            .match_indices('\n')
+            .map(|(idx, _)| idx)
+            .find(|&idx| rest[idx + 1..].starts_with(['\n', '\r']))
+            .unwrap_or(rest.len());
+        self.rest = &rest[end..];
+        Some(Self::within(self.input, rest[..end].trim_end()))
+    }
+
+    pub fn sections(mut self) -> impl Iterator<Item = Self> {
+        std::iter::from_fn(move || self.section())
+    }
+
+    /// The rest of the input as a grid, see `Grid::parse`.
+    pub fn grid<T>(&mut self, cell: impl FnMut(char) -> Option<T>) -> Result<Grid<T>, ParseError> {
+        let grid = Grid::parse_within(self.input, self.rest, cell)?;
+        self.advance(self.rest.len());
+        Ok(grid)
+    }
+}
+
+#[test]
+fn test_parse() {
+    let mut p = Parser::new("123abc");
+    assert_eq!(p.int::<u64>(), Ok(123));
+    assert_eq!(p.rest(), "abc");
+    assert!(p.int::<u64>().is_err());
+    assert_eq!(p.take_until(|c| c == 'c'), "ab");
+    assert_eq!((p.char(), p.end()), (Ok('c'), Ok(())));
+
+    let mut p = Parser::new("abc123");
+    assert!(p.token("abc123efg").is_err());
+    assert_eq!(p.token("abc"), Ok(()));
+    assert_eq!((p.int::<i32>(), p.is_empty()), (Ok(123), true));
+
+    let mut p = Parser::new("-5,300,7 x");
+    assert_eq!(p.int::<i8>(), Ok(-5));
+    assert!(!p.try_token(";") && p.try_token(","));
+    let err = p.int::<u8>().unwrap_err();
+    assert_eq!(
+        (err.column, err.message.as_str()),
+        (4, "number '300' is out of range")
+    );
+    assert_eq!(p.separated(",", Parser::int::<u32>), Ok(vec![300, 7]));
+    p.skip_whitespace();
+    assert_eq!(p.many(|p| p.token("x")).len(), 1);
+
+    let input = "x=-2, y=15: 3-4\n";
+    assert_eq!(ints::<i32>(input).collect::<Vec<_>>(), [-2, 15, 3, -4]);
+    assert_eq!(ints::<u32>(input).collect::<Vec<_>>(), [2, 15, 3, 4]);
+
+    let input = "1 2\n3 x\n";
+    let err = lines(input, |p| p.separated(" ", Parser::int::<u32>)).unwrap_err();
+    assert_eq!((err.line, err.column), (2, 3));
+    let err = Parser::new("1 2\n3")
+        .lines()
+        .last()
+        .unwrap()
+        .token(" ")
+        .unwrap_err();
+    assert!(err.to_string().ends_with("it may be truncated"));
+
+    let input = "#.\n.#\n\n\nab\ncd\n\n#x\n";
+    let mut sections = Parser::new(input).sections();
+    let grid = sections.next().unwrap().grid(|c| Some(c == '#')).unwrap();
+    assert_eq!((grid.width(), grid.height()), (2, 2));
+    let lines: Vec<_> = sections.next().unwrap().lines().map(|l| l.rest()).collect();
+    assert_eq!(lines, ["ab", "cd"]);
+    let err = sections
+        .next()
+        .unwrap()
+        .grid(|c| (c == '#').then_some(()))
+        .unwrap_err();
+    assert_eq!((err.line, err.column), (8, 2));
+    assert!(sections.next().is_none());
+
+    let mut sections = Parser::new("a\n\n1\nx\n").sections();
+    sections.next();
+    let err = sections
+        .next()
+        .unwrap()
+        .parse_lines(Parser::int::<u8>)
+        .unwrap_err();
+    assert_eq!((err.line, err.column), (4, 1));
+}
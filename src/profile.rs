@@ -0,0 +1,80 @@
+use std::path::PathBuf;
+
+use advent::Input;
+use advent::input::StoreConfig;
+
+pub const PROFILES_DIR: &str = "profiles";
+const SESSION_KEY_PATH: &str = ".session-key";
+const DEFAULT_PROFILE: &str = "default";
+
+pub struct Profile {
+    pub name: Option<String>,
+    pub session_key: PathBuf,
+    pub store: StoreConfig,
+}
+
+impl Profile {
+    pub fn default(store: StoreConfig) -> Self {
+        Self {
+            name: None,
+            session_key: PathBuf::from(SESSION_KEY_PATH),
+            store,
+        }
+    }
+
+    pub fn named(name: &str, base: &StoreConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        if name == DEFAULT_PROFILE {
+            return Ok(Self::default(base.clone()));
+        }
+
+        let dir = PathBuf::from(PROFILES_DIR).join(name);
+        if !dir.is_dir() {
+            return Err(format!("profile '{}' not found in '{}'", name, dir.display()).into());
+        }
+
+        Ok(Self {
+            name: Some(name.to_string()),
+            session_key: dir.join(SESSION_KEY_PATH),
+            store: StoreConfig {
+                kind: base.kind,
+                root: dir.join("input"),
+            },
+        })
+    }
+
+    pub fn all(base: &StoreConfig) -> Result<Vec<Self>, Box<dyn std::error::Error>> {
+        let mut names = Vec::new();
+
+        match std::fs::read_dir(PROFILES_DIR) {
+            Ok(dir) => {
+                for entry in dir {
+                    let entry = entry?;
+                    if entry.file_type()?.is_dir()
+                        && let Some(name) = entry.file_name().to_str()
+                    {
+                        names.push(name.to_string());
+                    }
+                }
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => (),
+            Err(err) => return Err(err.into()),
+        }
+
+        names.sort();
+
+        let mut profiles = vec![Self::default(base.clone())];
+        for name in names {
+            profiles.push(Self::named(&name, base)?);
+        }
+
+        Ok(profiles)
+    }
+
+    pub fn label(&self) -> &str {
+        self.name.as_deref().unwrap_or(DEFAULT_PROFILE)
+    }
+
+    pub fn input(&self) -> Result<Input, Box<dyn std::error::Error>> {
+        Input::open(&self.store)
+    }
+}
@@ -73,6 +73,7 @@ mod linux {
                 duration,
                 allocations,
                 peak_memory,
+                concurrent: false,
             }
         }
     }
@@ -111,21 +112,38 @@ mod fallback {
                 peak_memory,
                 instructions: 0,
                 cycles: 0,
+                concurrent: false,
             }
         }
     }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
 pub struct Metrics {
     pub instructions: u64,
     pub cycles: u64,
     pub duration: std::time::Duration,
     pub allocations: u64,
     pub peak_memory: usize,
+    pub concurrent: bool,
 }
 
 impl Metrics {
+    pub fn aggregate(samples: &[Metrics]) -> Metrics {
+        let count = samples.len().max(1) as u64;
+        let mut durations: Vec<_> = samples.iter().map(|m| m.duration).collect();
+        durations.sort_unstable();
+
+        Metrics {
+            instructions: samples.iter().map(|m| m.instructions).sum::<u64>() / count,
+            cycles: samples.iter().map(|m| m.cycles).sum::<u64>() / count,
+            duration: median(&durations),
+            allocations: samples.iter().map(|m| m.allocations).sum::<u64>() / count,
+            peak_memory: samples.iter().map(|m| m.peak_memory).max().unwrap_or(0),
+            concurrent: samples.iter().any(|m| m.concurrent),
+        }
+    }
+
     pub fn display(&self, detailed: bool) -> MetricsDisplay<'_> {
         MetricsDisplay(detailed, self)
     }
@@ -136,28 +154,101 @@ pub struct MetricsDisplay<'a>(bool, &'a Metrics);
 
 impl<'a> std::fmt::Display for MetricsDisplay<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let concurrent = if self.1.concurrent { " *" } else { "" };
         if self.0 {
             write!(
                 f,
-                "{:>12}{:>10} allocs.{:>10} mem.{:>15} cyc.{:>15} instr.",
+                "{:>12}{:>10} allocs.{:>10} mem.{:>15} cyc.{:>15} instr.{}",
                 Time(self.1.duration),
                 self.1.allocations,
                 Bytes(self.1.peak_memory),
                 self.1.cycles,
-                self.1.instructions
+                self.1.instructions,
+                concurrent
             )
         } else {
             write!(
                 f,
-                "{:>12}{:>10} allocations{:>10} peak memory",
+                "{:>12}{:>10} allocations{:>10} peak memory{}",
                 Time(self.1.duration),
                 self.1.allocations,
-                Bytes(self.1.peak_memory)
+                Bytes(self.1.peak_memory),
+                concurrent
             )
         }
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub std_dev: Duration,
+}
+
+impl Stats {
+    pub fn new(durations: &[Duration]) -> Self {
+        let mut sorted = durations.to_vec();
+        sorted.sort_unstable();
+
+        let samples = sorted.len();
+        let total: Duration = sorted.iter().sum();
+        let mean = total / samples.max(1) as u32;
+
+        let p95_rank = (samples * 95).div_ceil(100).max(1);
+        let p95 = sorted.get(p95_rank - 1).copied().unwrap_or_default();
+
+        let variance = if samples > 1 {
+            let mean = mean.as_secs_f64();
+            sorted
+                .iter()
+                .map(|d| (d.as_secs_f64() - mean).powi(2))
+                .sum::<f64>()
+                / (samples - 1) as f64
+        } else {
+            0.0
+        };
+
+        Stats {
+            samples,
+            min: sorted.first().copied().unwrap_or_default(),
+            median: median(&sorted),
+            mean,
+            p95,
+            std_dev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+impl std::fmt::Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min{:>9}  median{:>9}  mean{:>9}  p95{:>9}  std. dev.{:>9}  ({} samples)",
+            Time(self.min),
+            Time(self.median),
+            Time(self.mean),
+            Time(self.p95),
+            Time(self.std_dev),
+            self.samples
+        )
+    }
+}
+
+fn median(sorted: &[Duration]) -> Duration {
+    let mid = sorted.len() / 2;
+    if sorted.is_empty() {
+        Duration::ZERO
+    } else if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2
+    } else {
+        sorted[mid]
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct Time(pub Duration);
 
@@ -166,6 +257,8 @@ impl std::fmt::Display for Time {
         let micros = self.0.as_micros();
         let s = if micros > 2000 {
             format!("{}ms", micros / 1000)
+        } else if micros < 10 {
+            format!("{:.1}us", self.0.as_nanos() as f64 / 1000.0)
         } else {
             format!("{}us", micros)
         };
@@ -296,3 +389,20 @@ unsafe impl GlobalAlloc for CountingAlloc {
         ret
     }
 }
+
+#[test]
+fn test_stats() {
+    let durations: Vec<_> = [5, 1, 4, 2, 3].map(Duration::from_micros).to_vec();
+    let stats = Stats::new(&durations);
+
+    assert_eq!(stats.samples, 5);
+    assert_eq!(stats.min, Duration::from_micros(1));
+    assert_eq!(stats.median, Duration::from_micros(3));
+    assert_eq!(stats.mean, Duration::from_micros(3));
+    assert_eq!(stats.p95, Duration::from_micros(5));
+    assert_eq!(stats.std_dev.as_nanos(), 1581);
+
+    let stats = Stats::new(&durations[..4]);
+    assert_eq!(stats.median, Duration::from_nanos(3000));
+    assert_eq!(stats.p95, Duration::from_micros(5));
+}
@@ -0,0 +1,245 @@
+pub struct Puzzle {
+    html: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PuzzlePart {
+    pub examples: Vec<String>,
+    pub example_answer: Option<String>,
+}
+
+impl Puzzle {
+    pub fn new<S: Into<String>>(html: S) -> Self {
+        Self { html: html.into() }
+    }
+
+    pub fn html(&self) -> &str {
+        &self.html
+    }
+
+    fn articles(&self) -> impl Iterator<Item = &str> {
+        let mut rest = self.html.as_str();
+        std::iter::from_fn(move || {
+            let start = rest.find("<article")?;
+            let body = &rest[start..];
+            let body = &body[body.find('>')? + 1..];
+            let end = body.find("</article>")?;
+            rest = &body[end..];
+            Some(&body[..end])
+        })
+    }
+
+    pub fn title(&self) -> Option<String> {
+        let article = self.articles().next()?;
+        let start = article.find("<h2")?;
+        let heading = &article[start..];
+        let heading = &heading[heading.find('>')? + 1..];
+        let end = heading.find("</h2>")?;
+        let title = decode_entities(&strip_tags(&heading[..end]));
+
+        Some(title.trim_matches(|c| c == '-' || c == ' ').to_string())
+    }
+
+    pub fn parts(&self) -> Vec<PuzzlePart> {
+        self.articles()
+            .map(|article| PuzzlePart {
+                examples: code_blocks(article),
+                example_answer: example_answer(article),
+            })
+            .collect()
+    }
+
+    pub fn accepted_answers(&self) -> Vec<String> {
+        let mut answers = Vec::new();
+        let mut rest = self.html.as_str();
+        let marker = "Your puzzle answer was <code>";
+
+        while let Some(start) = rest.find(marker) {
+            rest = &rest[start + marker.len()..];
+            if let Some(end) = rest.find("</code>") {
+                answers.push(decode_entities(&rest[..end]));
+                rest = &rest[end..];
+            }
+        }
+
+        answers
+    }
+
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        for article in self.articles() {
+            let article = article
+                .replace("</h2>", "</h2>\n\n")
+                .replace("</p>", "</p>\n\n")
+                .replace("</li>", "</li>\n")
+                .replace("<li>", "<li>  - ")
+                .replace("</pre>", "</pre>\n");
+            text.push_str(&decode_entities(&strip_tags(&article)));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+fn code_blocks(article: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut rest = article;
+
+    while let Some(start) = rest.find("<pre><code>") {
+        rest = &rest[start + "<pre><code>".len()..];
+        let Some(end) = rest.find("</code></pre>") else {
+            break;
+        };
+        blocks.push(decode_entities(&strip_tags(&rest[..end])));
+        rest = &rest[end..];
+    }
+
+    blocks
+}
+
+fn example_answer(article: &str) -> Option<String> {
+    let code_em = article
+        .rfind("<code><em>")
+        .map(|idx| (idx, "<code><em>", "</em></code>"));
+    let em_code = article
+        .rfind("<em><code>")
+        .map(|idx| (idx, "<em><code>", "</code></em>"));
+
+    let (start, open, close) = match (code_em, em_code) {
+        (Some(a), Some(b)) => {
+            if a.0 > b.0 {
+                a
+            } else {
+                b
+            }
+        }
+        (a, b) => a.or(b)?,
+    };
+
+    let answer = &article[start + open.len()..];
+    let end = answer.find(close)?;
+
+    Some(decode_entities(&strip_tags(&answer[..end])))
+}
+
+pub fn calendar_days(html: &str, event: u32) -> Vec<u32> {
+    let prefix = format!("href=\"/{}/day/", event);
+    let mut days: Vec<u32> = html
+        .match_indices(&prefix)
+        .filter_map(|(idx, _)| {
+            let rest = &html[idx + prefix.len()..];
+            let end = rest.find('"')?;
+            rest[..end].parse().ok()
+        })
+        .collect();
+
+    days.sort_unstable();
+    days.dedup();
+    days
+}
+
+pub fn html_text(html: &str) -> String {
+    decode_entities(&strip_tags(html))
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+
+    text
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest.find(';').map(|end| (&rest[1..end], end));
+        let replacement = entity.and_then(|(name, end)| {
+            let c = match name {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => ' ',
+                _ if name.starts_with("#x") => {
+                    char::from_u32(u32::from_str_radix(&name[2..], 16).ok()?)?
+                }
+                _ if name.starts_with('#') => char::from_u32(name[1..].parse().ok()?)?,
+                _ => return None,
+            };
+            Some((c, end))
+        });
+
+        match replacement {
+            Some((c, end)) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+#[test]
+fn test_puzzle() {
+    let html = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2>
+<p>For example:</p>
+<pre><code>1abc2
+pqr3stu8vwx
+a1b2c3d4e5f &lt;&amp;&gt;
+</code></pre>
+<p>In this example, the values are <code>12</code> and <code>38</code>. Adding these together produces <code><em>142</em></code>.</p>
+<p><em>What is the sum?</em></p>
+</article>
+<p>Your puzzle answer was <code>54331</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>two<em>1</em>nine
+</code></pre>
+<p>Adding these together produces <em><code>281</code></em>.</p>
+</article>
+<p>Your puzzle answer was <code>54518</code>.</p>
+</main>"#;
+
+    let puzzle = Puzzle::new(html);
+    assert_eq!(puzzle.title().as_deref(), Some("Day 1: Trebuchet?!"));
+    assert_eq!(
+        puzzle.parts(),
+        vec![
+            PuzzlePart {
+                examples: vec!["1abc2\npqr3stu8vwx\na1b2c3d4e5f <&>\n".to_string()],
+                example_answer: Some("142".to_string()),
+            },
+            PuzzlePart {
+                examples: vec!["two1nine\n".to_string()],
+                example_answer: Some("281".to_string()),
+            },
+        ]
+    );
+    assert_eq!(puzzle.accepted_answers(), vec!["54331", "54518"]);
+    assert!(puzzle.text().contains("Day 1: Trebuchet?!"));
+
+    let calendar =
+        r#"<a aria-label="Day 2" href="/2023/day/2" class="calendar-day2"><a href="/2023/day/1">"#;
+    assert_eq!(calendar_days(calendar, 2023), vec![1, 2]);
+}
@@ -0,0 +1,210 @@
+use std::fs::{File, OpenOptions, TryLockError};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use advent::input;
+
+const STATE_FILE: &str = ".request-state";
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+struct RequestState {
+    last_request: Option<SystemTime>,
+    submit_after: Option<SystemTime>,
+}
+
+impl RequestState {
+    fn parse(text: &str) -> Self {
+        let mut state = RequestState::default();
+
+        for line in text.lines() {
+            let Some((key, value)) = line.split_once(' ') else {
+                continue;
+            };
+            let Ok(millis) = value.trim().parse() else {
+                continue;
+            };
+            let time = Some(UNIX_EPOCH + Duration::from_millis(millis));
+
+            match key {
+                "last_request" => state.last_request = time,
+                "submit_after" => state.submit_after = time,
+                _ => (),
+            }
+        }
+
+        state
+    }
+
+    fn serialize(&self) -> String {
+        let millis = |time: SystemTime| {
+            time.duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis())
+                .unwrap_or(0)
+        };
+
+        let mut text = String::new();
+        if let Some(time) = self.last_request {
+            text.push_str(&format!("last_request {}\n", millis(time)));
+        }
+        if let Some(time) = self.submit_after {
+            text.push_str(&format!("submit_after {}\n", millis(time)));
+        }
+        text
+    }
+
+    fn ready_at(&self, delay: Duration, submission: bool) -> Option<SystemTime> {
+        let next_request = self.last_request.map(|t| t + delay);
+
+        if submission {
+            next_request.max(self.submit_after)
+        } else {
+            next_request
+        }
+    }
+}
+
+fn until(time: SystemTime) -> Option<Duration> {
+    time.duration_since(SystemTime::now())
+        .ok()
+        .filter(|d| !d.is_zero())
+}
+
+pub struct RateLimit {
+    delay: Duration,
+    state_path: PathBuf,
+    lock_path: PathBuf,
+}
+
+pub struct RequestGuard<'a> {
+    limit: &'a RateLimit,
+    _lock: File,
+}
+
+impl RateLimit {
+    /// Requests are limited per profile, each profile has its own session and so its own limits
+    /// on the server. The state lives in the default input directory so it is shared no matter
+    /// where the command runs from or which input directory it uses.
+    pub fn new(delay: Duration, profile: Option<&str>) -> Self {
+        let file = match profile {
+            Some(name) => format!("{}.{}", STATE_FILE, name),
+            None => STATE_FILE.to_string(),
+        };
+        let state_path = input::default_root().join(file);
+        let mut lock_path = state_path.clone().into_os_string();
+        lock_path.push(".lock");
+
+        Self {
+            delay,
+            state_path,
+            lock_path: PathBuf::from(lock_path),
+        }
+    }
+
+    fn create_dir(&self) -> std::io::Result<()> {
+        match self.state_path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => std::fs::create_dir_all(dir),
+            _ => Ok(()),
+        }
+    }
+
+    fn state(&self) -> RequestState {
+        std::fs::read_to_string(&self.state_path)
+            .map(|text| RequestState::parse(&text))
+            .unwrap_or_default()
+    }
+
+    fn save(&self, state: &RequestState) -> std::io::Result<()> {
+        std::fs::write(&self.state_path, state.serialize())
+    }
+
+    pub fn remaining(&self, submission: bool) -> Option<Duration> {
+        until(self.state().ready_at(self.delay, submission)?)
+    }
+
+    pub fn begin(&self, submission: bool) -> std::io::Result<RequestGuard<'_>> {
+        self.create_dir()?;
+        let lock = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(false)
+            .open(&self.lock_path)?;
+
+        match lock.try_lock() {
+            Ok(()) => (),
+            Err(TryLockError::WouldBlock) => {
+                eprintln!("waiting for another request to finish...");
+                lock.lock()?;
+            }
+            Err(TryLockError::Error(err)) => return Err(err),
+        }
+
+        let state = self.state();
+        let ready = state.ready_at(self.delay, submission);
+        if let Some(wait) = ready.and_then(until) {
+            let reason = if submission && ready == state.submit_after {
+                "for the answer cooldown"
+            } else {
+                "between requests"
+            };
+            eprintln!(
+                "waiting {} seconds {}...",
+                wait.as_secs_f64().ceil(),
+                reason
+            );
+            std::thread::sleep(wait);
+        }
+
+        let state = RequestState {
+            last_request: Some(SystemTime::now()),
+            ..state
+        };
+        self.save(&state)?;
+
+        Ok(RequestGuard {
+            limit: self,
+            _lock: lock,
+        })
+    }
+}
+
+impl RequestGuard<'_> {
+    pub fn cooldown(&self, wait: Duration) -> std::io::Result<()> {
+        let state = RequestState {
+            submit_after: Some(SystemTime::now() + wait),
+            ..self.limit.state()
+        };
+        self.limit.save(&state)
+    }
+}
+
+#[test]
+fn test_request_state() {
+    let delay = Duration::from_secs(15);
+    let last = UNIX_EPOCH + Duration::from_millis(1_700_000_000_123);
+    let state = RequestState {
+        last_request: Some(last),
+        submit_after: Some(last + Duration::from_secs(60)),
+    };
+
+    assert_eq!(RequestState::parse(&state.serialize()), state);
+    assert_eq!(RequestState::parse("garbage\n"), RequestState::default());
+    assert_eq!(state.ready_at(delay, false), Some(last + delay));
+    assert_eq!(
+        state.ready_at(delay, true),
+        Some(last + Duration::from_secs(60))
+    );
+    assert_eq!(RequestState::default().ready_at(delay, true), None);
+}
+
+#[test]
+fn test_rate_limit_paths() {
+    let default = RateLimit::new(Duration::ZERO, None);
+    let alice = RateLimit::new(Duration::ZERO, Some("alice"));
+
+    assert_eq!(default.state_path.parent(), alice.state_path.parent());
+    assert_eq!(default.state_path.file_name().unwrap(), STATE_FILE);
+    assert_eq!(
+        alice.lock_path.file_name().unwrap(),
+        ".request-state.alice.lock"
+    );
+}
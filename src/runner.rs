@@ -0,0 +1,460 @@
+use std::cell::Cell;
+use std::collections::{BTreeMap, VecDeque};
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread::Scope;
+use std::time::Duration;
+
+use advent::solutions::{ParseFunc, ParsedFunc};
+use advent::{Answer, ParseError, SolutionFunc};
+
+use crate::profiler::Stats;
+use crate::{Metrics, Profiler};
+
+pub struct Task {
+    pub func: TaskFunc,
+    pub input: Arc<str>,
+}
+
+/// A task yields one result per part it runs, a parsed task parses its input once and runs
+/// each of its parts on the parsed value.
+#[derive(Copy, Clone)]
+pub enum TaskFunc {
+    Whole(&'static SolutionFunc),
+    Parsed {
+        parse: &'static ParseFunc,
+        part_one: Option<&'static ParsedFunc>,
+        part_two: Option<&'static ParsedFunc>,
+    },
+}
+
+impl TaskFunc {
+    pub fn parts(&self) -> usize {
+        match self {
+            TaskFunc::Whole(_) => 1,
+            TaskFunc::Parsed {
+                part_one, part_two, ..
+            } => part_one.iter().chain(part_two).count(),
+        }
+    }
+}
+
+/// The result of one part, the result of the first part of a parsed task carries the parse.
+pub struct PartResult {
+    pub answer: Result<Answer, Failure>,
+    pub metrics: Metrics,
+    pub stats: Option<Stats>,
+    pub parse: Option<ParseResult>,
+}
+
+pub struct ParseResult {
+    pub metrics: Metrics,
+    pub stats: Option<Stats>,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct RunOptions {
+    pub timeout: Option<Duration>,
+    pub samples: usize,
+    pub warmup: usize,
+}
+
+#[derive(Debug, Clone)]
+pub enum Failure {
+    Panic(String),
+    Timeout(Duration),
+    Error(ParseError),
+}
+
+impl Failure {
+    pub fn status(&self) -> &'static str {
+        match self {
+            Failure::Panic(_) => "PANIC",
+            Failure::Timeout(_) => "TIMEOUT",
+            Failure::Error(_) => "ERROR",
+        }
+    }
+}
+
+impl std::fmt::Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Panic(message) => write!(f, "panicked: {}", message),
+            Failure::Error(err) => write!(f, "invalid input at {}", err),
+            Failure::Timeout(timeout) => {
+                write!(
+                    f,
+                    "did not finish within {}",
+                    crate::profiler::Time(*timeout)
+                )
+            }
+        }
+    }
+}
+
+// a part that timed out keeps running on its own thread until it finishes, anything measured
+// meanwhile shares the cpu and the global allocation counters with it
+static RUNAWAY: AtomicUsize = AtomicUsize::new(0);
+static MEASURED_WITH_RUNAWAY: AtomicBool = AtomicBool::new(false);
+
+const RUNNING: u8 = 0;
+const FINISHED: u8 = 1;
+const ABANDONED: u8 = 2;
+
+/// Whether any result so far was measured while a part that timed out was still running.
+pub fn measured_with_runaway() -> bool {
+    MEASURED_WITH_RUNAWAY.load(Ordering::Relaxed)
+}
+
+fn mark_concurrent(result: &mut PartResult) {
+    result.metrics.concurrent = true;
+    if let Some(parse) = result.parse.as_mut() {
+        parse.metrics.concurrent = true;
+    }
+}
+
+thread_local! {
+    static ISOLATED: Cell<bool> = const { Cell::new(false) };
+    static PANIC_MESSAGE: Cell<Option<String>> = const { Cell::new(None) };
+}
+
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if ISOLATED.get() {
+            let message = info.payload_as_str().unwrap_or("Box<dyn Any>");
+            let message = if let Some(location) = info.location() {
+                format!("{} at {}:{}", message, location.file(), location.line())
+            } else {
+                message.to_string()
+            };
+            PANIC_MESSAGE.set(Some(message));
+        } else {
+            default_hook(info)
+        }
+    }));
+}
+
+fn measure<R>(
+    profiler: &mut Profiler,
+    func: impl FnOnce() -> R,
+) -> (std::thread::Result<R>, Metrics) {
+    profiler.start();
+    let result = std::panic::catch_unwind(AssertUnwindSafe(func));
+    (result, profiler.stop())
+}
+
+fn run_isolated(
+    profiler: &mut Profiler,
+    func: TaskFunc,
+    input: &str,
+    mut emit: impl FnMut(PartResult),
+) {
+    PANIC_MESSAGE.take();
+    ISOLATED.set(true);
+
+    match func {
+        TaskFunc::Whole(func) => {
+            let (answer, metrics) = measure(profiler, || func(input));
+            emit(part_result(answer, metrics, None));
+        }
+        TaskFunc::Parsed {
+            parse,
+            part_one,
+            part_two,
+        } => {
+            let (parsed, parse_metrics) = measure(profiler, || parse(input));
+            let mut parse = Some(ParseResult {
+                metrics: parse_metrics,
+                stats: None,
+            });
+
+            match parsed {
+                Ok(Ok(parsed)) => {
+                    for part in part_one.into_iter().chain(part_two) {
+                        let (answer, metrics) = measure(profiler, || part(parsed.as_ref()));
+                        emit(part_result(answer, metrics, parse.take()));
+                    }
+                }
+                // every part fails with the parse step
+                failed => {
+                    let failure = match failed {
+                        Ok(Err(err)) => Failure::Error(err),
+                        _ => panic_failure(),
+                    };
+                    for _ in 0..func.parts() {
+                        emit(PartResult {
+                            answer: Err(failure.clone()),
+                            metrics: Metrics::default(),
+                            stats: None,
+                            parse: parse.take(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    ISOLATED.set(false);
+}
+
+fn part_result(
+    answer: std::thread::Result<Result<Answer, ParseError>>,
+    metrics: Metrics,
+    parse: Option<ParseResult>,
+) -> PartResult {
+    let answer = match answer {
+        Ok(Ok(answer)) => Ok(answer),
+        Ok(Err(err)) => Err(Failure::Error(err)),
+        Err(_payload) => Err(panic_failure()),
+    };
+
+    PartResult {
+        answer,
+        metrics,
+        stats: None,
+        parse,
+    }
+}
+
+fn panic_failure() -> Failure {
+    let message = PANIC_MESSAGE
+        .take()
+        .unwrap_or_else(|| "unknown panic".to_string());
+    Failure::Panic(message)
+}
+
+pub fn run_task(profiler: &mut Profiler, task: &Task, options: RunOptions) -> Vec<PartResult> {
+    if options.samples <= 1 && options.warmup == 0 {
+        return run_once(profiler, task, options.timeout);
+    }
+
+    let mut samples = vec![Vec::with_capacity(options.samples); task.func.parts()];
+    let mut parse_samples = Vec::new();
+    let mut answers = Vec::new();
+
+    for run in 0..options.warmup + options.samples.max(1) {
+        let results = run_once(profiler, task, options.timeout);
+        if results.iter().any(|result| result.answer.is_err()) {
+            return results;
+        }
+
+        answers.clear();
+        for (samples, result) in samples.iter_mut().zip(results) {
+            if run >= options.warmup {
+                samples.push(result.metrics);
+                parse_samples.extend(result.parse.map(|p| p.metrics));
+            }
+            answers.push(result.answer);
+        }
+    }
+
+    let durations =
+        |samples: &[Metrics]| -> Vec<_> { samples.iter().map(|m| m.duration).collect() };
+    let mut parse = (!parse_samples.is_empty()).then(|| ParseResult {
+        metrics: Metrics::aggregate(&parse_samples),
+        stats: Some(Stats::new(&durations(&parse_samples))),
+    });
+
+    answers
+        .into_iter()
+        .zip(samples)
+        .map(|(answer, samples)| PartResult {
+            answer,
+            metrics: Metrics::aggregate(&samples),
+            stats: Some(Stats::new(&durations(&samples))),
+            parse: parse.take(),
+        })
+        .collect()
+}
+
+fn run_once(profiler: &mut Profiler, task: &Task, timeout: Option<Duration>) -> Vec<PartResult> {
+    let runaway = RUNAWAY.load(Ordering::SeqCst) > 0;
+    let mut results = run_once_with_timeout(profiler, task, timeout);
+
+    let timed_out = results
+        .iter()
+        .any(|result| matches!(result.answer, Err(Failure::Timeout(_))));
+    if !timed_out && (runaway || RUNAWAY.load(Ordering::SeqCst) > 0) {
+        MEASURED_WITH_RUNAWAY.store(true, Ordering::Relaxed);
+        results.iter_mut().for_each(mark_concurrent);
+    }
+
+    results
+}
+
+/// Every part of the task gets `timeout` from the end of the previous one, the parse step
+/// counts towards the first part.
+fn run_once_with_timeout(
+    profiler: &mut Profiler,
+    task: &Task,
+    timeout: Option<Duration>,
+) -> Vec<PartResult> {
+    let Some(timeout) = timeout else {
+        let mut results = Vec::with_capacity(task.func.parts());
+        run_isolated(profiler, task.func, &task.input, |result| {
+            results.push(result)
+        });
+        return results;
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    let func = task.func;
+    let input = task.input.clone();
+    let state = Arc::new(AtomicU8::new(RUNNING));
+    let thread_state = state.clone();
+
+    let spawned = std::thread::Builder::new()
+        .name("solution".to_string())
+        .spawn(move || {
+            let mut profiler = Profiler::new();
+            run_isolated(&mut profiler, func, &input, |result| {
+                let _ = sender.send(result);
+            });
+            if thread_state.swap(FINISHED, Ordering::SeqCst) == ABANDONED {
+                RUNAWAY.fetch_sub(1, Ordering::SeqCst);
+            }
+        });
+
+    let mut results = Vec::with_capacity(func.parts());
+    let failure = match spawned {
+        Ok(_) => loop {
+            if results.len() == func.parts() {
+                return results;
+            }
+            match receiver.recv_timeout(timeout) {
+                Ok(result) => results.push(result),
+                Err(RecvTimeoutError::Timeout) => {
+                    RUNAWAY.fetch_add(1, Ordering::SeqCst);
+                    if state.swap(ABANDONED, Ordering::SeqCst) == FINISHED {
+                        RUNAWAY.fetch_sub(1, Ordering::SeqCst);
+                    }
+                    break Failure::Timeout(timeout);
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    break Failure::Panic("solution thread exited without a result".to_string());
+                }
+            }
+        },
+        Err(err) => Failure::Panic(format!("unable to spawn solution thread: {}", err)),
+    };
+
+    let metrics = Metrics {
+        duration: timeout,
+        ..Default::default()
+    };
+
+    while results.len() < func.parts() {
+        results.push(PartResult {
+            answer: Err(failure.clone()),
+            metrics,
+            stats: None,
+            parse: None,
+        });
+    }
+
+    results
+}
+
+pub enum Executor<'a> {
+    Inline {
+        profiler: Profiler,
+        tasks: std::slice::Iter<'a, Task>,
+        options: RunOptions,
+        ready: VecDeque<PartResult>,
+    },
+    Pool {
+        receiver: Receiver<(usize, Vec<PartResult>)>,
+        pending: BTreeMap<usize, Vec<PartResult>>,
+        next: usize,
+        ready: VecDeque<PartResult>,
+    },
+}
+
+impl<'env> Executor<'env> {
+    pub fn new<'scope>(
+        scope: &'scope Scope<'scope, 'env>,
+        tasks: &'env [Task],
+        jobs: usize,
+        options: RunOptions,
+    ) -> Self {
+        if jobs <= 1 {
+            return Executor::Inline {
+                profiler: Profiler::new(),
+                tasks: tasks.iter(),
+                options,
+                ready: VecDeque::new(),
+            };
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        let next_task = Arc::new(AtomicUsize::new(0));
+
+        for _ in 0..jobs.min(tasks.len()) {
+            let sender = sender.clone();
+            let next_task = next_task.clone();
+            scope.spawn(move || {
+                let mut profiler = Profiler::new();
+                loop {
+                    let idx = next_task.fetch_add(1, Ordering::Relaxed);
+                    let Some(task) = tasks.get(idx) else {
+                        break;
+                    };
+
+                    let mut results = run_task(&mut profiler, task, options);
+                    results.iter_mut().for_each(mark_concurrent);
+
+                    if sender.send((idx, results)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        Executor::Pool {
+            receiver,
+            pending: BTreeMap::new(),
+            next: 0,
+            ready: VecDeque::new(),
+        }
+    }
+
+    /// The result of the next part, in the order of the tasks and of the parts within them.
+    pub fn next_result(&mut self) -> Option<PartResult> {
+        match self {
+            Executor::Inline {
+                profiler,
+                tasks,
+                options,
+                ready,
+            } => {
+                while ready.is_empty() {
+                    let task = tasks.next()?;
+                    ready.extend(run_task(profiler, task, *options));
+                }
+
+                ready.pop_front()
+            }
+            Executor::Pool {
+                receiver,
+                pending,
+                next,
+                ready,
+            } => {
+                while ready.is_empty() {
+                    while !pending.contains_key(next) {
+                        let (idx, results) = receiver.recv().ok()?;
+                        pending.insert(idx, results);
+                    }
+
+                    ready.extend(pending.remove(next).unwrap_or_default());
+                    *next += 1;
+                }
+
+                ready.pop_front()
+            }
+        }
+    }
+}
@@ -0,0 +1,431 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, VecDeque};
+use std::hash::Hash;
+
+use crate::{HashMap, HashSet};
+
+/// Path costs, `Default` is the cost of not moving.
+pub trait Cost: Copy + Ord + Default + std::ops::Add<Output = Self> {}
+
+impl<C: Copy + Ord + Default + std::ops::Add<Output = C>> Cost for C {}
+
+/// Breadth first search from `start`, returns the first node reached for which `is_goal` holds
+/// along with the number of steps to it.
+pub fn bfs<N, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(N, usize)>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    Search::<N>::new().bfs(start, successors, is_goal)
+}
+
+/// The number of steps to every node reachable from `start`.
+pub fn bfs_reachable<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut steps = HashMap::new();
+    let mut queue = VecDeque::new();
+    steps.insert(start.clone(), 0);
+    queue.push_back((start, 0));
+
+    while let Some((node, distance)) = queue.pop_front() {
+        for next in successors(&node) {
+            if !steps.contains_key(&next) {
+                steps.insert(next.clone(), distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+
+    steps
+}
+
+/// Lowest cost path from any of `starts` to a node for which `is_goal` holds, `successors`
+/// yields each neighbor with the cost of moving to it.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(N, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    Search::new().dijkstra(starts, successors, is_goal)
+}
+
+/// The lowest cost to every node reachable from any of `starts`.
+pub fn dijkstra_reachable<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+) -> HashMap<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut costs = HashMap::new();
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        costs.insert(start.clone(), C::default());
+        heap.push(Entry::new(C::default(), C::default(), start));
+    }
+
+    while let Some(Entry { cost, node, .. }) = heap.pop() {
+        if costs.get(&node).is_some_and(|best| cost > *best) {
+            continue;
+        }
+
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            if costs.get(&next).is_none_or(|best| next_cost < *best) {
+                costs.insert(next.clone(), next_cost);
+                heap.push(Entry::new(next_cost, next_cost, next));
+            }
+        }
+    }
+
+    costs
+}
+
+/// Like `dijkstra`, guided by a `heuristic` of the remaining cost. Nodes are not revisited once
+/// expanded, so the heuristic must be consistent: zero at every goal, and for each move from `a`
+/// to `b` costing `c`, `heuristic(a) <= c + heuristic(b)`. One that only never overestimates can
+/// return a path that is not the cheapest.
+pub fn astar<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(N, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    Search::new().astar(start, successors, heuristic, is_goal)
+}
+
+/// Like `dijkstra`, but keeps every predecessor on a lowest cost path so all of the shortest
+/// paths to every goal with the lowest cost can be rebuilt.
+pub fn dijkstra_all<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<AllPaths<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut best: HashMap<N, (C, Vec<N>)> = HashMap::new();
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        best.insert(start.clone(), (C::default(), Vec::new()));
+        heap.push(Entry::new(C::default(), C::default(), start));
+    }
+
+    let mut goals = Vec::new();
+    let mut goal_cost = None;
+
+    while let Some(Entry { cost, node, .. }) = heap.pop() {
+        if goal_cost.is_some_and(|goal| cost > goal) {
+            break;
+        }
+        if best.get(&node).is_some_and(|(best, _)| cost > *best) {
+            continue;
+        }
+
+        if is_goal(&node) {
+            goal_cost = Some(cost);
+            goals.push(node);
+            continue;
+        }
+
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            match best.get_mut(&next) {
+                Some((best, _)) if next_cost > *best => (),
+                Some((best, predecessors)) if next_cost == *best => {
+                    predecessors.push(node.clone());
+                }
+                _ => {
+                    best.insert(next.clone(), (next_cost, vec![node.clone()]));
+                    heap.push(Entry::new(next_cost, next_cost, next));
+                }
+            }
+        }
+    }
+
+    let cost = goal_cost?;
+    Some(AllPaths {
+        cost,
+        goals,
+        predecessors: best,
+    })
+}
+
+pub struct AllPaths<N, C> {
+    pub cost: C,
+    pub goals: Vec<N>,
+    predecessors: HashMap<N, (C, Vec<N>)>,
+}
+
+impl<N: Clone + Eq + Hash, C> AllPaths<N, C> {
+    pub fn predecessors(&self, node: &N) -> &[N] {
+        self.predecessors
+            .get(node)
+            .map_or(&[], |(_, predecessors)| predecessors.as_slice())
+    }
+
+    /// Every node that is part of at least one of the shortest paths.
+    pub fn nodes(&self) -> HashSet<N> {
+        let mut nodes = HashSet::new();
+        let mut stack = self.goals.clone();
+
+        while let Some(node) = stack.pop() {
+            if nodes.insert(node.clone()) {
+                stack.extend(self.predecessors(&node).iter().cloned());
+            }
+        }
+
+        nodes
+    }
+
+    /// One of the shortest paths, from its start to its goal.
+    pub fn path(&self) -> Vec<N> {
+        let mut path: Vec<N> = self.goals.first().cloned().into_iter().collect();
+        while let Some(previous) = path.last().and_then(|n| self.predecessors(n).first()) {
+            path.push(previous.clone());
+        }
+
+        path.reverse();
+        path
+    }
+}
+
+/// Reusable search state, for running many searches without reallocating between them.
+pub struct Search<N, C = usize> {
+    heap: BinaryHeap<Entry<N, C>>,
+    queue: VecDeque<(N, usize)>,
+    visited: HashSet<N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Cost> Search<N, C> {
+    pub fn new() -> Self {
+        Self {
+            heap: BinaryHeap::new(),
+            queue: VecDeque::new(),
+            visited: HashSet::new(),
+        }
+    }
+
+    pub fn bfs<I>(
+        &mut self,
+        start: N,
+        mut successors: impl FnMut(&N) -> I,
+        mut is_goal: impl FnMut(&N) -> bool,
+    ) -> Option<(N, usize)>
+    where
+        I: IntoIterator<Item = N>,
+    {
+        self.queue.clear();
+        self.visited.clear();
+        self.visited.insert(start.clone());
+        self.queue.push_back((start, 0));
+
+        while let Some((node, steps)) = self.queue.pop_front() {
+            if is_goal(&node) {
+                return Some((node, steps));
+            }
+
+            for next in successors(&node) {
+                if self.visited.insert(next.clone()) {
+                    self.queue.push_back((next, steps + 1));
+                }
+            }
+        }
+
+        None
+    }
+
+    pub fn dijkstra<I>(
+        &mut self,
+        starts: impl IntoIterator<Item = N>,
+        successors: impl FnMut(&N) -> I,
+        is_goal: impl FnMut(&N) -> bool,
+    ) -> Option<(N, C)>
+    where
+        I: IntoIterator<Item = (N, C)>,
+    {
+        self.run(starts, successors, |_| C::default(), is_goal)
+    }
+
+    /// See `astar`, the heuristic must be consistent.
+    pub fn astar<I>(
+        &mut self,
+        start: N,
+        successors: impl FnMut(&N) -> I,
+        heuristic: impl FnMut(&N) -> C,
+        is_goal: impl FnMut(&N) -> bool,
+    ) -> Option<(N, C)>
+    where
+        I: IntoIterator<Item = (N, C)>,
+    {
+        self.run(Some(start), successors, heuristic, is_goal)
+    }
+
+    fn run<I>(
+        &mut self,
+        starts: impl IntoIterator<Item = N>,
+        mut successors: impl FnMut(&N) -> I,
+        mut heuristic: impl FnMut(&N) -> C,
+        mut is_goal: impl FnMut(&N) -> bool,
+    ) -> Option<(N, C)>
+    where
+        I: IntoIterator<Item = (N, C)>,
+    {
+        self.heap.clear();
+        self.visited.clear();
+        for start in starts {
+            let estimate = heuristic(&start);
+            self.heap.push(Entry::new(estimate, C::default(), start));
+        }
+
+        while let Some(Entry { cost, node, .. }) = self.heap.pop() {
+            if is_goal(&node) {
+                return Some((node, cost));
+            }
+
+            if !self.visited.insert(node.clone()) {
+                continue;
+            }
+
+            for (next, step) in successors(&node) {
+                if !self.visited.contains(&next) {
+                    let cost = cost + step;
+                    let estimate = cost + heuristic(&next);
+                    self.heap.push(Entry::new(estimate, cost, next));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl<N: Clone + Eq + Hash, C: Cost> Default for Search<N, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// min-heap entry ordered by estimate, preferring the entry that got further on ties
+struct Entry<N, C> {
+    estimate: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C> Entry<N, C> {
+    fn new(estimate: C, cost: C, node: N) -> Self {
+        Self {
+            estimate,
+            cost,
+            node,
+        }
+    }
+}
+
+impl<N, C: Ord> Ord for Entry<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then_with(|| self.cost.cmp(&other.cost))
+    }
+}
+
+impl<N, C: Ord> PartialOrd for Entry<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> PartialEq for Entry<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N, C: Ord> Eq for Entry<N, C> {}
+
+#[test]
+fn test_search() {
+    use crate::grid::{Grid, Point};
+
+    let grid = Grid::parse("...#\n.#..\n...#\n#...\n", |c| Some(c == '#')).unwrap();
+    let start = Point::new(0, 0);
+    let end = Point::new(3, 3);
+    let open = |p: &Point| grid.neighbors(*p).filter(|n| !grid[*n]).collect::<Vec<_>>();
+
+    assert_eq!(bfs(start, open, |p| *p == end), Some((end, 6)));
+    assert_eq!(bfs_reachable(start, open).len(), 12);
+    assert_eq!(bfs(start, open, |p| *p == Point::new(3, 0)), None);
+
+    let weighted = |p: &Point| open(p).into_iter().map(|n| (n, 1 + n.y));
+    assert_eq!(dijkstra([start], weighted, |p| *p == end), Some((end, 15)));
+    assert_eq!(
+        astar(start, weighted, |p| p.manhattan(end), |p| *p == end),
+        Some((end, 15))
+    );
+
+    let unit = |p: &Point| open(p).into_iter().map(|n| (n, 1));
+    let paths = dijkstra_all([start], unit, |p| *p == end).unwrap();
+    assert_eq!(paths.cost, 6);
+    assert_eq!(paths.nodes().len(), 11);
+    let path = paths.path();
+    assert_eq!((path.len(), path[0], path[6]), (7, start, end));
+}
+
+#[test]
+fn test_graph() {
+    // two paths of cost 3 lead from a to c, one through b and d
+    let edges: HashMap<char, Vec<(char, u32)>> = [
+        ('a', vec![('b', 1), ('c', 3)]),
+        ('b', vec![('a', 1), ('d', 1)]),
+        ('c', vec![('a', 3), ('d', 1), ('e', 1)]),
+        ('d', vec![('b', 1), ('c', 1)]),
+        ('e', vec![('c', 1)]),
+    ]
+    .into_iter()
+    .collect();
+    let successors = |n: &char| edges[n].iter().copied();
+    let neighbors = |n: &char| edges[n].iter().map(|&(next, _)| next);
+
+    assert_eq!(bfs('a', neighbors, |&n| n == 'e'), Some(('e', 2)));
+    assert_eq!(bfs_reachable('a', neighbors)[&'d'], 2);
+    assert_eq!(dijkstra(['a'], successors, |&n| n == 'e'), Some(('e', 4)));
+    assert_eq!(astar('e', successors, |_| 0, |&n| n == 'b'), Some(('b', 3)));
+
+    let costs = dijkstra_reachable(['a'], successors);
+    assert_eq!((costs.len(), costs[&'c'], costs[&'e']), (5, 3, 4));
+
+    let paths = dijkstra_all(['a'], successors, |&n| n == 'c').unwrap();
+    assert_eq!((paths.cost, paths.nodes().len()), (3, 4));
+    assert_eq!(paths.predecessors(&'c'), ['a', 'd']);
+
+    let mut search = Search::new();
+    assert_eq!(
+        search.dijkstra(['b'], successors, |&n| n == 'e'),
+        Some(('e', 3))
+    );
+    assert_eq!(search.dijkstra(['e'], successors, |&n| n == 'f'), None);
+}
@@ -1,33 +1,176 @@
-mod year_2019;
-mod year_2020;
-mod year_2021;
-mod year_2022;
-mod year_2023;
-mod year_2024;
-mod year_2025;
-
-pub use year_2019::days as days_2019;
-pub use year_2020::days as days_2020;
-pub use year_2021::days as days_2021;
-pub use year_2022::days as days_2022;
-pub use year_2023::days as days_2023;
-pub use year_2024::days as days_2024;
-pub use year_2025::days as days_2025;
-
-pub type SolutionFunc = Box<dyn Fn(&str) -> Box<dyn std::fmt::Display>>;
+use std::any::Any;
+use std::sync::Arc;
+
+use crate::ParseError;
+
+// `mod year_XXXX;` for each event and `events()`, generated by build.rs
+include!(concat!(env!("OUT_DIR"), "/events.rs"));
+
+pub type SolutionFunc = Box<dyn Fn(&str) -> Result<Answer, ParseError> + Send + Sync>;
+pub type ParseFunc = Box<dyn Fn(&str) -> Result<Box<dyn Any>, ParseError> + Send + Sync>;
+pub type ParsedFunc = Box<dyn Fn(&dyn Any) -> Result<Answer, ParseError> + Send + Sync>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    /// Multi-line output, such as letters drawn on a screen
+    Art(String),
+    /// Parts without a puzzle of their own, like the second part of the last day
+    NotApplicable,
+    Unsolved,
+}
+
+impl Answer {
+    /// The value to submit or compare, `None` for parts without an answer.
+    pub fn value(&self) -> Option<String> {
+        match self {
+            Answer::Integer(n) => Some(n.to_string()),
+            Answer::Text(text) | Answer::Art(text) => Some(text.clone()),
+            Answer::NotApplicable | Answer::Unsolved => None,
+        }
+    }
+
+    /// The value to submit, `None` for art which has to be read and entered by hand.
+    pub fn submission(&self) -> Option<String> {
+        match self {
+            Answer::Art(_) => None,
+            answer => answer.value(),
+        }
+    }
+
+    pub fn status(&self) -> &'static str {
+        match self {
+            Answer::Integer(_) | Answer::Text(_) | Answer::Art(_) => "ok",
+            Answer::NotApplicable => "not_applicable",
+            Answer::Unsolved => "unsolved",
+        }
+    }
+}
+
+impl std::fmt::Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(n) => n.fmt(f),
+            Answer::Text(text) | Answer::Art(text) => f.pad(text),
+            Answer::NotApplicable => f.pad("N/A"),
+            Answer::Unsolved => f.pad("UNSOLVED"),
+        }
+    }
+}
+
+pub trait IntoAnswer {
+    fn into_answer(self) -> Result<Answer, ParseError>;
+}
+
+impl IntoAnswer for Answer {
+    fn into_answer(self) -> Result<Answer, ParseError> {
+        Ok(self)
+    }
+}
+
+impl<T: IntoAnswer, E: Into<ParseError>> IntoAnswer for Result<T, E> {
+    fn into_answer(self) -> Result<Answer, ParseError> {
+        self.map_err(Into::into)?.into_answer()
+    }
+}
+
+macro_rules! impl_into_answer_int {
+    ($($ty:ty),*) => {
+        $(
+            impl IntoAnswer for $ty {
+                fn into_answer(self) -> Result<Answer, ParseError> {
+                    Ok(Answer::Integer(self as i128))
+                }
+            }
+        )*
+    };
+}
+
+impl_into_answer_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128);
+
+impl IntoAnswer for u128 {
+    fn into_answer(self) -> Result<Answer, ParseError> {
+        match i128::try_from(self) {
+            Ok(n) => Ok(Answer::Integer(n)),
+            Err(_) => Ok(Answer::Text(self.to_string())),
+        }
+    }
+}
+
+impl IntoAnswer for String {
+    fn into_answer(self) -> Result<Answer, ParseError> {
+        if self.trim().contains('\n') {
+            Ok(Answer::Art(self))
+        } else {
+            Ok(Answer::Text(self))
+        }
+    }
+}
+
+impl IntoAnswer for &str {
+    fn into_answer(self) -> Result<Answer, ParseError> {
+        self.to_string().into_answer()
+    }
+}
 
 pub struct Solution {
     pub day: u32,
     pub part_one: SolutionFunc,
     pub part_two: SolutionFunc,
+    pub parsed: Option<ParsedSolution>,
+    pub examples: Vec<Example>,
+}
+
+/// Parse step and parts of a solution declared with a parse step, the parts
+/// borrow the value returned by `parse`.
+pub struct ParsedSolution {
+    pub parse: ParseFunc,
+    pub part_one: ParsedFunc,
+    pub part_two: ParsedFunc,
+}
+
+/// Declares the parts of a day for the registration generated by build.rs, which calls the
+/// `register` function it defines. Days with a separate parse step name it before the parts,
+/// and days with examples name the function returning them after, which also adds a test of
+/// them under `cargo test`:
+///
+/// ```text
+/// crate::solution!(part_one, part_two);
+/// crate::solution!(parse => part_one, part_two; examples);
+/// ```
+macro_rules! solution {
+    ($parse:path => $part_one:path, $part_two:path $(; $examples:path)?) => {
+        pub fn register(day: u32) -> $crate::Solution {
+            $crate::Solution::with_parse(day, $parse, $part_one, $part_two)
+                $(.with_examples($examples()))?
+        }
+
+        $($crate::solutions::solution!(@test $examples);)?
+    };
+    ($part_one:path, $part_two:path $(; $examples:path)?) => {
+        pub fn register(day: u32) -> $crate::Solution {
+            $crate::Solution::new(day, $part_one, $part_two)
+                $(.with_examples($examples()))?
+        }
+
+        $($crate::solutions::solution!(@test $examples);)?
+    };
+    (@test $examples:path) => {
+        #[test]
+        fn test_examples() {
+            $crate::solutions::check_examples(&register(0));
+        }
+    };
 }
+pub(crate) use solution;
 
 impl Solution {
     pub fn new<
-        F1: Fn(&str) -> D1 + 'static,
-        D1: std::fmt::Display + 'static,
-        F2: Fn(&str) -> D2 + 'static,
-        D2: std::fmt::Display + 'static,
+        F1: Fn(&str) -> D1 + Send + Sync + 'static,
+        D1: IntoAnswer + 'static,
+        F2: Fn(&str) -> D2 + Send + Sync + 'static,
+        D2: IntoAnswer + 'static,
     >(
         day: u32,
         part_one: F1,
@@ -35,12 +178,210 @@ impl Solution {
     ) -> Self {
         Self {
             day,
-            part_one: Box::new(move |input| Box::new(part_one(input))),
-            part_two: Box::new(move |input| Box::new(part_two(input))),
+            part_one: Box::new(move |input| part_one(input).into_answer()),
+            part_two: Box::new(move |input| part_two(input).into_answer()),
+            parsed: None,
+            examples: Vec::new(),
+        }
+    }
+
+    /// The whole input parts of the solution parse their input on every call, runs of both
+    /// parts go through `parsed` to share a single parse.
+    pub fn with_parse<
+        P: Fn(&str) -> Result<T, ParseError> + Send + Sync + 'static,
+        T: 'static,
+        F1: Fn(&T) -> D1 + Send + Sync + 'static,
+        D1: IntoAnswer + 'static,
+        F2: Fn(&T) -> D2 + Send + Sync + 'static,
+        D2: IntoAnswer + 'static,
+    >(
+        day: u32,
+        parse: P,
+        part_one: F1,
+        part_two: F2,
+    ) -> Self {
+        let parse = Arc::new(parse);
+        let part_one = Arc::new(part_one);
+        let part_two = Arc::new(part_two);
+
+        let parsed = ParsedSolution {
+            parse: Box::new({
+                let parse = parse.clone();
+                move |input| Ok(Box::new(parse(input)?) as Box<dyn Any>)
+            }),
+            part_one: Box::new({
+                let part_one = part_one.clone();
+                move |parsed| part_one(downcast(parsed)).into_answer()
+            }),
+            part_two: Box::new({
+                let part_two = part_two.clone();
+                move |parsed| part_two(downcast(parsed)).into_answer()
+            }),
+        };
+
+        Self {
+            day,
+            part_one: Box::new({
+                let parse = parse.clone();
+                move |input| part_one(&parse(input)?).into_answer()
+            }),
+            part_two: Box::new(move |input| part_two(&parse(input)?).into_answer()),
+            parsed: Some(parsed),
+            examples: Vec::new(),
+        }
+    }
+}
+
+impl Solution {
+    pub fn with_examples(mut self, examples: Vec<Example>) -> Self {
+        self.examples = examples;
+        self
+    }
+
+    /// The function to run and the expected answer for `part` of `example`, `None` when
+    /// the example has no answer for that part.
+    pub fn example_part<'a>(
+        &'a self,
+        example: &'a Example,
+        part: u32,
+    ) -> Option<(&'a SolutionFunc, &'a Answer)> {
+        let expected = match part {
+            1 => example.part_one.as_ref()?,
+            _ => example.part_two.as_ref()?,
+        };
+        let func = match (&expected.solve, part) {
+            (Some(solve), _) => solve,
+            (None, 1) => &self.part_one,
+            (None, _) => &self.part_two,
+        };
+
+        Some((func, &expected.expected))
+    }
+
+    /// The parse step and parts to run both parts of `example` on a single parse, `None` when
+    /// the day has no parse step or the example replaces one of its parts.
+    pub fn example_parsed(&self, example: &Example) -> Option<&ParsedSolution> {
+        let replaced = [&example.part_one, &example.part_two]
+            .into_iter()
+            .flatten()
+            .any(|part| part.solve.is_some());
+        self.parsed.as_ref().filter(|_| !replaced)
+    }
+}
+
+pub struct Example {
+    pub input: &'static str,
+    pub part_one: Option<ExamplePart>,
+    pub part_two: Option<ExamplePart>,
+}
+
+pub struct ExamplePart {
+    pub expected: Answer,
+    /// Replaces the part for this example, for puzzles where the example uses different
+    /// parameters than the real input, such as a smaller grid.
+    pub solve: Option<SolutionFunc>,
+}
+
+impl Example {
+    pub fn new(input: &'static str) -> Self {
+        Self {
+            input,
+            part_one: None,
+            part_two: None,
+        }
+    }
+
+    pub fn part_one<A: IntoAnswer>(mut self, expected: A) -> Self {
+        self.part_one = Some(ExamplePart::new(expected, None));
+        self
+    }
+
+    pub fn part_two<A: IntoAnswer>(mut self, expected: A) -> Self {
+        self.part_two = Some(ExamplePart::new(expected, None));
+        self
+    }
+
+    pub fn part_one_with<F, D, A>(mut self, solve: F, expected: A) -> Self
+    where
+        F: Fn(&str) -> D + Send + Sync + 'static,
+        D: IntoAnswer + 'static,
+        A: IntoAnswer,
+    {
+        let solve: SolutionFunc = Box::new(move |input| solve(input).into_answer());
+        self.part_one = Some(ExamplePart::new(expected, Some(solve)));
+        self
+    }
+
+    pub fn part_two_with<F, D, A>(mut self, solve: F, expected: A) -> Self
+    where
+        F: Fn(&str) -> D + Send + Sync + 'static,
+        D: IntoAnswer + 'static,
+        A: IntoAnswer,
+    {
+        let solve: SolutionFunc = Box::new(move |input| solve(input).into_answer());
+        self.part_two = Some(ExamplePart::new(expected, Some(solve)));
+        self
+    }
+}
+
+impl ExamplePart {
+    fn new<A: IntoAnswer>(expected: A, solve: Option<SolutionFunc>) -> Self {
+        Self {
+            expected: expected
+                .into_answer()
+                .expect("expected example answer should not be an error"),
+            solve,
         }
     }
 }
 
+/// Runs every example of `solution` and panics with the mismatches, used by the tests
+/// `solution!` adds for days that have examples.
+pub fn check_examples(solution: &Solution) {
+    let mut failures = Vec::new();
+    for (idx, example) in solution.examples.iter().enumerate() {
+        let parsed = solution
+            .example_parsed(example)
+            .map(|parsed| (parsed, (parsed.parse)(example.input)));
+
+        for part in [1, 2] {
+            let Some((func, expected)) = solution.example_part(example, part) else {
+                continue;
+            };
+            let answer = match &parsed {
+                Some((parsed, Ok(input))) if part == 1 => (parsed.part_one)(input.as_ref()),
+                Some((parsed, Ok(input))) => (parsed.part_two)(input.as_ref()),
+                Some((_, Err(err))) => Err(err.clone()),
+                None => func(example.input),
+            };
+            match answer {
+                Ok(answer) if answer == *expected => (),
+                Ok(answer) => failures.push(format!(
+                    "example {} part {}: expected {} but found {}",
+                    idx + 1,
+                    part,
+                    expected,
+                    answer
+                )),
+                Err(err) => failures.push(format!(
+                    "example {} part {}: invalid input at {}",
+                    idx + 1,
+                    part,
+                    err
+                )),
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+fn downcast<T: 'static>(parsed: &dyn Any) -> &T {
+    parsed
+        .downcast_ref()
+        .expect("parsed input produced by the matching parse function")
+}
+
 pub struct SolutionCollection {
     solutions: Vec<Solution>,
 }
@@ -54,16 +395,128 @@ impl SolutionCollection {
 
     pub fn add<F1, F2, D1, D2>(mut self, day: u32, part_one: F1, part_two: F2) -> Self
     where
-        F1: Fn(&str) -> D1 + 'static,
-        D1: std::fmt::Display + 'static,
-        F2: Fn(&str) -> D2 + 'static,
-        D2: std::fmt::Display + 'static,
+        F1: Fn(&str) -> D1 + Send + Sync + 'static,
+        D1: IntoAnswer + 'static,
+        F2: Fn(&str) -> D2 + Send + Sync + 'static,
+        D2: IntoAnswer + 'static,
     {
         self.solutions.push(Solution::new(day, part_one, part_two));
         self
     }
 
+    pub fn add_solution(mut self, solution: Solution) -> Self {
+        self.solutions.push(solution);
+        self
+    }
+
     pub fn solutions(&self) -> impl Iterator<Item = &Solution> {
         self.solutions.iter()
     }
+
+    pub fn missing_days(&self, event: u32) -> Vec<u32> {
+        (1..=event_days(event))
+            .filter(|day| !self.solutions.iter().any(|s| s.day == *day))
+            .collect()
+    }
+}
+
+/// Number of puzzles in an event, events from 2025 onward have 12 instead of 25.
+pub fn event_days(event: u32) -> u32 {
+    if event >= 2025 { 12 } else { 25 }
+}
+
+#[test]
+fn test_parsed_solution() {
+    let solution = Solution::with_parse(
+        1,
+        |input: &str| match input.lines().count() {
+            0 => Err(ParseError::end(input, "expected a line")),
+            lines => Ok(lines),
+        },
+        |lines: &usize| lines + 1,
+        |lines: &usize| lines * 2,
+    );
+
+    assert_eq!(Ok(Answer::Integer(4)), (solution.part_one)("a\nb\nc"));
+    assert_eq!(Ok(Answer::Integer(6)), (solution.part_two)("a\nb\nc"));
+    assert!((solution.part_one)("").is_err());
+
+    let parsed = solution.parsed.as_ref().unwrap();
+    let input = (parsed.parse)("a\nb").unwrap();
+    assert_eq!(Ok(Answer::Integer(3)), (parsed.part_one)(input.as_ref()));
+    assert_eq!(Ok(Answer::Integer(4)), (parsed.part_two)(input.as_ref()));
+    assert!((parsed.parse)("").is_err());
+
+    let example = Example::new("a").part_one(2).part_two(2);
+    assert!(solution.example_parsed(&example).is_some());
+    let example = Example::new("a").part_one(2).part_two_with(|_: &str| 2, 2);
+    assert!(solution.example_parsed(&example).is_none());
+
+    static PARSES: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+    let solution = Solution::with_parse(
+        1,
+        |input: &str| {
+            PARSES.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            Ok(input.len())
+        },
+        |len: &usize| *len,
+        |len: &usize| len * 2,
+    )
+    .with_examples(vec![Example::new("abc").part_one(3).part_two(6)]);
+    check_examples(&solution);
+    assert_eq!(1, PARSES.load(std::sync::atomic::Ordering::Relaxed));
+}
+
+#[test]
+fn test_answer() {
+    assert_eq!(Ok(Answer::Integer(-5)), (-5i32).into_answer());
+    assert_eq!(
+        Ok(Answer::Integer(u64::MAX as i128)),
+        u64::MAX.into_answer()
+    );
+    assert_eq!(Ok(Answer::Text("abc".to_string())), "abc".into_answer());
+    assert_eq!(
+        Ok(Answer::Art("#.\n.#\n".to_string())),
+        "#.\n.#\n".to_string().into_answer()
+    );
+
+    let err = ParseError::end("1\n2", "missing line");
+    assert_eq!(Err(err.clone()), Err::<u32, _>(err.clone()).into_answer());
+    assert_eq!(
+        Ok(Answer::Integer(3)),
+        Ok::<_, ParseError>(3u8).into_answer()
+    );
+
+    assert_eq!(Some("12".to_string()), Answer::Integer(12).value());
+    assert_eq!(None, Answer::Unsolved.value());
+    assert_eq!(None, Answer::NotApplicable.value());
+    assert_eq!(None, Answer::Art("#.\n.#\n".to_string()).submission());
+    assert_eq!(
+        Some("abc".to_string()),
+        Answer::Text("abc".to_string()).submission()
+    );
+    assert_eq!("unsolved", Answer::Unsolved.status());
+}
+
+#[test]
+fn test_events() {
+    let events = events();
+    assert!(events.windows(2).all(|w| w[0].0 < w[1].0));
+
+    for (event, days) in &events {
+        let days: Vec<_> = days.solutions().map(|s| s.day).collect();
+        assert!(days.windows(2).all(|w| w[0] < w[1]));
+        assert!(
+            days.iter()
+                .all(|day| (1..=event_days(*event)).contains(day))
+        );
+    }
+
+    let days = SolutionCollection::new()
+        .add(1, |_: &str| 0, |_: &str| 0)
+        .add(3, |_: &str| 0, |_: &str| 0);
+    assert_eq!(
+        days.missing_days(2025),
+        vec![2, 4, 5, 6, 7, 8, 9, 10, 11, 12]
+    );
 }
@@ -1,3 +1,7 @@
+use crate::Example;
+
+crate::solution!(part_one, part_two; examples);
+
 pub fn part_one(input: &str) -> u64 {
     let lines = input.trim().lines();
 
@@ -10,10 +14,8 @@ pub fn part_two(input: &str) -> u64 {
     0
 }
 
-#[test]
-fn test() {
+pub fn examples() -> Vec<Example> {
     let input = r#""#;
 
-    assert_eq!(0, part_one(input));
-    assert_eq!(0, part_two(input));
+    vec![Example::new(input).part_one(0).part_two(0)]
 }
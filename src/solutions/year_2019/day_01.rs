@@ -1,5 +1,7 @@
 use std::str::FromStr;
 
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> i32 {
     input
         .split('\n')
@@ -1,5 +1,7 @@
 use super::intcode::Machine;
 
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> i32 {
     solve_part_one::<12, 2>(input)
 }
@@ -1,48 +1,33 @@
-use std::str::FromStr;
-
-pub fn part_one(input: &str) -> i32 {
-    let mut lines = input.trim().split('\n');
-
-    let first_line = lines
-        .next()
-        .unwrap()
-        .split(',')
-        .map(Movement::from_str)
-        .filter_map(Result::ok);
-    let second_line = lines
-        .next()
-        .unwrap()
-        .split(',')
-        .map(Movement::from_str)
-        .filter_map(Result::ok);
-
-    let first_segments = LineIter::new(first_line);
-    let second_segments: Vec<_> = LineIter::new(second_line).collect();
+use crate::ParseError;
+use crate::geometry::Point2;
+use crate::parse::{self, Parser};
+
+crate::solution!(part_one, part_two);
+
+type Point = Point2<i32>;
+
+pub fn part_one(input: &str) -> Result<i32, ParseError> {
+    let (first_line, second_line) = parse(input)?;
+
+    let first_segments = LineIter::new(first_line.into_iter());
+    let second_segments: Vec<_> = LineIter::new(second_line.into_iter()).collect();
 
     let intersections =
         first_segments.filter_map(|(_, f)| second_segments.iter().find_map(|(_, s)| f.crosses(s)));
 
-    intersections.map(|p| p.distance()).min().unwrap_or(0)
+    let closest = intersections
+        .map(|p| p.manhattan(Point::default()))
+        .min()
+        .unwrap_or(0);
+
+    Ok(closest)
 }
 
-pub fn part_two(input: &str) -> i32 {
-    let mut lines = input.trim().split('\n');
-
-    let first_line = lines
-        .next()
-        .unwrap()
-        .split(',')
-        .map(Movement::from_str)
-        .filter_map(Result::ok);
-    let second_line = lines
-        .next()
-        .unwrap()
-        .split(',')
-        .map(Movement::from_str)
-        .filter_map(Result::ok);
-
-    let first_segments = LineIter::new(first_line);
-    let second_segments: Vec<_> = LineIter::new(second_line)
+pub fn part_two(input: &str) -> Result<i32, ParseError> {
+    let (first_line, second_line) = parse(input)?;
+
+    let first_segments = LineIter::new(first_line.into_iter());
+    let second_segments: Vec<_> = LineIter::new(second_line.into_iter())
         .scan(0, |distance, (p, l)| {
             *distance += l.length();
             Some((*distance, p, l))
@@ -57,8 +42,8 @@ pub fn part_two(input: &str) -> i32 {
             let total_distance = sl_distance + fl_distance;
 
             if let Some(cross) = fl.crosses(sl) {
-                let distance = total_distance + fl_start.distance_to(&cross)
-                    - (sl.length() - sl_start.distance_to(&cross));
+                let distance = total_distance + fl_start.manhattan(cross)
+                    - (sl.length() - sl_start.manhattan(cross));
                 if distance < min_distance {
                     min_distance = distance;
                     break;
@@ -72,7 +57,16 @@ pub fn part_two(input: &str) -> i32 {
         fl_distance += fl.length();
     }
 
-    min_distance
+    Ok(min_distance)
+}
+
+fn parse(input: &str) -> Result<(Vec<Movement>, Vec<Movement>), ParseError> {
+    let wires = parse::lines(input, |p| p.separated(",", Movement::parse))?;
+    let [first, second] = wires
+        .try_into()
+        .map_err(|_| ParseError::end(input, "expected two wires"))?;
+
+    Ok((first, second))
 }
 
 #[derive(Debug, Clone)]
@@ -97,10 +91,7 @@ impl Line {
                     && ns.start.x > ew.start.x
                     && ns.start.x <= ew.end.x
                 {
-                    Some(Point {
-                        x: ns.start.x,
-                        y: ew.start.y,
-                    })
+                    Some(Point::new(ns.start.x, ew.start.y))
                 } else {
                     None
                 }
@@ -110,12 +101,12 @@ impl Line {
     }
 
     fn new(location: &mut Point, movement: Movement) -> Self {
-        let old_location = location.clone();
+        let old_location = *location;
         match movement {
             Movement::Left(offset) => {
                 location.x -= offset;
                 Line::EastWest(Segment {
-                    start: location.clone(),
+                    start: *location,
                     end: old_location,
                 })
             }
@@ -123,20 +114,20 @@ impl Line {
                 location.x += offset;
                 Line::EastWest(Segment {
                     start: old_location,
-                    end: location.clone(),
+                    end: *location,
                 })
             }
             Movement::Up(offset) => {
                 location.y += offset;
                 Line::NorthSouth(Segment {
                     start: old_location,
-                    end: location.clone(),
+                    end: *location,
                 })
             }
             Movement::Down(offset) => {
                 location.y -= offset;
                 Line::NorthSouth(Segment {
-                    start: location.clone(),
+                    start: *location,
                     end: old_location,
                 })
             }
@@ -160,7 +151,7 @@ impl<I: Iterator<Item = Movement>> LineIter<I> {
     pub fn new(moves: I) -> Self {
         LineIter {
             moves,
-            location: Point { x: 0, y: 0 },
+            location: Point::new(0, 0),
         }
     }
 }
@@ -170,7 +161,7 @@ impl<I: Iterator<Item = Movement>> Iterator for LineIter<I> {
 
     fn next(&mut self) -> Option<Self::Item> {
         if let Some(movement) = self.moves.next() {
-            let start = self.location.clone();
+            let start = self.location;
             Some((start, Line::new(&mut self.location, movement)))
         } else {
             None
@@ -186,59 +177,35 @@ pub enum Movement {
     Down(i32),
 }
 
-pub enum ParseMovementError {
-    InvalidOffset,
-    InvalidDirection,
-}
-
-impl std::str::FromStr for Movement {
-    type Err = ParseMovementError;
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let offset = input
-            .get(1..)
-            .map(i32::from_str)
-            .and_then(Result::ok)
-            .ok_or(ParseMovementError::InvalidOffset)?;
-
-        match input.chars().next() {
-            Some('L') => Ok(Movement::Left(offset)),
-            Some('R') => Ok(Movement::Right(offset)),
-            Some('U') => Ok(Movement::Up(offset)),
-            Some('D') => Ok(Movement::Down(offset)),
-            _ => Err(ParseMovementError::InvalidDirection),
+impl Movement {
+    fn parse(p: &mut Parser) -> Result<Self, ParseError> {
+        let start = *p;
+        let direction = p.char()?;
+        let offset = p.int()?;
+
+        match direction {
+            'L' => Ok(Movement::Left(offset)),
+            'R' => Ok(Movement::Right(offset)),
+            'U' => Ok(Movement::Up(offset)),
+            'D' => Ok(Movement::Down(offset)),
+            _ => Err(start.error("unknown direction")),
         }
     }
 }
 
-#[derive(Debug, Clone, Hash, Eq, PartialEq)]
-pub struct Point {
-    x: i32,
-    y: i32,
-}
-
-impl Point {
-    pub fn distance(&self) -> i32 {
-        self.x.abs() + self.y.abs()
-    }
-
-    pub fn distance_to(&self, other: &Point) -> i32 {
-        (self.x - other.x).abs() + (self.y - other.y).abs()
-    }
-}
-
 #[test]
 fn test() {
     let input = r#"R75,D30,R83,U83,L12,D49,R71,U7,L72
 U62,R66,U55,R34,D71,R55,D58,R83
 "#;
 
-    assert_eq!(159, part_one(input));
-    assert_eq!(610, part_two(input));
+    assert_eq!(Ok(159), part_one(input));
+    assert_eq!(Ok(610), part_two(input));
 
     let input = r#"R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51
 U98,R91,D20,R16,D67,R40,U7,R15,U6,R7
 "#;
 
-    assert_eq!(135, part_one(input));
-    assert_eq!(410, part_two(input));
+    assert_eq!(Ok(135), part_one(input));
+    assert_eq!(Ok(410), part_two(input));
 }
@@ -1,5 +1,7 @@
 use super::intcode;
 
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> i32 {
     let mut machine = intcode::Machine::new(input);
     let mut result = 0;
@@ -1,5 +1,7 @@
 use crate::HashMap;
 
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> usize {
     let lines = input.as_bytes().chunks(8);
 
@@ -1,3 +1,5 @@
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> usize {
     solve_part_one::<25, 6>(input)
 }
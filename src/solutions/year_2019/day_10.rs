@@ -1,8 +1,14 @@
+use crate::geometry::Point2;
+use crate::math::gcd;
 use crate::{HashMap, HashSet};
 
-use super::{Gcd, Point2};
+crate::solution!(part_one, part_two);
 
-pub fn part_one(input: &str) -> DayTenResult {
+pub fn part_one(input: &str) -> usize {
+    best_station(input).max_roids
+}
+
+fn best_station(input: &str) -> Station {
     let grid = AsteroidGrid::new(input);
 
     let mut max_roids = 0;
@@ -15,11 +21,11 @@ pub fn part_one(input: &str) -> DayTenResult {
         }
     }
 
-    DayTenResult { max_roids, target }
+    Station { max_roids, target }
 }
 
 pub fn part_two(input: &str) -> i32 {
-    let DayTenResult { target, .. } = part_one(input);
+    let Station { target, .. } = best_station(input);
     let mut grid = AsteroidGrid::new(input);
 
     grid.angles
@@ -125,8 +131,8 @@ fn create_angle_grid(width: i32, height: i32) -> Vec<(Point2<i32>, f32)> {
     for y in 1..=height {
         for x in 1..=width {
             let angle = PI - (x as f32 / y as f32).atan();
-            let gcd = x.gcd(y);
-            results.insert(Point2::new(x / gcd, y / gcd), angle);
+            let divisor = gcd(x, y);
+            results.insert(Point2::new(x / divisor, y / divisor), angle);
         }
     }
 
@@ -168,17 +174,11 @@ fn create_angle_grid(width: i32, height: i32) -> Vec<(Point2<i32>, f32)> {
 }
 
 #[derive(Debug, PartialEq, Eq)]
-pub struct DayTenResult {
+struct Station {
     max_roids: usize,
     target: Point2<i32>,
 }
 
-impl std::fmt::Display for DayTenResult {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.max_roids)
-    }
-}
-
 #[test]
 fn test() {
     let input = r#".#..##.###...#######
@@ -203,10 +203,11 @@ fn test() {
 ###.##.####.##.#..##
 "#;
 
-    let result = DayTenResult {
+    let result = Station {
         max_roids: 210,
         target: Point2::new(11, 13),
     };
-    assert_eq!(result, part_one(input));
+    assert_eq!(result, best_station(input));
+    assert_eq!(210, part_one(input));
     assert_eq!(802, part_two(input));
 }
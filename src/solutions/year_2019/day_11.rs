@@ -1,6 +1,10 @@
 use crate::HashMap;
+use crate::geometry::Point2;
+use crate::grid::Direction;
 
-use super::{intcode, Point2};
+use super::intcode;
+
+crate::solution!(part_one, part_two);
 
 pub fn part_one(input: &str) -> usize {
     let mut machine = intcode::Machine::<i64, _>::new(input);
@@ -27,7 +31,12 @@ pub fn part_one(input: &str) -> usize {
             _ => unreachable!(),
         };
 
-        dir = dir.do_move(movement == 0, &mut point);
+        dir = if movement == 0 {
+            dir.turn_left()
+        } else {
+            dir.turn_right()
+        };
+        point = point.step(dir);
     }
 
     painted_spots.len()
@@ -71,7 +80,12 @@ pub fn part_two(input: &str) -> String {
             _ => unreachable!(),
         };
 
-        dir = dir.do_move(movement == 0, &mut point);
+        dir = if movement == 0 {
+            dir.turn_left()
+        } else {
+            dir.turn_right()
+        };
+        point = point.step(dir);
     }
 
     let mut result = String::new();
@@ -91,34 +105,3 @@ pub fn part_two(input: &str) -> String {
 
     result
 }
-
-#[derive(Debug)]
-enum Direction {
-    Up,
-    Left,
-    Right,
-    Down,
-}
-
-impl Direction {
-    fn do_move(self, turn_left: bool, point: &mut Point2<i32>) -> Self {
-        match (self, turn_left) {
-            (Direction::Up, true) | (Direction::Down, false) => {
-                point.x -= 1;
-                Direction::Left
-            }
-            (Direction::Up, false) | (Direction::Down, true) => {
-                point.x += 1;
-                Direction::Right
-            }
-            (Direction::Left, false) | (Direction::Right, true) => {
-                point.y -= 1;
-                Direction::Up
-            }
-            (Direction::Left, true) | (Direction::Right, false) => {
-                point.y += 1;
-                Direction::Down
-            }
-        }
-    }
-}
@@ -1,4 +1,8 @@
-use super::{Gcd, Point3};
+use crate::geometry::Point3;
+use crate::math::lcm_iter;
+
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> i64 {
     solve_part_one::<1000>(input)
 }
@@ -92,10 +96,7 @@ pub fn part_two(input: &str) -> usize {
     let y_length = y_handle.join().unwrap();
     let z_length = z_handle.join().unwrap();
 
-    let xy_gcd = x_length.gcd(y_length);
-    let xy_lcm = (x_length * y_length) / xy_gcd;
-    let xyz_gcd = xy_lcm.gcd(z_length);
-    (xy_lcm * z_length) / xyz_gcd
+    lcm_iter([x_length, y_length, z_length])
 }
 
 fn sequence_length(initial_points: &[i64]) -> usize {
@@ -1,4 +1,8 @@
-use super::{intcode, Point2};
+use crate::geometry::Point2;
+
+use super::intcode;
+
+crate::solution!(part_one, part_two);
 
 pub fn part_one(input: &str) -> i64 {
     let mut machine = intcode::Machine::new(input);
@@ -1,60 +1,17 @@
-use crate::HashMap;
+use crate::parse::{self, Parser};
+use crate::{HashMap, ParseError};
 
-pub fn part_one(input: &str) -> u64 {
-    let reactions: HashMap<_, _> = input
-        .lines()
-        .map(|l| {
-            let mut sides = l.split(" => ");
-            let input = sides.next().unwrap();
-            let output = sides.next().unwrap();
+crate::solution!(part_one, part_two);
 
-            let mut output = output.split_whitespace();
-            let output_n: u64 = output.next().unwrap().parse().unwrap();
-            let output_chem = output.next().unwrap();
-
-            let mut inputs = Vec::new();
-
-            for input in input.split(", ") {
-                let mut input = input.split_whitespace();
-                let input_n: u64 = input.next().unwrap().parse().unwrap();
-                let input_chem = input.next().unwrap();
-
-                inputs.push((input_chem, input_n))
-            }
-
-            (output_chem, (output_n, inputs))
-        })
-        .collect();
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
+    let reactions = parse(input)?;
 
     let mut inventory = HashMap::new();
-    fill_request(&reactions, &mut inventory, "FUEL", 1)
+    Ok(fill_request(&reactions, &mut inventory, "FUEL", 1))
 }
 
-pub fn part_two(input: &str) -> u64 {
-    let reactions: HashMap<_, _> = input
-        .lines()
-        .map(|l| {
-            let mut sides = l.split(" => ");
-            let input = sides.next().unwrap();
-            let output = sides.next().unwrap();
-
-            let mut output = output.split_whitespace();
-            let output_n: u64 = output.next().unwrap().parse().unwrap();
-            let output_chem = output.next().unwrap();
-
-            let mut inputs = Vec::new();
-
-            for input in input.split(", ") {
-                let mut input = input.split_whitespace();
-                let input_n: u64 = input.next().unwrap().parse().unwrap();
-                let input_chem = input.next().unwrap();
-
-                inputs.push((input_chem, input_n))
-            }
-
-            (output_chem, (output_n, inputs))
-        })
-        .collect();
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
+    let reactions = parse(input)?;
 
     let n = 1_000_000_000_000;
     let mut inventory = HashMap::new();
@@ -76,7 +33,7 @@ pub fn part_two(input: &str) -> u64 {
 
     loop {
         if fuel_max - fuel_min <= 1 {
-            return fuel_min;
+            return Ok(fuel_min);
         }
 
         inventory.clear();
@@ -90,6 +47,26 @@ pub fn part_two(input: &str) -> u64 {
     }
 }
 
+fn parse(input: &str) -> Result<HashMap<&str, (u64, Vec<(&str, u64)>)>, ParseError> {
+    let reactions = parse::lines(input, |p| {
+        let inputs = p.separated(", ", parse_chemical)?;
+        p.token(" => ")?;
+        let (output_chem, output_n) = parse_chemical(p)?;
+
+        Ok((output_chem, (output_n, inputs)))
+    })?;
+
+    Ok(reactions.into_iter().collect())
+}
+
+fn parse_chemical<'a>(p: &mut Parser<'a>) -> Result<(&'a str, u64), ParseError> {
+    let n = p.int()?;
+    p.token(" ")?;
+    let chem = p.take_while(|c| c.is_ascii_alphabetic());
+
+    Ok((chem, n))
+}
+
 fn fill_request<'a>(
     reactions: &HashMap<&'a str, (u64, Vec<(&'a str, u64)>)>,
     inventory: &mut HashMap<&'a str, u64>,
@@ -159,6 +136,6 @@ fn test() {
 5 BHXH, 4 VRPVC => 5 LTCX
 "#;
 
-    assert_eq!(2210736, part_one(input));
-    assert_eq!(460664, part_two(input));
+    assert_eq!(Ok(2210736), part_one(input));
+    assert_eq!(Ok(460664), part_two(input));
 }
@@ -1,3 +1,5 @@
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> String {
     let mut signal: Vec<_> = input
         .chars()
@@ -1,6 +1,9 @@
+use crate::geometry::Point2;
 use crate::HashMap;
 
-use super::{intcode, Point2};
+use super::intcode;
+
+crate::solution!(part_one, part_two);
 
 pub fn part_one(input: &str) -> i32 {
     let mut machine = intcode::Machine::new(input);
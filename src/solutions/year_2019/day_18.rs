@@ -1,8 +1,8 @@
-use super::Point2;
-use crate::{HashMap, HashSet};
-use std::collections::BinaryHeap;
+use crate::HashMap;
+use crate::grid::{Direction, Grid, Point};
+use crate::search;
 
-type Point = Point2<i32>;
+crate::solution!(part_one, part_two);
 
 pub fn part_one(input: &str) -> u32 {
     let map = Map::<1>::new(input);
@@ -101,74 +101,53 @@ impl Move {
 }
 
 struct Map<const STARTS: usize> {
-    cells: Vec<Cell>,
+    cells: Grid<Cell>,
     keys: HashMap<Key, Point>,
     doors: HashMap<Door, Point>,
     starts: [Point; STARTS],
-    width: i32,
-    height: i32,
 }
 
 impl<const STARTS: usize> Map<STARTS> {
     fn new(input: &str) -> Self {
-        let mut x = 0;
-        let mut y = 0;
-        let mut start = None;
-        let mut width = None;
-        let mut cells = Vec::new();
+        let chars = Grid::parse(input.trim(), |c| c.is_ascii_graphic().then_some(c as u8))
+            .expect("valid grid");
+        let start = chars.position(|&b| b == b'@').unwrap();
         let mut doors = HashMap::new();
         let mut keys = HashMap::new();
 
-        for b in input.trim().bytes() {
+        for (p, &b) in chars.iter() {
             match b {
-                b'#' => cells.push(Cell::Wall),
-                b'.' => cells.push(Cell::Open),
-                b'@' => {
-                    start = Some(Point::new(x, y));
-                    cells.push(Cell::Open);
-                }
                 b'a'..=b'z' => {
-                    let key = Key::from_byte(b);
-                    keys.insert(key, Point::new(x, y));
-                    cells.push(Cell::Key(Key::from_byte(b)))
+                    keys.insert(Key::from_byte(b), p);
                 }
                 b'A'..=b'Z' => {
-                    let door = Door::from_byte(b);
-                    doors.insert(door, Point::new(x, y));
-                    cells.push(Cell::Door(door));
-                }
-                b'\n' => {
-                    if width.is_none() {
-                        width = Some(x);
-                    }
-                    y += 1;
-                    x = 0;
-                    continue;
+                    doors.insert(Door::from_byte(b), p);
                 }
-                _ => unreachable!(),
+                _ => (),
             }
-            x += 1;
         }
 
-        let height = y + 1;
-        let width = width.unwrap();
-        let start = start.unwrap();
+        let mut cells = chars.map(|&b| match b {
+            b'#' => Cell::Wall,
+            b'.' | b'@' => Cell::Open,
+            b'a'..=b'z' => Cell::Key(Key::from_byte(b)),
+            b'A'..=b'Z' => Cell::Door(Door::from_byte(b)),
+            _ => unreachable!(),
+        });
 
         let starts = match STARTS {
             1 => [start; STARTS],
             4 => {
-                let x = start.x as usize;
-                let y = start.y as usize;
-                let width = width as usize;
-                cells[(y + 0) * width + (x + 0)] = Cell::Wall;
-                cells[(y - 1) * width + (x - 1)] = Cell::Open;
-                cells[(y - 1) * width + (x + 0)] = Cell::Wall;
-                cells[(y - 1) * width + (x + 1)] = Cell::Open;
-                cells[(y + 0) * width + (x - 1)] = Cell::Wall;
-                cells[(y + 0) * width + (x + 1)] = Cell::Wall;
-                cells[(y + 1) * width + (x - 1)] = Cell::Open;
-                cells[(y + 1) * width + (x + 0)] = Cell::Wall;
-                cells[(y + 1) * width + (x + 1)] = Cell::Open;
+                for offset in Point::default().neighbors_diagonal() {
+                    let cell = if offset.x != 0 && offset.y != 0 {
+                        Cell::Open
+                    } else {
+                        Cell::Wall
+                    };
+                    cells.set(start + offset, cell);
+                }
+                cells.set(start, Cell::Wall);
+
                 let x = start.x;
                 let y = start.y;
                 vec![
@@ -188,88 +167,54 @@ impl<const STARTS: usize> Map<STARTS> {
             keys,
             doors,
             starts,
-            width,
-            height,
-        }
-    }
-
-    fn get(&self, point: Point) -> Option<Cell> {
-        if point.x < 0 || point.x >= self.width || point.y < 0 || point.y >= self.height {
-            None
-        } else {
-            let idx = point.y * self.width + point.x;
-            Some(self.cells[idx as usize])
         }
     }
 
     fn moves(&self, point: Point) -> impl Iterator<Item = Move> + '_ {
-        let mut i = 0;
-        std::iter::from_fn(move || loop {
-            let p = match i {
-                0 => Point::new(point.x - 1, point.y),
-                1 => Point::new(point.x + 1, point.y),
-                2 => Point::new(point.x, point.y - 1),
-                3 => Point::new(point.x, point.y + 1),
-                _ => return None,
-            };
-            i += 1;
-
-            let cell = self.get(p)?;
-
-            return match cell {
-                Cell::Wall => continue,
-                Cell::Open => Some(Move::Point(p)),
-                Cell::Door(d) => Some(Move::Door(d, p)),
-                Cell::Key(k) => Some(Move::Key(k, p)),
-            };
+        [
+            Direction::Left,
+            Direction::Right,
+            Direction::Up,
+            Direction::Down,
+        ]
+        .into_iter()
+        .map(move |dir| point.step(dir))
+        .map_while(|p| self.cells.get(p).map(|&cell| (p, cell)))
+        .filter_map(|(p, cell)| match cell {
+            Cell::Wall => None,
+            Cell::Open => Some(Move::Point(p)),
+            Cell::Door(d) => Some(Move::Door(d, p)),
+            Cell::Key(k) => Some(Move::Key(k, p)),
         })
     }
 
     fn paths(&self, start: Point) -> impl Iterator<Item = (Cell, u32)> + '_ {
-        let mut visited = HashSet::new();
-        let mut haystack = BinaryHeap::new();
-
-        haystack.push(Ordered(0, Move::Point(start)));
-
-        std::iter::from_fn(move || {
-            while let Some(Ordered(count, next)) = haystack.pop() {
-                let p = next.point();
-                visited.insert(next.point());
-
-                if !matches!(next, Move::Door(_, _)) {
-                    haystack.extend(self.moves(p).filter_map(|m| {
-                        if !visited.contains(&m.point()) {
-                            Some(Ordered(count + 1, m))
-                        } else {
-                            None
-                        }
-                    }));
-                };
-
-                match next {
-                    Move::Point(_) => (),
-                    Move::Key(k, _) => return Some((Cell::Key(k), count)),
-                    Move::Door(d, _) => return Some((Cell::Door(d), count)),
-                }
+        let steps = search::bfs_reachable(start, |&p| {
+            // doors end a path, keys are passed through
+            if p != start && matches!(self.cells[p], Cell::Door(_)) {
+                return Vec::new();
             }
-
-            None
-        })
+            self.moves(p).map(|m| m.point()).collect()
+        });
+
+        steps
+            .into_iter()
+            .filter(move |&(p, _)| p != start)
+            .map(|(p, count)| (self.cells[p], count as u32))
+            .filter(|(cell, _)| matches!(cell, Cell::Key(_) | Cell::Door(_)))
     }
 
     #[allow(dead_code)]
     fn dump(&self) {
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let p = Point::new(x, y);
-                if self.starts.iter().any(|s| *s == p) {
-                    print!("@");
-                } else {
-                    let cell = self.get(p).unwrap();
-                    print!("{}", cell);
-                }
+        for (p, cell) in self.cells.iter() {
+            if self.starts.contains(&p) {
+                print!("@");
+            } else {
+                print!("{}", cell);
+            }
+            if p.x as usize == self.cells.width() - 1 {
+                println!();
             }
-            println!();
         }
     }
 }
@@ -397,64 +342,24 @@ impl<const STARTS: usize> Graph<STARTS> {
         })
     }
 
-    fn virtual_edges<'a>(
-        &'a self,
-        node: Node,
-        keyring: Keyring,
-        visited: &'a mut HashSet<Node>,
-        haystack: &'a mut BinaryHeap<Ordered<Edge>>,
-    ) -> impl Iterator<Item = Edge> + 'a {
-        visited.clear();
-        haystack.clear();
-        haystack.extend(
+    /// The keys reachable from `start` with the doors `keyring` unlocks, and the cost to each.
+    fn keys(&self, start: Node, keyring: Keyring) -> impl Iterator<Item = (Key, u32)> {
+        let costs = search::dijkstra_reachable([start], |&node| {
             self.unlocked_edges(node, keyring)
-                .map(|e| Ordered(e.cost, e)),
-        );
-
-        std::iter::from_fn(move || {
-            while let Some(Ordered(cost, next)) = haystack.pop() {
-                let node = self.nodes[next.right.0];
-
-                if !visited.insert(node) {
-                    continue;
-                }
-
-                haystack.extend(self.unlocked_edges(node, keyring).filter_map(|edge| {
-                    let right_node = self.nodes[edge.right.0];
-                    if !visited.contains(&right_node) {
-                        Some(Ordered(cost + edge.cost, edge))
-                    } else {
-                        None
-                    }
-                }));
-
-                if let Node::Key(_) = node {
-                    let mut next = next;
-                    next.cost = cost;
-                    return Some(next);
-                }
-            }
-
-            None
-        })
+                .map(|edge| (self.nodes[edge.right.0], edge.cost))
+        });
+
+        costs
+            .into_iter()
+            .filter_map(move |(node, cost)| match node {
+                Node::Key(key) if node != start => Some((key, cost)),
+                _ => None,
+            })
     }
 
-    fn reachable_keys(
-        &self,
-        start: Node,
-        visited: &mut HashSet<Node>,
-        haystack: &mut BinaryHeap<Ordered<Edge>>,
-    ) -> Keyring {
-        self.virtual_edges(start, Keyring::master(), visited, haystack)
-            .filter_map(|e| {
-                let node = self.nodes[e.right.0];
-                if let Node::Key(k) = node {
-                    Some(k)
-                } else {
-                    None
-                }
-            })
-            .fold(Keyring::empty(), |mut keyring, key| {
+    fn reachable_keys(&self, start: Node) -> Keyring {
+        self.keys(start, Keyring::master())
+            .fold(Keyring::empty(), |mut keyring, (key, _)| {
                 keyring.add(key);
                 keyring
             })
@@ -462,71 +367,34 @@ impl<const STARTS: usize> Graph<STARTS> {
 
     fn route(&self) -> u32 {
         let positions = SearchPositions::<STARTS>::new();
-        let mut scratch_set = HashSet::new();
-        let mut scratch_heap = BinaryHeap::new();
         let reachable_keys = positions
             .0
             .iter()
-            .map(|p| self.reachable_keys(*p, &mut scratch_set, &mut scratch_heap))
+            .map(|&p| self.reachable_keys(p))
             .collect::<Vec<_>>();
 
-        let keyring = Keyring::empty();
-        let mut visited = HashSet::new();
-        let mut haystack = BinaryHeap::new();
-
-        haystack.push(Ordered(0, (positions, None, keyring)));
-
-        while let Some(Ordered(cost, (positions, robot_idx, mut keyring))) = haystack.pop() {
-            if let Some(robot_idx) = robot_idx {
-                if !visited.insert((positions, keyring)) {
-                    continue;
-                }
-
-                if let Node::Key(k) = positions.0[robot_idx] {
-                    keyring.add(k);
-
-                    if keyring.count() == self.key_count {
-                        return cost;
+        search::dijkstra(
+            [(positions, Keyring::empty())],
+            |&(positions, keyring)| {
+                let mut moves = Vec::new();
+                for (idx, &robot) in positions.0.iter().enumerate() {
+                    if keyring.overlaps(reachable_keys[idx]) {
+                        continue;
                     }
-                }
-            }
 
-            for (idx, &robot) in positions.0.iter().enumerate() {
-                if keyring.overlaps(reachable_keys[idx]) {
-                    continue;
+                    moves.extend(self.keys(robot, keyring).map(|(key, cost)| {
+                        let mut positions = positions;
+                        positions.0[idx] = Node::Key(key);
+                        let mut keyring = keyring;
+                        keyring.add(key);
+                        ((positions, keyring), cost)
+                    }));
                 }
-
-                haystack.extend(
-                    self.virtual_edges(robot, keyring, &mut scratch_set, &mut scratch_heap)
-                        .filter_map(|e| {
-                            let right_node = self.nodes[e.right.0];
-                            let mut positions = positions;
-                            positions.0[idx] = right_node;
-
-                            if visited.contains(&(positions, keyring)) {
-                                None
-                            } else {
-                                Some(Ordered(cost + e.cost, (positions, Some(idx), keyring)))
-                            }
-                        }),
-                );
-            }
-        }
-
-        0
-    }
-}
-
-#[derive(Copy, Clone, PartialEq, Eq, Hash)]
-struct Ordered<T>(u32, T);
-impl<T: Eq> std::cmp::PartialOrd for Ordered<T> {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-impl<T: Eq> std::cmp::Ord for Ordered<T> {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.0.cmp(&self.0)
+                moves
+            },
+            |(_, keyring)| keyring.count() == self.key_count,
+        )
+        .map_or(0, |(_, cost)| cost)
     }
 }
 
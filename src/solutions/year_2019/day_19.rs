@@ -1,5 +1,7 @@
 use super::intcode::{Interrupt, Machine, VecMem};
 
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> usize {
     let mut machine = Machine::new(input);
     (0..50)
@@ -1,5 +1,8 @@
-use crate::{HashMap, HashSet};
-use std::collections::BinaryHeap;
+use crate::HashMap;
+use crate::grid::{Direction, Grid, Point};
+use crate::search;
+
+crate::solution!(part_one, part_two);
 
 pub fn part_one(input: &str) -> i32 {
     let map = Map::new(input);
@@ -22,127 +25,71 @@ enum Cell {
 }
 
 struct Map {
-    cells: Vec<Cell>,
-    width: usize,
-    height: usize,
+    cells: Grid<Cell>,
 }
 
 impl Map {
     fn new(input: &str) -> Self {
-        let mut cells = Vec::new();
-        let mut width = None;
-        let mut height = 0;
-        for line in input.lines() {
-            let line = line.as_bytes();
-            if width.is_none() {
-                width = Some(line.len());
+        let lines: Vec<_> = input.lines().collect();
+        let width = lines.iter().map(|line| line.len()).max().unwrap();
+        let mut cells = Grid::new(width, lines.len(), Cell::Void);
+
+        for (y, line) in lines.into_iter().enumerate() {
+            if line.is_empty() {
+                panic!()
             }
 
-            for b in line {
+            for (x, b) in line.bytes().enumerate() {
                 let cell = match b {
                     b' ' => Cell::Void,
                     b'#' => Cell::Wall,
                     b'.' => Cell::Open,
-                    b'A'..=b'Z' => Cell::Letter(*b),
+                    b'A'..=b'Z' => Cell::Letter(b),
                     _ => unreachable!(),
                 };
 
-                cells.push(cell);
-            }
-
-            if !line.is_empty() {
-                height += 1;
-            } else {
-                panic!()
+                cells.set(Point::new(x as i32, y as i32), cell);
             }
         }
 
-        let width = width.unwrap();
-
-        Self {
-            cells,
-            width,
-            height,
-        }
+        Self { cells }
     }
 
-    fn get(&self, x: i32, y: i32) -> Option<Cell> {
-        if x < 0 || y < 0 {
-            return None;
-        }
-
-        let x = x as usize;
-        let y = y as usize;
-
-        if x >= self.width || y >= self.height {
-            None
-        } else {
-            let idx = y * self.width + x;
-            self.cells.get(idx).copied()
-        }
-    }
-
-    fn neighbors(&self, x: i32, y: i32) -> impl Iterator<Item = (Cell, i32, i32)> + '_ {
-        let mut i = 0;
-        std::iter::from_fn(move || loop {
-            let (x, y) = match i {
-                0 => (x + 1, y + 0),
-                1 => (x - 1, y + 0),
-                2 => (x + 0, y + 1),
-                3 => (x + 0, y - 1),
-                _ => return None,
-            };
-
-            i += 1;
-
-            if let Some(cell) = self.get(x, y) {
-                return Some((cell, x, y));
-            }
-        })
+    fn neighbors(&self, p: Point) -> impl Iterator<Item = (Cell, Point)> + '_ {
+        [
+            Direction::Right,
+            Direction::Left,
+            Direction::Down,
+            Direction::Up,
+        ]
+        .into_iter()
+        .map(move |dir| p.step(dir))
+        .filter_map(|p| self.cells.get(p).map(|&cell| (cell, p)))
     }
 
     fn paths<'a, T: Copy>(
         &'a self,
-        start_x: i32,
-        start_y: i32,
-        targets: &'a HashMap<(i32, i32), T>,
+        start: Point,
+        targets: &'a HashMap<Point, T>,
     ) -> impl Iterator<Item = (T, i32)> + 'a {
-        let mut visited = HashSet::new();
-        let mut haystack = BinaryHeap::new();
-
-        haystack.push((0, (start_x, start_y)));
-
-        std::iter::from_fn(move || {
-            while let Some((cost, (x, y))) = haystack.pop() {
-                if !visited.insert((x, y)) {
-                    continue;
-                }
-
-                haystack.extend(self.neighbors(x, y).filter_map(|(ty, x, y)| {
-                    if let Cell::Open = ty {
-                        Some((cost + 1, (x, y)))
-                    } else {
-                        None
-                    }
-                }));
-
-                if let Some(targ) = targets.get(&(x, y)) {
-                    if (start_x, start_y) != (x, y) {
-                        return Some((*targ, cost));
-                    }
-                }
-            }
-
-            None
+        let steps = search::bfs_reachable(start, |&p| {
+            self.neighbors(p)
+                .filter(|(cell, _)| *cell == Cell::Open)
+                .map(|(_, p)| p)
+        });
+
+        steps.into_iter().filter_map(move |(p, steps)| {
+            let target = targets.get(&p).filter(|_| p != start)?;
+            Some((*target, steps as i32))
         })
     }
 
-    fn classify(&self, x: i32, y: i32) -> NodeClass {
+    fn classify(&self, p: Point) -> NodeClass {
         const EDGE_WIDTH: i32 = 4;
-        if x >= EDGE_WIDTH
-            && y >= EDGE_WIDTH
-            && x < self.width as i32 - EDGE_WIDTH
-            && y < self.height as i32 - EDGE_WIDTH
+        if p.x >= EDGE_WIDTH
+            && p.y >= EDGE_WIDTH
+            && p.x < self.cells.width() as i32 - EDGE_WIDTH
+            && p.y < self.cells.height() as i32 - EDGE_WIDTH
         {
             NodeClass::Descend
         } else {
@@ -214,47 +161,41 @@ impl Graph {
 
         let mut teleporters = HashMap::new();
 
-        for y in 0..map.height {
-            let y = y as i32;
-            for x in 0..map.width {
-                let x = x as i32;
-
-                let cell = map.get(x, y);
-                if let Some(Cell::Letter(l1)) = cell {
-                    let mut l2 = None;
-                    let mut point = None;
-
-                    for (cell, x, y) in map.neighbors(x, y) {
-                        match cell {
-                            Cell::Letter(l) => l2 = Some((l, x, y)),
-                            Cell::Open => point = Some((x, y)),
-                            _ => (),
-                        }
+        for (p, &cell) in map.cells.iter() {
+            if let Cell::Letter(l1) = cell {
+                let mut l2 = None;
+                let mut point = None;
+
+                for (cell, n) in map.neighbors(p) {
+                    match cell {
+                        Cell::Letter(l) => l2 = Some((l, n)),
+                        Cell::Open => point = Some(n),
+                        _ => (),
                     }
+                }
 
-                    let l2 = l2.unwrap();
+                let (l2, l2_point) = l2.unwrap();
 
-                    if point.is_none() {
-                        point = map
-                            .neighbors(l2.1, l2.2)
-                            .find(|(c, _, _)| *c == Cell::Open)
-                            .map(|(_, x, y)| (x, y));
-                    }
+                if point.is_none() {
+                    point = map
+                        .neighbors(l2_point)
+                        .find(|(c, _)| *c == Cell::Open)
+                        .map(|(_, n)| n);
+                }
 
-                    let point = point.unwrap();
+                let point = point.unwrap();
 
-                    let swap = y > l2.2 || x > l2.1;
+                let swap = p.y > l2_point.y || p.x > l2_point.x;
 
-                    let node = if swap {
-                        Node { name: [l2.0, l1] }
-                    } else {
-                        Node { name: [l1, l2.0] }
-                    };
+                let node = if swap {
+                    Node { name: [l2, l1] }
+                } else {
+                    Node { name: [l1, l2] }
+                };
 
-                    let node = ClassifiedNode(map.classify(point.0, point.1), node);
+                let node = ClassifiedNode(map.classify(point), node);
 
-                    teleporters.insert(point, node);
-                }
+                teleporters.insert(point, node);
             }
         }
 
@@ -271,11 +212,11 @@ impl Graph {
         let mut ordered_nodes: Vec<_> = teleporters.iter().collect();
         ordered_nodes.sort_by_key(|n| u16::from_be_bytes(n.1 .1.name));
 
-        for (&(x, y), &ClassifiedNode(left_class, node)) in ordered_nodes {
+        for (&point, &ClassifiedNode(left_class, node)) in ordered_nodes {
             let left = *node_map.get(&node).unwrap();
             let edge_id = EdgeId(edges.len());
 
-            for (ClassifiedNode(right_class, right_node), cost) in map.paths(x, y, &teleporters) {
+            for (ClassifiedNode(right_class, right_node), cost) in map.paths(point, &teleporters) {
                 let right = *node_map.get(&right_node).unwrap();
 
                 let class = if left_class == right_class {
@@ -353,67 +294,27 @@ impl Graph {
         let start = Node { name: [b'A', b'A'] };
         let end = Node { name: [b'Z', b'Z'] };
 
-        let mut visited = HashSet::new();
-        let mut haystack = BinaryHeap::new();
-
-        haystack.push(Ordered(0, start));
-
-        while let Some(Ordered(cost, node)) = haystack.pop() {
-            if !visited.insert(node) {
-                continue;
-            }
-
-            if node == end {
-                return cost - 1;
-            }
-
-            haystack.extend(
-                self.edges(node)
-                    .map(|e| Ordered(cost + e.cost, self.nodes[e.right.0])),
-            );
-        }
-
-        0
+        search::dijkstra(
+            [start],
+            |&node| self.edges(node).map(|e| (self.nodes[e.right.0], e.cost)),
+            |&node| node == end,
+        )
+        .map_or(0, |(_, cost)| cost - 1)
     }
 
     fn recursive_path(self) -> i32 {
         let start = RecursiveNode(Node { name: [b'A', b'A'] }, 0);
         let end = RecursiveNode(Node { name: [b'Z', b'Z'] }, 0);
 
-        let mut visited = HashSet::new();
-        let mut haystack = BinaryHeap::new();
-
-        haystack.push(Ordered(0, start));
-
-        while let Some(Ordered(cost, node)) = haystack.pop() {
-            if !visited.insert(node) {
-                continue;
-            }
-
-            if node == end {
-                return cost - 1;
-            }
-
-            haystack
-                .extend(self.recursive_edges(node).map(|e| {
-                    Ordered(cost + e.0.cost, RecursiveNode(self.nodes[e.0.right.0], e.1))
-                }));
-        }
-
-        0
-    }
-}
-
-#[derive(Copy, Clone, PartialEq, Eq, Hash)]
-struct Ordered<T>(i32, T);
-impl<T: Eq> std::cmp::PartialOrd for Ordered<T> {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-impl<T: Eq> std::cmp::Ord for Ordered<T> {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.0.cmp(&self.0)
+        search::dijkstra(
+            [start],
+            |&node| {
+                self.recursive_edges(node)
+                    .map(|e| (RecursiveNode(self.nodes[e.0.right.0], e.1), e.0.cost))
+            },
+            |&node| node == end,
+        )
+        .map_or(0, |(_, cost)| cost - 1)
     }
 }
 
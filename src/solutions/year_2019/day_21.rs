@@ -1,6 +1,8 @@
 use super::intcode::{Interrupt, Machine, VecMem};
 use std::iter::once;
 
+crate::solution!(part_one, part_two);
+
 macro_rules! springcode {
     (RUN $($token:ident)*) => { springcode!(@build, RunReg, $($token)*) };
     (WALK $($token:ident)*) => { springcode!(@build, WalkReg, $($token)*) };
@@ -1,91 +1,69 @@
-pub fn part_one(input: &str) -> i64 {
+use crate::ParseError;
+use crate::math::Affine;
+use crate::parse::{self, Parser};
+
+crate::solution!(part_one, part_two);
+
+pub fn part_one(input: &str) -> Result<i64, ParseError> {
     solve_part_one::<10007>(input, 2019)
 }
 
-pub fn part_two(_input: &str) -> i64 {
-    0
+pub fn part_two(input: &str) -> Result<i64, ParseError> {
+    solve_part_two::<119315717514047>(input, 101741582076661, 2020)
 }
 
-fn solve_part_one<const DECK_SIZE: i64>(input: &str, tracked: i64) -> i64 {
-    let tracker = input
-        .trim()
-        .lines()
-        .map(|s| s.parse::<Action>().unwrap())
-        .fold(CardTracker::new(DECK_SIZE, tracked), |tracker, action| {
-            tracker.apply(action)
-        });
-
-    tracker.position()
+fn solve_part_one<const DECK_SIZE: i64>(input: &str, tracked: i64) -> Result<i64, ParseError> {
+    Ok(shuffle(input, DECK_SIZE)?.apply(tracked))
 }
 
-struct CardTracker {
-    deck_size: i64,
-    tracked: i64,
+fn solve_part_two<const DECK_SIZE: i64>(
+    input: &str,
+    shuffles: u64,
+    position: i64,
+) -> Result<i64, ParseError> {
+    Ok(shuffle(input, DECK_SIZE)?
+        .pow(shuffles)
+        .invert()
+        .unwrap()
+        .apply(position))
 }
 
-impl CardTracker {
-    fn new(deck_size: i64, tracked: i64) -> Self {
-        Self { deck_size, tracked }
-    }
-
-    fn deal(&mut self) {
-        self.tracked = self.deck_size - self.tracked - 1;
-    }
-
-    fn cut(&mut self, cut: i64) {
-        self.tracked = (self.tracked - cut).rem_euclid(self.deck_size);
-    }
-
-    fn shuffle(&mut self, count: i64) {
-        self.tracked = (self.tracked * count) % self.deck_size;
-    }
-
-    fn apply(mut self, action: Action) -> Self {
-        match action {
-            Action::Deal => self.deal(),
-            Action::Cut(n) => self.cut(n),
-            Action::Shuffle(n) => self.shuffle(n),
-            Action::Print => {
-                println!("{}", self.tracked);
-            }
-        }
-        self
-    }
+// every technique moves a card's position by an affine map, so the whole shuffle is one as well
+fn shuffle(input: &str, deck_size: i64) -> Result<Affine, ParseError> {
+    let shuffle = parse::lines(input, Action::parse)?
+        .into_iter()
+        .fold(Affine::identity(deck_size), |shuffle, action| {
+            shuffle.then(&action.affine(deck_size))
+        });
 
-    fn position(&self) -> i64 {
-        self.tracked
-    }
+    Ok(shuffle)
 }
 
-#[allow(dead_code)]
 #[derive(Debug, Copy, Clone)]
 enum Action {
     Deal,
     Cut(i64),
     Shuffle(i64),
-    Print,
 }
 
-#[derive(Debug, Copy, Clone)]
-struct ActionParseErr;
-
-impl std::str::FromStr for Action {
-    type Err = ActionParseErr;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (kind, num) = s.rsplit_once(' ').ok_or(ActionParseErr)?;
-
-        match kind {
-            "deal into new" => Ok(Action::Deal),
-            "cut" => {
-                let num = num.parse().map_err(|_| ActionParseErr)?;
-                Ok(Action::Cut(num))
-            }
-            "deal with increment" => {
-                let num = num.parse().map_err(|_| ActionParseErr)?;
-                Ok(Action::Shuffle(num))
-            }
-            _ => Err(ActionParseErr),
+impl Action {
+    fn parse(p: &mut Parser) -> Result<Self, ParseError> {
+        if p.try_token("deal into new stack") {
+            Ok(Action::Deal)
+        } else if p.try_token("cut ") {
+            Ok(Action::Cut(p.int()?))
+        } else if p.try_token("deal with increment ") {
+            Ok(Action::Shuffle(p.int()?))
+        } else {
+            Err(p.error("unknown technique"))
+        }
+    }
+
+    fn affine(&self, deck_size: i64) -> Affine {
+        match *self {
+            Action::Deal => Affine::new(-1, -1, deck_size),
+            Action::Cut(n) => Affine::new(1, -n, deck_size),
+            Action::Shuffle(n) => Affine::new(n, 0, deck_size),
         }
     }
 }
@@ -96,19 +74,19 @@ fn test() {
 deal into new stack
 deal into new stack"#;
 
-    assert_eq!(2, solve_part_one::<10>(input, 6));
+    assert_eq!(Ok(2), solve_part_one::<10>(input, 6));
 
     let input = r#"cut 6
 deal with increment 7
 deal into new stack"#;
 
-    assert_eq!(9, solve_part_one::<10>(input, 6));
+    assert_eq!(Ok(9), solve_part_one::<10>(input, 6));
 
     let input = r#"deal with increment 7
 deal with increment 9
 cut -2"#;
 
-    assert_eq!(0, solve_part_one::<10>(input, 6));
+    assert_eq!(Ok(0), solve_part_one::<10>(input, 6));
 
     let input = r#"deal into new stack
 cut -2
@@ -121,5 +99,10 @@ deal with increment 9
 deal with increment 3
 cut -1"#;
 
-    assert_eq!(9, solve_part_one::<10>(input, 6));
+    assert_eq!(Ok(9), solve_part_one::<10>(input, 6));
+
+    let card = (0..10)
+        .find(|&card| (0..3).fold(card, |pos, _| solve_part_one::<10>(input, pos).unwrap()) == 6)
+        .unwrap();
+    assert_eq!(Ok(card), solve_part_two::<10>(input, 3, 6));
 }
@@ -1,6 +1,8 @@
 use super::intcode::{Interrupt, Machine, VecMem};
 use std::collections::VecDeque;
 
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> i64 {
     let mut machines = Vec::with_capacity(50);
     for id in 0..50 {
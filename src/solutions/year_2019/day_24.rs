@@ -1,7 +1,10 @@
 use crate::HashSet;
+use crate::grid::{Grid, Point};
+
+crate::solution!(part_one, part_two);
 
 pub fn part_one(input: &str) -> u64 {
-    let mut grid = Grid::new(input);
+    let mut grid = Eris::new(input);
     grid.find_dupicate()
 }
 
@@ -20,91 +23,41 @@ enum Cell {
     Bug,
 }
 
-struct Grid {
-    cells: Vec<Cell>,
-    width: usize,
-    height: usize,
+struct Eris {
+    cells: Grid<Cell>,
 }
 
-impl Grid {
+impl Eris {
     fn new(input: &str) -> Self {
-        let mut cells = Vec::with_capacity(input.len());
-        let mut width = 0;
-        let mut height = 0;
-
-        for line in input.trim().lines() {
-            if width < line.len() {
-                width = line.len();
-            }
-
-            height += 1;
-
-            for c in line.chars() {
-                let cell = match c {
-                    '.' => Cell::Empty,
-                    '#' => Cell::Bug,
-                    _ => unreachable!(),
-                };
-
-                cells.push(cell);
-            }
-        }
-
-        Self {
-            cells,
-            width,
-            height,
-        }
-    }
+        let cells = Grid::parse(input.trim(), |c| match c {
+            '.' => Some(Cell::Empty),
+            '#' => Some(Cell::Bug),
+            _ => None,
+        })
+        .expect("valid grid");
 
-    fn get(&self, (x, y): (i32, i32)) -> Option<Cell> {
-        if x < 0 || y < 0 {
-            return None;
-        }
-
-        let (x, y) = (x as usize, y as usize);
-
-        if x >= self.width || y >= self.height {
-            None
-        } else {
-            let idx = y * self.width + x;
-            self.cells.get(idx).copied()
-        }
+        Self { cells }
     }
 
-    fn count_neighbors(&self, (x, y): (i32, i32)) -> usize {
-        [(x + 1, y), (x, y + 1), (x - 1, y), (x, y - 1)]
-            .into_iter()
-            .filter_map(|p| self.get(p))
-            .filter(|&c| c == Cell::Bug)
+    fn count_neighbors(&self, p: Point) -> usize {
+        self.cells
+            .neighbors(p)
+            .filter(|&n| self.cells[n] == Cell::Bug)
             .count()
     }
 
     fn tick(&mut self) {
-        let mut new_cells = vec![Cell::Empty; self.cells.len()];
-
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let p = (x as i32, y as i32);
-
-                let count = self.count_neighbors(p);
-                let Some(cell) = self.get(p) else {
-                    continue;
-                };
-
-                let new_cell = match (cell, count) {
-                    (Cell::Empty, 1 | 2) => Cell::Bug,
-                    (Cell::Bug, 1) => Cell::Bug,
-                    (Cell::Bug, _) => Cell::Empty,
-                    _ => cell,
-                };
-
-                let idx = y * self.width + x;
-                new_cells[idx] = new_cell;
+        let new_cells = self.cells.points().map(|p| {
+            let cell = self.cells[p];
+            match (cell, self.count_neighbors(p)) {
+                (Cell::Empty, 1 | 2) => Cell::Bug,
+                (Cell::Bug, 1) => Cell::Bug,
+                (Cell::Bug, _) => Cell::Empty,
+                _ => cell,
             }
-        }
+        });
 
-        self.cells = new_cells;
+        self.cells = Grid::from_vec(self.cells.width(), self.cells.height(), new_cells.collect());
     }
 
     fn find_dupicate(&mut self) -> u64 {
@@ -125,7 +78,7 @@ impl Grid {
 
     fn score(&self) -> u64 {
         let mut score = 0;
-        for (idx, &cell) in self.cells.iter().enumerate() {
+        for (idx, &cell) in self.cells.cells().iter().enumerate() {
             let n = idx as u32;
 
             if cell == Cell::Bug {
@@ -1,5 +1,8 @@
 use super::intcode::{Interrupt, Machine};
 use std::collections::VecDeque;
+use crate::Answer;
+
+crate::solution!(part_one, part_two);
 
 pub fn part_one(input: &str) -> String {
     let mut machine = Machine::<i64, _>::new(input);
@@ -147,6 +150,6 @@ impl Move {
     }
 }
 
-pub fn part_two(_input: &str) -> &'static str {
-    "Almost there..."
+pub fn part_two(_input: &str) -> Answer {
+    Answer::NotApplicable
 }
@@ -1,5 +1,7 @@
 use crate::HashSet;
 
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> u64 {
     let values: HashSet<u64> = input
         .lines()
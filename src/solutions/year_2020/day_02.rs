@@ -1,4 +1,7 @@
-use super::{take_char, take_token, take_u64, take_until};
+use crate::ParseError;
+use crate::parse::{self, Parser};
+
+crate::solution!(part_one, part_two);
 
 #[derive(Debug)]
 struct PwLine<'a> {
@@ -9,26 +12,26 @@ struct PwLine<'a> {
 }
 
 impl<'a> PwLine<'a> {
-    fn from_str(s: &'a str) -> Option<Self> {
-        let (s, min) = take_u64(s)?;
-        let s = take_token(s, "-")?;
-        let (s, max) = take_u64(s)?;
-        let s = take_token(s, " ")?;
-        let (s, character) = take_char(s)?;
-        let s = take_token(s, ": ")?;
-        let (_s, password) = take_until(s, |_| false)?;
+    fn parse(p: &mut Parser<'a>) -> Result<Self, ParseError> {
+        let min = p.int()?;
+        p.token("-")?;
+        let max = p.int()?;
+        p.token(" ")?;
+        let character = p.char()?;
+        p.token(": ")?;
+        let password = p.take_while(|_| true);
 
-        Some(PwLine {
-            min: min as usize,
-            max: max as usize,
+        Ok(PwLine {
+            min,
+            max,
             character,
             password,
         })
     }
 }
 
-pub fn part_one(input: &str) -> u64 {
-    let lines: Vec<_> = input.lines().flat_map(PwLine::from_str).collect();
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
+    let lines = parse::lines(input, PwLine::parse)?;
 
     let mut valid = 0;
     for pw in lines {
@@ -45,11 +48,11 @@ pub fn part_one(input: &str) -> u64 {
         }
     }
 
-    valid
+    Ok(valid)
 }
 
-pub fn part_two(input: &str) -> u64 {
-    let lines: Vec<_> = input.lines().flat_map(PwLine::from_str).collect();
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
+    let lines = parse::lines(input, PwLine::parse)?;
 
     let mut valid = 0;
     for pw in lines {
@@ -70,7 +73,7 @@ pub fn part_two(input: &str) -> u64 {
         }
     }
 
-    valid
+    Ok(valid)
 }
 
 #[test]
@@ -80,6 +83,9 @@ fn test() {
 2-9 c: ccccccccc
 "#;
 
-    assert_eq!(2, part_one(input));
-    assert_eq!(1, part_two(input));
+    assert_eq!(Ok(2), part_one(input));
+    assert_eq!(Ok(1), part_two(input));
+
+    let err = part_one("1-3 a: abcde\n1-x b: cdefg\n").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 3));
 }
@@ -1,3 +1,5 @@
+crate::solution!(part_one, part_two);
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 enum Map {
     Empty,
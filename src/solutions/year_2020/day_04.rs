@@ -1,13 +1,15 @@
-use super::{take_char, take_u64};
-
 use crate::HashMap;
+use crate::parse::Parser;
+
+crate::solution!(part_one, part_two);
 
 pub fn part_one(input: &str) -> u64 {
-    let records = input.split("\n\n");
+    let records = Parser::new(input).sections();
 
     let passports: Vec<HashMap<_, _>> = records
         .map(|r| {
-            r.split_whitespace()
+            r.rest()
+                .split_whitespace()
                 .flat_map(|kv| {
                     let mut splits = kv.split(':');
                     let key = splits.next();
@@ -35,11 +37,12 @@ pub fn part_one(input: &str) -> u64 {
 }
 
 pub fn part_two(input: &str) -> u64 {
-    let records = input.split("\n\n");
+    let records = Parser::new(input).sections();
 
     let passports: Vec<HashMap<_, _>> = records
         .map(|r| {
-            r.split_whitespace()
+            r.rest()
+                .split_whitespace()
                 .flat_map(|kv| {
                     let mut splits = kv.split(':');
                     let key = splits.next();
@@ -108,10 +111,10 @@ impl PassportValidator for PassportYearValidator {
 struct PassportHeightValidator;
 impl PassportValidator for PassportHeightValidator {
     fn validate(&self, val: &str) -> bool {
-        let parsed = take_u64(val);
-        match parsed {
-            Some(("in", num)) if (59..=76).contains(&num) => true,
-            Some(("cm", num)) if (150..=193).contains(&num) => true,
+        let mut p = Parser::new(val);
+        match (p.int::<u64>(), p.rest()) {
+            (Ok(num), "in") => (59..=76).contains(&num),
+            (Ok(num), "cm") => (150..=193).contains(&num),
             _ => false,
         }
     }
@@ -120,12 +123,8 @@ impl PassportValidator for PassportHeightValidator {
 struct PassportHairColorValidator;
 impl PassportValidator for PassportHairColorValidator {
     fn validate(&self, val: &str) -> bool {
-        let parsed = take_char(val);
-        if let Some((s, '#')) = parsed {
-            s.len() == 6 && s.chars().all(|c| c.is_ascii_hexdigit())
-        } else {
-            false
-        }
+        let mut p = Parser::new(val);
+        p.try_token("#") && p.take_while(|c| c.is_ascii_hexdigit()).len() == 6 && p.is_empty()
     }
 }
 
@@ -1,5 +1,7 @@
 use crate::{HashMap, HashSet};
 
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> u64 {
     let bags: HashMap<_, _> = input
         .lines()
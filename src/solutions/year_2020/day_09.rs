@@ -1,5 +1,7 @@
 use std::collections::VecDeque;
 
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> u64 {
     solve_part_one::<25>(input)
 }
@@ -1,5 +1,7 @@
 use crate::{HashMap, HashSet};
 
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> u32 {
     let mut adapters: HashSet<u32> = input.lines().filter_map(|l| l.parse().ok()).collect();
     let built_in = adapters.iter().copied().max().unwrap_or(0) + 3;
@@ -1,15 +1,17 @@
-pub fn part_one(input: &str) -> u32 {
-    let mut lines = input.lines();
-    let depart: u32 = lines.next().unwrap().trim().parse().unwrap();
-    let buses = lines
-        .next()
-        .unwrap()
-        .split(',')
-        .filter(|c| *c != "x")
-        .filter_map(|n| n.parse().ok());
+use crate::ParseError;
+use crate::parse::Parser;
+
+crate::solution!(part_one, part_two);
+
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
+    let (depart, buses) = parse(input)?;
+    let buses = buses.into_iter().filter_map(|bus| match bus {
+        Bus::Id(n) => Some(n),
+        Bus::Any => None,
+    });
 
     let mut closest_bus = 0;
-    let mut closest_depart = u32::MAX;
+    let mut closest_depart = u64::MAX;
     for bus in buses {
         for n in 0.. {
             let time = n * bus;
@@ -24,24 +26,16 @@ pub fn part_one(input: &str) -> u32 {
         }
     }
 
-    (closest_depart - depart) * closest_bus
+    Ok((closest_depart - depart) * closest_bus)
 }
 
-pub fn part_two(input: &str) -> u64 {
-    let mut lines = input.lines();
-    let _depart = lines.next();
-    let buses = lines.next().unwrap().split(',').filter_map(|n| {
-        if n == "x" {
-            Some(Bus::Any)
-        } else {
-            n.parse().ok().map(Bus::Id)
-        }
-    });
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
+    let (_depart, buses) = parse(input)?;
 
     let mut bus_reqs = Vec::new();
     let mut max_bus = 0;
     let mut max_bus_offset = 0;
-    for (t_off, bus) in buses.enumerate() {
+    for (t_off, bus) in buses.into_iter().enumerate() {
         let t_off = t_off as u64;
         if let Bus::Id(n) = bus {
             if n > max_bus {
@@ -69,7 +63,7 @@ pub fn part_two(input: &str) -> u64 {
         }
 
         if matches == bus_reqs.len() {
-            return n;
+            return Ok(n);
         }
 
         if matches > match_count {
@@ -87,6 +81,23 @@ pub fn part_two(input: &str) -> u64 {
     }
 }
 
+fn parse(input: &str) -> Result<(u64, Vec<Bus>), ParseError> {
+    let mut p = Parser::new(input);
+    let depart = p.int()?;
+    p.skip_whitespace();
+    let buses = p.separated(",", |p| {
+        if p.try_token("x") {
+            Ok(Bus::Any)
+        } else {
+            p.int().map(Bus::Id)
+        }
+    })?;
+    p.skip_whitespace();
+    p.end()?;
+
+    Ok((depart, buses))
+}
+
 enum Bus {
     Id(u64),
     Any,
@@ -94,8 +105,8 @@ enum Bus {
 
 #[test]
 fn test() {
-    let run_a = |input, res| assert_eq!(part_one(input), res);
-    let run_b = |input, res| assert_eq!(part_two(input), res);
+    let run_a = |input, res| assert_eq!(part_one(input), Ok(res));
+    let run_b = |input, res| assert_eq!(part_two(input), Ok(res));
 
     let i = r#"939
 7,13,x,x,59,x,31,19"#;
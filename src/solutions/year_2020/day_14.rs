@@ -1,23 +1,18 @@
-use crate::HashMap;
+use crate::parse::{self, Parser};
+use crate::{HashMap, ParseError};
 
-pub fn part_one(input: &str) -> u64 {
-    let lines = input.lines();
+crate::solution!(part_one, part_two);
 
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
     let mut and_mask = 0;
     let mut or_mask = 0;
     let mut mem = HashMap::new();
 
-    for line in lines {
-        let mut split = line
-            .split(&['[', ']', '=', ' '][..])
-            .filter(|w| !w.trim().is_empty());
-        let op = split.next().unwrap();
-
-        match op {
-            "mask" => {
+    for instruction in parse::lines(input, Instruction::parse)? {
+        match instruction {
+            Instruction::Mask(mask) => {
                 and_mask = 0;
                 or_mask = 0;
-                let mask = split.last().unwrap();
                 for c in mask.chars() {
                     and_mask <<= 1;
                     or_mask <<= 1;
@@ -40,55 +35,58 @@ pub fn part_one(input: &str) -> u64 {
                     }
                 }
             }
-            "mem" => {
-                let addr: usize = split.next().unwrap().parse().unwrap();
-                let mut val: u64 = split.next().unwrap().parse().unwrap();
-
+            Instruction::Mem(addr, mut val) => {
                 val &= and_mask;
                 val |= or_mask;
 
                 mem.insert(addr, val);
             }
-            _ => (),
         }
     }
 
-    mem.values().sum()
+    Ok(mem.values().sum())
 }
 
-pub fn part_two(input: &str) -> u64 {
-    let lines = input.lines();
-
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
     let mut mem = HashMap::new();
 
     let mut mask = None;
-    for line in lines {
-        let mut split = line
-            .split(&['[', ']', '=', ' '][..])
-            .filter(|w| !w.trim().is_empty());
-
-        let op = split.next().unwrap();
-        match op {
-            "mask" => {
-                let mask_str = split.last().unwrap();
+    for instruction in parse::lines(input, Instruction::parse)? {
+        match instruction {
+            Instruction::Mask(mask_str) => {
                 mask = Some(Mask::new(mask_str));
             }
-            "mem" => {
-                let addr: u64 = split.next().unwrap().parse().unwrap();
-                let val: u64 = split.next().unwrap().parse().unwrap();
-
+            Instruction::Mem(addr, val) => {
                 if let Some(mask) = &mask {
                     for addr in mask.addrs(addr) {
                         mem.insert(addr, val);
                     }
                 }
             }
-
-            _ => (),
         }
     }
 
-    mem.values().sum()
+    Ok(mem.values().sum())
+}
+
+enum Instruction<'a> {
+    Mask(&'a str),
+    Mem(u64, u64),
+}
+
+impl<'a> Instruction<'a> {
+    fn parse(p: &mut Parser<'a>) -> Result<Self, ParseError> {
+        if p.try_token("mask = ") {
+            let mask = p.take_while(|c| matches!(c, 'X' | '0' | '1'));
+            Ok(Instruction::Mask(mask))
+        } else {
+            p.token("mem[")?;
+            let addr = p.int()?;
+            p.token("] = ")?;
+            let val = p.int()?;
+            Ok(Instruction::Mem(addr, val))
+        }
+    }
 }
 
 struct Mask {
@@ -157,8 +155,8 @@ impl Mask {
 
 #[test]
 fn test() {
-    let run_a = |input, res| assert_eq!(part_one(input), res);
-    let run_b = |input, res| assert_eq!(part_two(input), res);
+    let run_a = |input, res| assert_eq!(part_one(input), Ok(res));
+    let run_b = |input, res| assert_eq!(part_two(input), Ok(res));
 
     let i = r#"mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
 mem[8] = 11
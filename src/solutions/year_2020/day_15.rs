@@ -1,3 +1,5 @@
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> u32 {
     solution(input, 2020)
 }
@@ -1,150 +1,42 @@
-use crate::HashMap;
-
-pub fn part_one(input: &str) -> u64 {
-    let lines = input.lines();
-    let mut mode = 0;
-
-    let mut rules = Vec::new();
-    let mut error_num = 0;
-    for line in lines {
-        match mode {
-            0 => {
-                if line.is_empty() {
-                    mode += 1;
-                    continue;
-                }
-                let mut splits: Vec<_> = line.split_whitespace().collect();
-                splits.reverse();
-                let mut left_rules = splits[0].split('-');
-                let left_a: u64 = left_rules.next().unwrap().parse().unwrap();
-                let left_b: u64 = left_rules.next().unwrap().parse().unwrap();
-                let left = left_a..=left_b;
-
-                let mut right_rules = splits[2].split('-');
-                let right_a: u64 = right_rules.next().unwrap().parse().unwrap();
-                let right_b: u64 = right_rules.next().unwrap().parse().unwrap();
-                let right = right_a..=right_b;
-
-                rules.push((left, right));
-            }
-            1 => {
-                mode += 1;
-            }
-            2 => {
-                //my ticket
-                mode += 1;
-            }
-            3 => {
-                mode += 1;
-            }
-            4 => {
-                mode += 1;
-            }
-            5 => {
-                let values = line.trim().split(',').filter_map(|n| n.parse().ok());
-
-                'outer: for value in values {
-                    for r in rules.iter() {
-                        if r.0.contains(&value) {
-                            continue 'outer;
-                        }
-                        if r.1.contains(&value) {
-                            continue 'outer;
-                        }
-                    }
+use std::ops::RangeInclusive;
 
-                    error_num += value;
-                }
-            }
-            _ => panic!(),
-        }
-    }
+use crate::parse::Parser;
+use crate::{HashMap, ParseError};
 
-    error_num
-}
+crate::solution!(part_one, part_two);
 
-pub fn part_two(input: &str) -> u64 {
-    let lines = input.lines();
-    let mut mode = 0;
-
-    let mut rules = Vec::new();
-    let mut tickets = Vec::new();
-    let mut my_ticket: Vec<u64> = Vec::new();
-    for line in lines {
-        match mode {
-            0 => {
-                if line.is_empty() {
-                    mode += 1;
-                    continue;
-                }
-                let mut splits: Vec<_> = line.split_whitespace().collect();
-                splits.reverse();
-                let mut left_rules = splits[0].split('-');
-                let left_a: u64 = left_rules.next().unwrap().parse().unwrap();
-                let left_b: u64 = left_rules.next().unwrap().parse().unwrap();
-                let left = left_a..=left_b;
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
+    let notes = parse(input)?;
 
-                let mut right_rules = splits[2].split('-');
-                let right_a: u64 = right_rules.next().unwrap().parse().unwrap();
-                let right_b: u64 = right_rules.next().unwrap().parse().unwrap();
-                let right = right_a..=right_b;
+    let error_rate = notes
+        .nearby
+        .iter()
+        .flatten()
+        .filter(|value| !notes.rules.iter().any(|r| r.contains(value)))
+        .sum();
 
-                let depature = splits.last().unwrap() == &"departure";
+    Ok(error_rate)
+}
 
-                rules.push((left, right, depature, false));
-            }
-            1 => {
-                mode += 1;
-            }
-            2 => {
-                my_ticket = line
-                    .trim()
-                    .split(',')
-                    .filter_map(|n| n.parse().ok())
-                    .collect();
-
-                mode += 1;
-            }
-            3 => {
-                mode += 1;
-            }
-            4 => {
-                mode += 1;
-            }
-            5 => {
-                let values: Vec<_> = line
-                    .trim()
-                    .split(',')
-                    .filter_map(|n| n.parse().ok())
-                    .collect();
-
-                let mut valid = true;
-                'outer: for value in values.iter() {
-                    for r in rules.iter() {
-                        if r.0.contains(value) {
-                            continue 'outer;
-                        }
-                        if r.1.contains(value) {
-                            continue 'outer;
-                        }
-                    }
-                    valid = false;
-                }
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
+    let notes = parse(input)?;
 
-                if valid {
-                    tickets.push(values);
-                }
-            }
-            _ => panic!(),
-        }
-    }
+    let tickets: Vec<_> = notes
+        .nearby
+        .iter()
+        .filter(|t| {
+            t.iter()
+                .all(|value| notes.rules.iter().any(|r| r.contains(value)))
+        })
+        .collect();
 
+    let mut rules: Vec<_> = notes.rules.iter().map(|r| (r, false)).collect();
     let mut rule_map = HashMap::new();
-    let column_count = my_ticket.len();
+    let column_count = notes.my_ticket.len();
 
     loop {
-        for r in rules.iter_mut() {
-            if r.3 {
+        for (rule, mapped) in rules.iter_mut() {
+            if *mapped {
                 continue;
             }
             let mut possible_cols = Vec::new();
@@ -153,8 +45,7 @@ pub fn part_two(input: &str) -> u64 {
                     continue 'col;
                 }
                 for t in tickets.iter() {
-                    let v = t[col];
-                    if !r.0.contains(&v) && !r.1.contains(&v) {
+                    if !rule.contains(&t[col]) {
                         continue 'col;
                     }
                 }
@@ -163,8 +54,8 @@ pub fn part_two(input: &str) -> u64 {
 
             if possible_cols.len() == 1 {
                 let col = possible_cols.first().unwrap();
-                r.3 = true;
-                rule_map.insert(*col, r.clone());
+                *mapped = true;
+                rule_map.insert(*col, *rule);
             } else if possible_cols.is_empty() {
                 panic!("shiiit");
             }
@@ -176,17 +67,80 @@ pub fn part_two(input: &str) -> u64 {
 
     let mut total = 1;
     for (k, v) in rule_map {
-        if v.2 {
-            total *= my_ticket[k];
+        if v.name.starts_with("departure") {
+            total *= notes.my_ticket[k];
         }
     }
 
-    total
+    Ok(total)
+}
+
+struct Notes<'a> {
+    rules: Vec<Rule<'a>>,
+    my_ticket: Vec<u64>,
+    nearby: Vec<Vec<u64>>,
+}
+
+fn parse(input: &str) -> Result<Notes<'_>, ParseError> {
+    let mut p = Parser::new(input);
+    let rules = p.section().ok_or_else(|| p.error("expected the rules"))?;
+    let mut mine = p.section().ok_or_else(|| p.error("expected your ticket"))?;
+    let mut nearby = p
+        .section()
+        .ok_or_else(|| p.error("expected the nearby tickets"))?;
+
+    let rules = rules.parse_lines(Rule::parse)?;
+
+    mine.token("your ticket:")?;
+    mine.skip_whitespace();
+    let my_ticket = mine.separated(",", Parser::int)?;
+    mine.end()?;
+
+    nearby.token("nearby tickets:")?;
+    nearby.skip_whitespace();
+    let nearby = nearby.parse_lines(|p| p.separated(",", Parser::int))?;
+
+    Ok(Notes {
+        rules,
+        my_ticket,
+        nearby,
+    })
+}
+
+#[derive(Debug, Clone)]
+struct Rule<'a> {
+    name: &'a str,
+    left: RangeInclusive<u64>,
+    right: RangeInclusive<u64>,
+}
+
+impl<'a> Rule<'a> {
+    fn parse(p: &mut Parser<'a>) -> Result<Self, ParseError> {
+        let name = p.take_until(|c| c == ':');
+        p.token(": ")?;
+        let left = parse_range(p)?;
+        p.token(" or ")?;
+        let right = parse_range(p)?;
+
+        Ok(Rule { name, left, right })
+    }
+
+    fn contains(&self, value: &u64) -> bool {
+        self.left.contains(value) || self.right.contains(value)
+    }
+}
+
+fn parse_range(p: &mut Parser) -> Result<RangeInclusive<u64>, ParseError> {
+    let start = p.int()?;
+    p.token("-")?;
+    let end = p.int()?;
+
+    Ok(start..=end)
 }
 
 #[test]
 fn test() {
-    let run_a = |input, res| assert_eq!(part_one(input), res);
+    let run_a = |input, res| assert_eq!(part_one(input), Ok(res));
 
     let input = r#"class: 1-3 or 5-7
 row: 6-11 or 33-44
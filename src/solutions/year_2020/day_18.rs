@@ -1,3 +1,5 @@
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> u64 {
     let mut result = 0;
     for line in input.trim().lines() {
@@ -1,34 +1,50 @@
-use crate::HashMap;
-
-pub fn part_one(input: &str) -> usize {
-    let mut lines = input.trim().lines();
-    let mut rules = RuleCollection::new();
-    for line in lines.by_ref() {
-        if line.is_empty() {
-            break;
-        }
-        rules.insert(line);
-    }
+use crate::parse::Parser;
+use crate::{HashMap, ParseError};
+
+crate::solution!(part_one, part_two);
+
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    let (rules, messages) = parse(input)?;
 
-    lines.filter(|l| rules.is_non_recursive_match(l)).count()
+    Ok(messages
+        .into_iter()
+        .filter(|l| rules.is_non_recursive_match(l))
+        .count())
 }
 
-pub fn part_two(input: &str) -> usize {
-    let mut lines = input.trim().lines();
-    let mut rules = RuleCollection::new();
-    for line in lines.by_ref() {
-        if line.is_empty() {
-            break;
-        }
-        rules.insert(line);
-    }
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    let (mut rules, messages) = parse(input)?;
 
-    rules.insert("8: 42 | 42 8");
-    rules.insert("11: 42 31 | 42 11 31");
+    rules.insert(Rule {
+        id: 8,
+        matcher: Match::Pair(vec![42], vec![42, 8]),
+    });
+    rules.insert(Rule {
+        id: 11,
+        matcher: Match::Pair(vec![42, 31], vec![42, 11, 31]),
+    });
 
     rules.compile();
 
-    lines.filter(|l| rules.is_cyk_match(l)).count()
+    Ok(messages
+        .into_iter()
+        .filter(|l| rules.is_cyk_match(l))
+        .count())
+}
+
+fn parse(input: &str) -> Result<(RuleCollection, Vec<&str>), ParseError> {
+    let mut p = Parser::new(input);
+    let rules = p.section().ok_or_else(|| p.error("expected the rules"))?;
+    let messages = p
+        .section()
+        .ok_or_else(|| p.error("expected the messages"))?;
+
+    let mut collection = RuleCollection::new();
+    for rule in rules.parse_lines(Rule::parse)? {
+        collection.insert(rule);
+    }
+
+    Ok((collection, messages.lines().map(|l| l.rest()).collect()))
 }
 
 struct Rule {
@@ -43,82 +59,37 @@ enum Match {
     Char(char),
 }
 
-impl std::str::FromStr for Rule {
-    type Err = std::convert::Infallible;
+impl Rule {
+    fn parse(p: &mut Parser) -> Result<Self, ParseError> {
+        let id = p.int()?;
+        p.token(":")?;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        #[derive(Eq, PartialEq)]
-        enum Stage {
-            Id,
-            Char,
-            MatchOne,
-            MatchTwo,
-        }
-
-        let mut stage = Stage::Id;
-        let mut num = None;
-        let mut id = 0;
-        let mut match_one = Vec::new();
-        let mut match_two = Vec::new();
-        let mut match_char = '0';
-        for c in s.trim().chars() {
-            match c {
-                '|' => {
-                    stage = Stage::MatchTwo;
-                }
-                ':' => {
-                    id = num.unwrap();
-                    num = None;
-                    stage = Stage::MatchOne;
-                }
-                '"' => {
-                    stage = Stage::Char;
-                }
-                c if c.is_ascii_digit() && stage != Stage::Char => {
-                    num = Some((num.unwrap_or(0) * 10) + (c as u8 - b'0') as CnfRuleId);
-                }
-                c if c.is_whitespace() => {
-                    if let Some(next) = num {
-                        match stage {
-                            Stage::MatchOne => match_one.push(next),
-                            Stage::MatchTwo => match_two.push(next),
-                            _ => unreachable!("invalid rule"),
-                        }
-                        num = None;
-                    }
-                }
-                c => {
-                    match_char = c;
-                }
+        let matcher = if p.try_token(" \"") {
+            let c = p.char()?;
+            p.token("\"")?;
+            Match::Char(c)
+        } else {
+            let match_one = p.many(rule_id);
+            if match_one.is_empty() {
+                return Err(p.error("expected a rule"));
             }
-        }
 
-        if let Some(next) = num {
-            match stage {
-                Stage::MatchOne => match_one.push(next),
-                Stage::MatchTwo => match_two.push(next),
-                _ => unreachable!("invalid rule"),
+            if p.try_token(" |") {
+                Match::Pair(match_one, p.many(rule_id))
+            } else {
+                Match::Simple(match_one)
             }
-        }
-
-        let res = match (match_one.len(), match_two.len()) {
-            (0, 0) => Rule {
-                id,
-                matcher: Match::Char(match_char),
-            },
-            (_, 0) => Rule {
-                id,
-                matcher: Match::Simple(match_one),
-            },
-            (_, _) => Rule {
-                id,
-                matcher: Match::Pair(match_one, match_two),
-            },
         };
-        Ok(res)
+
+        Ok(Rule { id, matcher })
     }
 }
 
+fn rule_id(p: &mut Parser) -> Result<CnfRuleId, ParseError> {
+    p.skip_whitespace();
+    p.int()
+}
+
 impl std::fmt::Display for Rule {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match &self.matcher {
@@ -185,8 +156,7 @@ impl RuleCollection {
         }
     }
 
-    fn insert<S: AsRef<str>>(&mut self, rule: S) {
-        let rule: Rule = rule.as_ref().trim().parse().unwrap();
+    fn insert(&mut self, rule: Rule) {
         self.map.insert(rule.id, rule);
     }
 
@@ -398,8 +368,8 @@ impl RuleCollection {
 
 #[test]
 fn test() {
-    let run_a = |input, res| assert_eq!(part_one(input), res);
-    let run_b = |input, res| assert_eq!(part_two(input), res);
+    let run_a = |input, res| assert_eq!(part_one(input), Ok(res));
+    let run_b = |input, res| assert_eq!(part_two(input), Ok(res));
 
     let i = r#"0: 4 1 5
 1: 2 3 | 3 2
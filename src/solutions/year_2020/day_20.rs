@@ -1,6 +1,8 @@
 use crate::HashMap;
 use std::collections::VecDeque;
 
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> u64 {
     let lines = input.trim().lines();
 
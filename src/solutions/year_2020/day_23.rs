@@ -1,3 +1,5 @@
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> usize {
     let cups = input.trim().chars().map(|n| (n as u8 - b'0') as usize);
 
@@ -1,3 +1,7 @@
+use crate::Answer;
+
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> u64 {
     let mut input = input.trim().lines().filter_map(|n| n.parse().ok());
     let card_pub_key: u64 = input.next().unwrap();
@@ -29,8 +33,8 @@ pub fn part_one(input: &str) -> u64 {
     result
 }
 
-pub fn part_two(_input: &str) -> &'static str {
-    "I did it!!!!"
+pub fn part_two(_input: &str) -> Answer {
+    Answer::NotApplicable
 }
 
 #[test]
@@ -1,3 +1,5 @@
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> usize {
     let lines: Vec<u64> = input
         .trim()
@@ -1,40 +1,32 @@
+use crate::ParseError;
+use crate::parse::{self, Parser};
+
+crate::solution!(part_one, part_two);
+
 enum SubCommand {
     Forward(u64),
     Up(u64),
     Down(u64),
 }
 
-struct SubCommandErr;
-
-impl std::str::FromStr for SubCommand {
-    type Err = SubCommandErr;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts = s
-            .split_once(' ')
-            .and_then(|(dir, dist)| Some(dir).zip(dist.parse().ok()));
-
-        if let Some((direction, distance)) = parts {
-            let res = match direction {
-                "forward" => SubCommand::Forward(distance),
-                "down" => SubCommand::Down(distance),
-                "up" => SubCommand::Up(distance),
-                _ => return Err(SubCommandErr),
-            };
+impl SubCommand {
+    fn parse(p: &mut Parser) -> Result<Self, ParseError> {
+        let start = *p;
+        let direction = p.take_until(|c| c == ' ');
+        p.token(" ")?;
+        let distance = p.int()?;
 
-            Ok(res)
-        } else {
-            Err(SubCommandErr)
+        match direction {
+            "forward" => Ok(SubCommand::Forward(distance)),
+            "down" => Ok(SubCommand::Down(distance)),
+            "up" => Ok(SubCommand::Up(distance)),
+            _ => Err(start.error(format!("unknown direction '{}'", direction))),
         }
     }
 }
 
-pub fn part_one(input: &str) -> u64 {
-    let lines = input
-        .trim()
-        .lines()
-        .map(|s| s.parse::<SubCommand>())
-        .filter_map(Result::ok);
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
+    let lines = parse::lines(input, SubCommand::parse)?;
 
     let mut horizontal = 0;
     let mut depth = 0;
@@ -47,15 +39,11 @@ pub fn part_one(input: &str) -> u64 {
         }
     }
 
-    horizontal * depth
+    Ok(horizontal * depth)
 }
 
-pub fn part_two(input: &str) -> u64 {
-    let lines = input
-        .trim()
-        .lines()
-        .map(|s| s.parse::<SubCommand>())
-        .filter_map(Result::ok);
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
+    let lines = parse::lines(input, SubCommand::parse)?;
 
     let mut horizontal = 0;
     let mut depth = 0;
@@ -72,7 +60,7 @@ pub fn part_two(input: &str) -> u64 {
         }
     }
 
-    horizontal * depth
+    Ok(horizontal * depth)
 }
 
 #[test]
@@ -85,6 +73,6 @@ down 8
 forward 2
 "#;
 
-    assert_eq!(150, part_one(input));
-    assert_eq!(900, part_two(input));
+    assert_eq!(Ok(150), part_one(input));
+    assert_eq!(Ok(900), part_two(input));
 }
@@ -1,3 +1,8 @@
+use crate::ParseError;
+use crate::parse::Parser;
+
+crate::solution!(part_one, part_two);
+
 #[derive(Copy, Clone, PartialEq, Eq)]
 enum Cell {
     Marked,
@@ -19,21 +24,18 @@ struct Board {
 }
 
 impl Board {
-    fn new(data: &str) -> Self {
-        let nums = data
-            .split_whitespace()
-            .filter_map(|n| n.parse().ok())
-            .map(Cell::Open);
-
+    fn parse(mut p: Parser) -> Result<Self, ParseError> {
         let mut cells = [Cell::Marked; 25];
-        for (cell, num) in cells.iter_mut().zip(nums) {
-            *cell = num;
+        for cell in cells.iter_mut() {
+            p.skip_whitespace();
+            *cell = Cell::Open(p.int()?);
         }
+        p.end()?;
 
-        Self {
+        Ok(Self {
             cells,
             winner: None,
-        }
+        })
     }
 
     fn mark(&mut self, value: u8) {
@@ -95,23 +97,15 @@ impl Board {
     }
 }
 
-pub fn part_one(input: &str) -> u64 {
-    let mut sections = input.trim().split("\n\n");
-
-    let draws = sections
-        .next()
-        .unwrap()
-        .split(',')
-        .filter_map(|s| s.parse::<u8>().ok());
-
-    let mut boards: Vec<_> = sections.map(Board::new).collect();
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
+    let (draws, mut boards) = parse(input)?;
 
     for draw in draws {
         for board in boards.iter_mut() {
             board.mark(draw);
 
             if board.winner() {
-                return board.score();
+                return Ok(board.score());
             }
         }
     }
@@ -119,16 +113,8 @@ pub fn part_one(input: &str) -> u64 {
     panic!("ran out of draws")
 }
 
-pub fn part_two(input: &str) -> u64 {
-    let mut sections = input.trim().split("\n\n");
-
-    let draws = sections
-        .next()
-        .unwrap()
-        .split(',')
-        .filter_map(|s| s.parse::<u8>().ok());
-
-    let mut boards: Vec<_> = sections.map(Board::new).collect();
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
+    let (draws, mut boards) = parse(input)?;
 
     for draw in draws {
         for board in boards.iter_mut() {
@@ -136,7 +122,7 @@ pub fn part_two(input: &str) -> u64 {
         }
 
         if boards.len() == 1 && boards[0].winner() {
-            return boards[0].score();
+            return Ok(boards[0].score());
         }
 
         boards.retain(|b| !b.winner());
@@ -145,6 +131,17 @@ pub fn part_two(input: &str) -> u64 {
     panic!("ran out of draws")
 }
 
+fn parse(input: &str) -> Result<(Vec<u8>, Vec<Board>), ParseError> {
+    let mut p = Parser::new(input);
+    let mut draws = p.section().ok_or_else(|| p.error("expected the draws"))?;
+    let numbers = draws.separated(",", Parser::int)?;
+    draws.end()?;
+
+    let boards = p.sections().map(Board::parse).collect::<Result<_, _>>()?;
+
+    Ok((numbers, boards))
+}
+
 #[test]
 fn test() {
     let input = r#"7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
@@ -168,6 +165,6 @@ fn test() {
  2  0 12  3  7
 "#;
 
-    assert_eq!(4512, part_one(input));
-    assert_eq!(1924, part_two(input));
+    assert_eq!(Ok(4512), part_one(input));
+    assert_eq!(Ok(1924), part_two(input));
 }
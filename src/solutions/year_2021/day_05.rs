@@ -1,98 +1,111 @@
-pub fn part_one(input: &str) -> usize {
-    let text = input.trim().lines();
+use crate::ParseError;
+use crate::geometry::Point2;
+use crate::grid::{Grid, Point};
+use crate::parse::{self, Parser};
 
+crate::solution!(part_one, part_two);
+
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
     let mut max_x = 0;
     let mut max_y = 0;
     let mut lines = Vec::new();
-    for line in text {
-        if let Some((from, to)) = line.split_once(" -> ") {
-            let start = from
-                .split_once(",")
-                .and_then(|(a, b)| a.parse::<i64>().ok().zip(b.parse::<i64>().ok()));
-            let end = to
-                .split_once(",")
-                .and_then(|(a, b)| a.parse::<i64>().ok().zip(b.parse::<i64>().ok()));
-
-            if let Some(((x0, y0), (x1, y1))) = start.zip(end) {
-                max_x = x0.max(x1).max(max_x);
-                max_y = y0.max(y1).max(max_y);
-                if x0 == x1 {
-                    lines.push(Segment::Vertical {
-                        column: x0,
-                        start: y0.min(y1),
-                        end: y0.max(y1),
-                    });
-                } else if y0 == y1 {
-                    lines.push(Segment::Horizontal {
-                        row: y0,
-                        start: x0.min(x1),
-                        end: x0.max(x1),
-                    });
-                }
-            }
+    for Line { start, end } in parse::lines(input, parse_line)? {
+        let (x0, y0, x1, y1) = (start.x, start.y, end.x, end.y);
+        max_x = x0.max(x1).max(max_x);
+        max_y = y0.max(y1).max(max_y);
+        if x0 == x1 {
+            lines.push(Segment::Vertical {
+                column: x0,
+                start: y0.min(y1),
+                end: y0.max(y1),
+            });
+        } else if y0 == y1 {
+            lines.push(Segment::Horizontal {
+                row: y0,
+                start: x0.min(x1),
+                end: x0.max(x1),
+            });
         }
     }
 
-    solver(lines.into_iter(), max_x as usize + 1, max_y as usize + 1)
+    Ok(solver(
+        lines.into_iter(),
+        max_x as usize + 1,
+        max_y as usize + 1,
+    ))
 }
 
-pub fn part_two(input: &str) -> usize {
-    let text = input.trim().lines();
-
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
     let mut max_x = 0;
     let mut max_y = 0;
     let mut lines = Vec::new();
-    for line in text {
-        if let Some((from, to)) = line.split_once(" -> ") {
-            let start = from
-                .split_once(",")
-                .and_then(|(a, b)| a.parse::<i64>().ok().zip(b.parse::<i64>().ok()));
-            let end = to
-                .split_once(",")
-                .and_then(|(a, b)| a.parse::<i64>().ok().zip(b.parse::<i64>().ok()));
-
-            if let Some(((x0, y0), (x1, y1))) = start.zip(end) {
-                max_x = x0.max(x1).max(max_x);
-                max_y = y0.max(y1).max(max_y);
-                if x0 == x1 {
-                    lines.push(Segment::Vertical {
-                        column: x0,
-                        start: y0.min(y1),
-                        end: y0.max(y1),
-                    });
-                } else if y0 == y1 {
-                    lines.push(Segment::Horizontal {
-                        row: y0,
-                        start: x0.min(x1),
-                        end: x0.max(x1),
-                    });
-                } else {
-                    if x1 > x0 {
-                        lines.push(Segment::Diagonal { x0, y0, x1, y1 });
-                    } else {
-                        lines.push(Segment::Diagonal {
-                            x0: x1,
-                            y0: y1,
-                            x1: x0,
-                            y1: y0,
-                        });
-                    }
-                }
+    for Line { start, end } in parse::lines(input, parse_line)? {
+        let (x0, y0, x1, y1) = (start.x, start.y, end.x, end.y);
+        max_x = x0.max(x1).max(max_x);
+        max_y = y0.max(y1).max(max_y);
+        if x0 == x1 {
+            lines.push(Segment::Vertical {
+                column: x0,
+                start: y0.min(y1),
+                end: y0.max(y1),
+            });
+        } else if y0 == y1 {
+            lines.push(Segment::Horizontal {
+                row: y0,
+                start: x0.min(x1),
+                end: x0.max(x1),
+            });
+        } else {
+            if x1 > x0 {
+                lines.push(Segment::Diagonal { x0, y0, x1, y1 });
+            } else {
+                lines.push(Segment::Diagonal {
+                    x0: x1,
+                    y0: y1,
+                    x1: x0,
+                    y1: y0,
+                });
             }
         }
     }
 
-    solver(lines.into_iter(), max_x as usize + 1, max_y as usize + 1)
+    Ok(solver(
+        lines.into_iter(),
+        max_x as usize + 1,
+        max_y as usize + 1,
+    ))
+}
+
+struct Line {
+    start: Point2<i64>,
+    end: Point2<i64>,
+}
+
+fn parse_line(p: &mut Parser) -> Result<Line, ParseError> {
+    let start = parse_point(p)?;
+    p.token(" -> ")?;
+    let end = parse_point(p)?;
+
+    Ok(Line { start, end })
+}
+
+fn parse_point(p: &mut Parser) -> Result<Point2<i64>, ParseError> {
+    let x = p.int()?;
+    p.token(",")?;
+    let y = p.int()?;
+
+    Ok(Point2::new(x, y))
 }
 
 fn solver<I: Iterator<Item = Segment>>(iter: I, width: usize, height: usize) -> usize {
-    let mut map = CellMap::new(width, height);
+    let mut map = Grid::new(width, height, 0u8);
 
     let mut count = 0;
     for line in iter {
-        for point in line.iter() {
-            if let Some(cell) = map.increment(point) {
-                if cell == 2 {
+        for (x, y) in line.iter() {
+            if let Some(cell) = map.get_mut(Point::new(x as i32, y as i32)) {
+                *cell = cell.saturating_add(1);
+                if *cell == 2 {
                     count += 1;
                 }
             }
@@ -102,31 +115,6 @@ fn solver<I: Iterator<Item = Segment>>(iter: I, width: usize, height: usize) ->
     count
 }
 
-struct CellMap {
-    cells: Vec<u8>,
-    stride: usize,
-}
-
-impl CellMap {
-    fn new(width: usize, height: usize) -> Self {
-        let cells = vec![0; width * height];
-        let stride = width;
-
-        Self { cells, stride }
-    }
-
-    fn increment(&mut self, (x, y): (i64, i64)) -> Option<u8> {
-        let x = x as usize;
-        let y = y as usize;
-        let idx = (y * self.stride) + x;
-        let mut cell = self.cells.get_mut(idx);
-        if let Some(cell) = cell.as_mut() {
-            **cell = cell.saturating_add(1);
-        }
-        cell.copied()
-    }
-}
-
 #[derive(Debug, Copy, Clone)]
 enum Segment {
     Horizontal { row: i64, start: i64, end: i64 },
@@ -204,6 +192,6 @@ fn test() {
 0,0 -> 8,8
 5,5 -> 8,2"#;
 
-    assert_eq!(5, part_one(input));
-    assert_eq!(12, part_two(input));
+    assert_eq!(Ok(5), part_one(input));
+    assert_eq!(Ok(12), part_two(input));
 }
@@ -1,3 +1,5 @@
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> i64 {
     let crabs: Vec<_> = input
         .trim()
@@ -1,5 +1,7 @@
 use crate::HashMap;
 
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> u64 {
     let nodes = Nodes::new(input.lines().filter_map(|l| l.split_once('-')));
 
@@ -1,89 +1,42 @@
-use crate::HashSet;
+use crate::geometry::Point2;
+use crate::parse::Parser;
+use crate::{HashSet, ParseError};
 
-pub fn part_one(input: &str) -> usize {
-    let mut lines = input.trim().split("\n\n");
-    let dots = lines.next().unwrap().trim().lines();
-    let mut folds = lines.next().unwrap().trim().lines();
+crate::solution!(part_one, part_two);
 
-    let dots: HashSet<_> = dots
-        .filter_map(|dot| {
-            dot.split_once(",")
-                .and_then(|(x, y)| x.parse::<i64>().ok().zip(y.parse::<i64>().ok()))
-        })
-        .collect();
-
-    let mut working_dots = HashSet::new();
+type Point = Point2<i64>;
 
-    if let Some(fold) = folds.next() {
-        if let Some((front, coord)) = fold.split_once('=') {
-            if let Ok(coord) = coord.parse::<i64>() {
-                let y_flip = front.ends_with('y');
-
-                for dot in dots {
-                    if y_flip && dot.1 > coord {
-                        let new_coord = coord - (dot.1 - coord);
-                        working_dots.insert((dot.0, new_coord));
-                    } else if !y_flip && dot.0 > coord {
-                        let new_coord = coord - (dot.0 - coord);
-                        working_dots.insert((new_coord, dot.1));
-                    } else {
-                        working_dots.insert(dot);
-                    }
-                }
-            }
-        }
-    }
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    let (dots, folds) = parse(input)?;
+    let Some(fold) = folds.first() else {
+        return Ok(0);
+    };
 
-    working_dots.len()
+    let dots: HashSet<_> = dots.into_iter().map(|dot| fold.apply(dot)).collect();
+    Ok(dots.len())
 }
 
-pub fn part_two(input: &str) -> String {
-    let mut lines = input.trim().split("\n\n");
-    let dots = lines.next().unwrap().trim().lines();
-    let folds = lines.next().unwrap().trim().lines();
-
-    let mut dots: HashSet<_> = dots
-        .filter_map(|dot| {
-            dot.split_once(",")
-                .and_then(|(x, y)| x.parse::<i64>().ok().zip(y.parse::<i64>().ok()))
-        })
-        .collect();
+pub fn part_two(input: &str) -> Result<String, ParseError> {
+    let (mut dots, folds) = parse(input)?;
     let mut working_dots = HashSet::new();
 
     for fold in folds {
-        if let Some((front, coord)) = fold.split_once('=') {
-            if let Ok(coord) = coord.parse::<i64>() {
-                let y_flip = front.ends_with('y');
-
-                for dot in dots.drain() {
-                    if y_flip && dot.1 > coord {
-                        let new_coord = coord - (dot.1 - coord);
-                        working_dots.insert((dot.0, new_coord));
-                    } else if !y_flip && dot.0 > coord {
-                        let new_coord = coord - (dot.0 - coord);
-                        working_dots.insert((new_coord, dot.1));
-                    } else {
-                        working_dots.insert(dot);
-                    }
-                }
-
-                std::mem::swap(&mut dots, &mut working_dots);
-            }
-        }
+        working_dots.extend(dots.drain().map(|dot| fold.apply(dot)));
+        std::mem::swap(&mut dots, &mut working_dots);
     }
 
     let mut max_y = 0;
     let mut max_x = 0;
 
-    for (x, y) in dots.iter() {
-        max_y = max_y.max(*y + 1);
-        max_x = max_x.max(*x + 1);
+    for dot in dots.iter() {
+        max_y = max_y.max(dot.y + 1);
+        max_x = max_x.max(dot.x + 1);
     }
 
     let mut result = String::with_capacity((max_x * max_y) as usize * 2);
     for y in 0..max_y {
         for x in 0..max_x {
-            if dots.contains(&(x, y)) {
+            if dots.contains(&Point::new(x, y)) {
                 result.push_str("##");
             } else {
                 result.push_str("  ");
@@ -92,7 +45,50 @@ pub fn part_two(input: &str) -> String {
         result.push('\n');
     }
 
-    result
+    Ok(result)
+}
+
+struct Fold {
+    along_y: bool,
+    line: i64,
+}
+
+impl Fold {
+    fn apply(&self, dot: Point) -> Point {
+        if self.along_y && dot.y > self.line {
+            Point::new(dot.x, self.line - (dot.y - self.line))
+        } else if !self.along_y && dot.x > self.line {
+            Point::new(self.line - (dot.x - self.line), dot.y)
+        } else {
+            dot
+        }
+    }
+}
+
+fn parse(input: &str) -> Result<(HashSet<Point>, Vec<Fold>), ParseError> {
+    let mut p = Parser::new(input);
+    let dots = p.section().ok_or_else(|| p.error("expected the dots"))?;
+    let folds = p.section().ok_or_else(|| p.error("expected the folds"))?;
+
+    let dots = dots.parse_lines(|p| {
+        let x = p.int()?;
+        p.token(",")?;
+        Ok(Point::new(x, p.int()?))
+    })?;
+
+    let folds = folds.parse_lines(|p| {
+        p.token("fold along ")?;
+        let along_y = p.try_token("y=");
+        if !along_y {
+            p.token("x=")?;
+        }
+        Ok(Fold {
+            along_y,
+            line: p.int()?,
+        })
+    })?;
+
+    Ok((dots.into_iter().collect(), folds))
 }
 
 #[test]
@@ -119,5 +115,5 @@ fn test() {
 fold along y=7
 fold along x=5"#;
 
-    assert_eq!(17, part_one(input));
+    assert_eq!(Ok(17), part_one(input));
 }
@@ -1,5 +1,7 @@
 #![allow(dead_code, unused_variables)]
 
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> i64 {
     let result: Option<Expression> =
         input
@@ -1,50 +1,37 @@
-pub fn part_one(input: &str) -> u64 {
-    let lines = input.trim().lines();
+use crate::ParseError;
+use crate::parse::Parser;
 
-    let mut max = 0;
-    let mut cur = 0;
+crate::solution!(part_one, part_two);
 
-    for line in lines.chain(std::iter::once("")) {
-        if line.is_empty() {
-            if cur > max {
-                max = cur;
-            }
-
-            cur = 0;
-        } else {
-            cur += line.parse::<u64>().unwrap();
-        }
-    }
-
-    max
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
+    Ok(parse(input)?.into_iter().max().unwrap_or(0))
 }
 
-pub fn part_two(input: &str) -> u64 {
-    let lines = input.trim().lines();
-
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
     let mut max = [0; 3];
-    let mut cur = 0;
-
-    for line in lines.chain(std::iter::once("")) {
-        if line.is_empty() {
-            if cur > max[0] {
-                max[2] = max[1];
-                max[1] = max[0];
-                max[0] = cur;
-            } else if cur > max[1] {
-                max[2] = max[1];
-                max[1] = cur;
-            } else if cur > max[2] {
-                max[2] = cur;
-            }
 
-            cur = 0;
-        } else {
-            cur += line.parse::<u64>().unwrap();
+    for cur in parse(input)? {
+        if cur > max[0] {
+            max[2] = max[1];
+            max[1] = max[0];
+            max[0] = cur;
+        } else if cur > max[1] {
+            max[2] = max[1];
+            max[1] = cur;
+        } else if cur > max[2] {
+            max[2] = cur;
         }
     }
 
-    max.iter().sum()
+    Ok(max.iter().sum())
+}
+
+// total calories carried by each elf
+fn parse(input: &str) -> Result<Vec<u64>, ParseError> {
+    Parser::new(input)
+        .sections()
+        .map(|elf| Ok(elf.parse_lines(Parser::int::<u64>)?.into_iter().sum()))
+        .collect()
 }
 
 #[test]
@@ -64,6 +51,6 @@ fn test() {
 
 10000"#;
 
-    assert_eq!(24000, part_one(input));
-    assert_eq!(45000, part_two(input));
+    assert_eq!(Ok(24000), part_one(input));
+    assert_eq!(Ok(45000), part_two(input));
 }
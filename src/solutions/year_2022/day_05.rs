@@ -1,28 +1,35 @@
-use crate::HashMap;
+use crate::parse::Parser;
+use crate::{HashMap, ParseError};
 use std::collections::VecDeque;
 
-pub fn part_one(input: &str) -> String {
-    let mut lines = input.lines();
+crate::solution!(part_one, part_two);
 
-    let mut stacks = Stacks::new(&mut lines);
+pub fn part_one(input: &str) -> Result<String, ParseError> {
+    let (mut stacks, moves) = parse(input)?;
 
-    for move_crate in lines.filter_map(|l| l.parse::<Move>().ok()) {
+    for move_crate in moves {
         stacks.perform_move(move_crate);
     }
 
-    stacks.top_crates()
+    Ok(stacks.top_crates())
 }
 
-pub fn part_two(input: &str) -> String {
-    let mut lines = input.lines();
+pub fn part_two(input: &str) -> Result<String, ParseError> {
+    let (mut stacks, moves) = parse(input)?;
 
-    let mut stacks = Stacks::new(&mut lines);
-
-    for move_crate in lines.filter_map(|l| l.parse::<Move>().ok()) {
+    for move_crate in moves {
         stacks.perform_upgraded_move(move_crate);
     }
 
-    stacks.top_crates()
+    Ok(stacks.top_crates())
+}
+
+fn parse(input: &str) -> Result<(Stacks, Vec<Move>), ParseError> {
+    let mut p = Parser::new(input);
+    let stacks = p.section().ok_or_else(|| p.error("expected the stacks"))?;
+    let moves = p.section().ok_or_else(|| p.error("expected the moves"))?;
+
+    Ok((Stacks::new(stacks.rest()), moves.parse_lines(Move::parse)?))
 }
 
 struct Stacks {
@@ -31,14 +38,10 @@ struct Stacks {
 }
 
 impl Stacks {
-    fn new<'a, I: Iterator<Item = &'a str>>(lines: &mut I) -> Stacks {
+    fn new(drawing: &str) -> Stacks {
         let mut stacks = HashMap::new();
         let mut names = HashMap::new();
-        for line in lines {
-            if line.trim().is_empty() {
-                break;
-            }
-
+        for line in drawing.lines() {
             for (idx, c) in line
                 .chars()
                 .enumerate()
@@ -118,22 +121,14 @@ struct Move {
     count: usize,
 }
 
-struct MoveParseErr;
-
-impl std::str::FromStr for Move {
-    type Err = MoveParseErr;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts: [_; 3] = s
-            .split(|c: char| !c.is_ascii_digit())
-            .filter(|p| !p.is_empty())
-            .collect::<Vec<_>>()
-            .try_into()
-            .map_err(|_| MoveParseErr)?;
-
-        let count = parts[0].parse::<usize>().map_err(|_| MoveParseErr)?;
-        let source = parts[1].chars().next().ok_or(MoveParseErr)?;
-        let destination = parts[2].chars().next().ok_or(MoveParseErr)?;
+impl Move {
+    fn parse(p: &mut Parser) -> Result<Self, ParseError> {
+        p.token("move ")?;
+        let count = p.int()?;
+        p.token(" from ")?;
+        let source = p.char()?;
+        p.token(" to ")?;
+        let destination = p.char()?;
 
         Ok(Move {
             count,
@@ -155,6 +150,6 @@ move 3 from 1 to 3
 move 2 from 2 to 1
 move 1 from 1 to 2"#;
 
-    assert_eq!("CMZ".to_string(), part_one(input));
-    assert_eq!("MCD", part_two(input));
+    assert_eq!(Ok("CMZ".to_string()), part_one(input));
+    assert_eq!(Ok("MCD".to_string()), part_two(input));
 }
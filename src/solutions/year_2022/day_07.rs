@@ -1,7 +1,10 @@
-use crate::HashMap;
+use crate::parse::{self, Parser};
+use crate::{HashMap, ParseError};
 
-pub fn part_one(input: &str) -> usize {
-    let dir_size = parse_directory_sizes(input);
+crate::solution!(part_one, part_two);
+
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    let dir_size = parse_directory_sizes(input)?;
     let mut total_size = 0;
 
     for path in dir_size.keys() {
@@ -14,11 +17,11 @@ pub fn part_one(input: &str) -> usize {
         }
     }
 
-    total_size
+    Ok(total_size)
 }
 
-pub fn part_two(input: &str) -> usize {
-    let dir_size = parse_directory_sizes(input);
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    let dir_size = parse_directory_sizes(input)?;
     let total_space = 70000000;
     let needed_space = 30000000;
     let used_space: usize = dir_size.values().copied().sum::<usize>();
@@ -36,64 +39,55 @@ pub fn part_two(input: &str) -> usize {
         }
     }
 
-    best_size
+    Ok(best_size)
 }
 
-fn parse_directory_sizes(input: &str) -> HashMap<String, usize> {
+fn parse_directory_sizes(input: &str) -> Result<HashMap<String, usize>, ParseError> {
     let mut dir_size = HashMap::new();
-    let commands: Vec<_> = input
-        .split("$")
-        .filter_map(|c| c.parse::<Command>().ok())
-        .collect();
     let mut path_stack = Vec::new();
 
-    for command in commands.iter() {
-        match command {
-            Command::CdParent => {
+    for line in parse::lines(input, Line::parse)? {
+        match line {
+            Line::CdParent => {
                 path_stack.pop();
             }
-            Command::Cd(dir) => path_stack.push(dir.as_str()),
-            Command::List(size) => {
-                dir_size.insert(path_stack.join("|"), *size);
+            Line::Cd(dir) => path_stack.push(dir),
+            Line::List => {
+                dir_size.insert(path_stack.join("|"), 0);
             }
+            Line::Dir => (),
+            Line::File(size) => *dir_size.entry(path_stack.join("|")).or_default() += size,
         }
     }
 
-    dir_size
+    Ok(dir_size)
 }
 
 #[derive(Debug)]
-enum Command {
+enum Line<'a> {
     CdParent,
-    Cd(String),
-    List(usize),
+    Cd(&'a str),
+    List,
+    Dir,
+    File(usize),
 }
 
-struct CommandParseErr;
-
-impl std::str::FromStr for Command {
-    type Err = CommandParseErr;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.starts_with(" cd ..") {
-            Ok(Command::CdParent)
-        } else if s.starts_with(" cd ") {
-            let dir = s.trim_start_matches(" cd ").trim();
-            Ok(Command::Cd(dir.to_string()))
-        } else if s.starts_with(" ls") {
-            let lines = s.trim_start_matches(" ls").trim().lines();
-            let mut total_size = 0;
-            for line in lines {
-                if !line.starts_with("dir ") {
-                    if let Some((size, _name)) = line.split_once(" ") {
-                        total_size += size.parse::<usize>().unwrap();
-                    }
-                }
-            }
-
-            Ok(Command::List(total_size))
+impl<'a> Line<'a> {
+    fn parse(p: &mut Parser<'a>) -> Result<Self, ParseError> {
+        if p.try_token("$ cd ..") {
+            Ok(Line::CdParent)
+        } else if p.try_token("$ cd ") {
+            Ok(Line::Cd(p.take_while(|_| true)))
+        } else if p.try_token("$ ls") {
+            Ok(Line::List)
+        } else if p.try_token("dir ") {
+            p.take_while(|_| true);
+            Ok(Line::Dir)
         } else {
-            Err(CommandParseErr)
+            let size = p.int()?;
+            p.token(" ")?;
+            p.take_while(|_| true);
+            Ok(Line::File(size))
         }
     }
 }
@@ -124,6 +118,6 @@ $ ls
 5626152 d.ext
 7214296 k"#;
 
-    assert_eq!(95437, part_one(input));
-    assert_eq!(24933642, part_two(input));
+    assert_eq!(Ok(95437), part_one(input));
+    assert_eq!(Ok(24933642), part_two(input));
 }
@@ -1,4 +1,7 @@
 use crate::HashSet;
+use crate::grid::{Direction, Point};
+
+crate::solution!(part_one, part_two);
 
 pub fn part_one(input: &str) -> usize {
     solve::<2>(input)
@@ -9,17 +12,17 @@ pub fn part_two(input: &str) -> usize {
 }
 
 fn solve<const N: usize>(input: &str) -> usize {
-    let moves = input.trim().lines().filter_map(|l| l.parse::<Move>().ok());
+    let moves = input.trim().lines().filter_map(parse_move);
 
     let mut knots = [Point::default(); N];
     let mut visited = HashSet::new();
 
     visited.insert(knots[N - 1]);
-    for mov in moves {
-        for _ in 0..mov.count() {
-            knots[0] = knots[0] + mov;
+    for (direction, count) in moves {
+        for _ in 0..count {
+            knots[0] = knots[0].step(direction);
             for idx in 1..N {
-                knots[idx] = knots[idx].follow(knots[idx - 1]);
+                knots[idx] = follow(knots[idx], knots[idx - 1]);
             }
 
             visited.insert(knots[N - 1]);
@@ -29,99 +32,25 @@ fn solve<const N: usize>(input: &str) -> usize {
     visited.len()
 }
 
-#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, Default)]
-struct Point {
-    x: i32,
-    y: i32,
-}
-
-impl Point {
-    fn follow(self, other: Self) -> Self {
-        let (dx, dy) = self.diff(other);
-
-        if dx.abs() > 1 || dy.abs() > 1 {
-            self.adjust(dx.signum(), dy.signum())
-        } else {
-            self
-        }
-    }
-
-    fn diff(self, other: Self) -> (i32, i32) {
-        (other.x - self.x, other.y - self.y)
-    }
-
-    fn adjust(self, x: i32, y: i32) -> Self {
-        Point {
-            x: self.x + x,
-            y: self.y + y,
-        }
-    }
-}
+fn follow(knot: Point, other: Point) -> Point {
+    let diff = other - knot;
 
-impl std::ops::Add<Move> for Point {
-    type Output = Self;
-
-    fn add(self, rhs: Move) -> Self::Output {
-        match rhs {
-            Move::Left(_) => Point {
-                x: self.x - 1,
-                y: self.y,
-            },
-            Move::Right(_) => Point {
-                x: self.x + 1,
-                y: self.y,
-            },
-            Move::Up(_) => Point {
-                x: self.x,
-                y: self.y - 1,
-            },
-            Move::Down(_) => Point {
-                x: self.x,
-                y: self.y + 1,
-            },
-        }
+    if diff.x.abs() > 1 || diff.y.abs() > 1 {
+        knot + diff.map(i32::signum)
+    } else {
+        knot
     }
 }
 
-#[derive(Debug, Copy, Clone)]
-enum Move {
-    Left(i32),
-    Right(i32),
-    Up(i32),
-    Down(i32),
-}
-
-impl Move {
-    fn count(&self) -> i32 {
-        match self {
-            Move::Left(n) => *n,
-            Move::Right(n) => *n,
-            Move::Up(n) => *n,
-            Move::Down(n) => *n,
-        }
+fn parse_move(line: &str) -> Option<(Direction, i32)> {
+    let (direction, count) = line.split_once(" ")?;
+    let mut chars = direction.chars();
+    let direction = chars.next().and_then(Direction::from_char)?;
+    if chars.next().is_some() {
+        return None;
     }
-}
 
-struct MoveParseErr;
-
-impl std::str::FromStr for Move {
-    type Err = MoveParseErr;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let value = match s
-            .split_once(" ")
-            .and_then(|(d, v)| v.parse::<i32>().ok().map(|v| (d, v)))
-            .ok_or(MoveParseErr)?
-        {
-            ("R", v) => Move::Right(v),
-            ("L", v) => Move::Left(v),
-            ("U", v) => Move::Up(v),
-            ("D", v) => Move::Down(v),
-            _ => return Err(MoveParseErr),
-        };
-
-        Ok(value)
-    }
+    Some((direction, count.parse().ok()?))
 }
 
 #[test]
@@ -1,8 +1,10 @@
-pub fn part_one(input: &str) -> i32 {
-    let instructions = input
-        .trim()
-        .lines()
-        .filter_map(|l| l.parse::<Instruction>().ok());
+use crate::ParseError;
+use crate::parse::{self, Parser};
+
+crate::solution!(part_one, part_two);
+
+pub fn part_one(input: &str) -> Result<i32, ParseError> {
+    let instructions = parse(input)?;
 
     let mut x = 1;
     let mut cycles = 0;
@@ -25,14 +27,11 @@ pub fn part_one(input: &str) -> i32 {
         }
     }
 
-    signal_strength
+    Ok(signal_strength)
 }
 
-pub fn part_two(input: &str) -> String {
-    let instructions = input
-        .trim()
-        .lines()
-        .filter_map(|l| l.parse::<Instruction>().ok());
+pub fn part_two(input: &str) -> Result<String, ParseError> {
+    let instructions = parse(input)?;
 
     let mut screen = String::with_capacity(41 * 6 * 2);
     let mut x = 1;
@@ -61,7 +60,11 @@ pub fn part_two(input: &str) -> String {
         }
     }
 
-    screen
+    Ok(screen)
+}
+
+fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    parse::lines(input, Instruction::parse)
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -77,27 +80,14 @@ impl Instruction {
             Instruction::Addx(_) => 2,
         }
     }
-}
-
-struct InstructionParseErr;
 
-impl From<std::num::ParseIntError> for InstructionParseErr {
-    fn from(_: std::num::ParseIntError) -> Self {
-        Self
-    }
-}
-
-impl std::str::FromStr for Instruction {
-    type Err = InstructionParseErr;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s == "noop" {
-            return Ok(Instruction::Noop);
-        }
-
-        match s.split_once(" ") {
-            Some(("addx", n)) => Ok(Instruction::Addx(n.parse()?)),
-            _ => Err(InstructionParseErr),
+    fn parse(p: &mut Parser) -> Result<Self, ParseError> {
+        if p.try_token("noop") {
+            Ok(Instruction::Noop)
+        } else if p.try_token("addx ") {
+            Ok(Instruction::Addx(p.int()?))
+        } else {
+            Err(p.error("unknown instruction"))
         }
     }
 }
@@ -251,7 +241,7 @@ noop
 noop
 noop"#;
 
-    assert_eq!(13140, part_one(input));
+    assert_eq!(Ok(13140), part_one(input));
 
     let screen = r#"####    ####    ####    ####    ####    ####    ####    ####    ####    ####    
 ######      ######      ######      ######      ######      ######      ######  
@@ -260,5 +250,10 @@ noop"#;
 ############            ############            ############            ########
 ##############              ##############              ##############          "#;
 
-    assert_eq!(screen, part_two(input));
+    assert_eq!(Ok(screen.to_string()), part_two(input));
+
+    let err = part_one("noop\naddx 3\naddx").unwrap_err();
+    assert_eq!((err.line, err.column), (3, 1));
+    let err = part_two("noop\naddx 3x\nnoop\n").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 7));
 }
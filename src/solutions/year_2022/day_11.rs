@@ -1,17 +1,21 @@
-pub fn part_one(input: &str) -> u64 {
+use crate::ParseError;
+use crate::parse::Parser;
+
+crate::solution!(part_one, part_two);
+
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
     solve::<20, 3>(input)
 }
 
-pub fn part_two(input: &str) -> u64 {
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
     solve::<10000, 1>(input)
 }
 
-fn solve<const ROUNDS: usize, const WORRY_FACTOR: u64>(input: &str) -> u64 {
-    let mut monkeys: Vec<_> = input
-        .trim()
-        .split("\n\n")
-        .filter_map(parse_monkey)
-        .collect();
+fn solve<const ROUNDS: usize, const WORRY_FACTOR: u64>(input: &str) -> Result<u64, ParseError> {
+    let mut monkeys = Parser::new(input)
+        .sections()
+        .map(parse_monkey)
+        .collect::<Result<Vec<_>, _>>()?;
 
     let total_test: u64 = monkeys.iter().map(|m| m.test).product();
 
@@ -28,46 +32,38 @@ fn solve<const ROUNDS: usize, const WORRY_FACTOR: u64>(input: &str) -> u64 {
 
     monkeys.sort_by_key(|m| std::cmp::Reverse(m.inspections));
 
-    monkeys[0..2].iter().map(|m| m.inspections).product()
+    Ok(monkeys[0..2].iter().map(|m| m.inspections).product())
 }
 
-fn parse_monkey(input: &str) -> Option<Monkey> {
-    let mut lines = input.lines();
-
-    let parse_only_num = |line: Option<&str>| {
-        line?
-            .trim_start_matches(|c: char| !c.is_ascii_digit())
-            .trim_end_matches(|c: char| !c.is_ascii_digit())
-            .parse::<u64>()
-            .ok()
+fn parse_monkey(mut p: Parser) -> Result<Monkey, ParseError> {
+    p.token("Monkey ")?;
+    let id = p.int()?;
+    p.token(":")?;
+    p.skip_whitespace();
+    p.token("Starting items: ")?;
+    let items = p.separated(", ", Parser::int)?;
+    p.skip_whitespace();
+    p.token("Operation: new = old ")?;
+    let op = if p.try_token("* old") {
+        Operation::Square
+    } else if p.try_token("* ") {
+        Operation::Mul(p.int()?)
+    } else {
+        p.token("+ ")?;
+        Operation::Add(p.int()?)
     };
-
-    let id = parse_only_num(lines.next())? as usize;
-    let items = lines
-        .next()?
-        .trim()
-        .trim_start_matches("Starting items: ")
-        .split(",")
-        .filter_map(|n| n.trim().parse().ok())
-        .collect();
-
-    let op = match lines
-        .next()?
-        .trim()
-        .trim_start_matches("Operation: new = old ")
-        .split_once(" ")?
-    {
-        ("*", "old") => Operation::Square,
-        ("*", n) => Operation::Mul(n.parse().ok()?),
-        ("+", n) => Operation::Add(n.parse().ok()?),
-        _ => return None,
-    };
-
-    let test = parse_only_num(lines.next())?;
-    let pass = parse_only_num(lines.next())? as usize;
-    let fail = parse_only_num(lines.next())? as usize;
-
-    Some(Monkey {
+    p.skip_whitespace();
+    p.token("Test: divisible by ")?;
+    let test = p.int()?;
+    p.skip_whitespace();
+    p.token("If true: throw to monkey ")?;
+    let pass = p.int()?;
+    p.skip_whitespace();
+    p.token("If false: throw to monkey ")?;
+    let fail = p.int()?;
+    p.end()?;
+
+    Ok(Monkey {
         id,
         items,
         op,
@@ -154,6 +150,6 @@ Monkey 3:
     If true: throw to monkey 0
     If false: throw to monkey 1"#;
 
-    assert_eq!(10605, part_one(input));
-    assert_eq!(2713310158, part_two(input));
+    assert_eq!(Ok(10605), part_one(input));
+    assert_eq!(Ok(2713310158), part_two(input));
 }
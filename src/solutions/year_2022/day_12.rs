@@ -1,85 +1,57 @@
 use crate::HashMap;
+use crate::grid::{Grid, Point};
 
 use std::collections::VecDeque;
 
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> usize {
-    let grid = Grid::new(input.trim());
+    let map = Map::new(input.trim());
 
-    grid.search()
+    map.search()
 }
 
 pub fn part_two(input: &str) -> usize {
-    let grid = Grid::new(input.trim());
+    let map = Map::new(input.trim());
 
-    grid.search_wide()
+    map.search_wide()
 }
 
-struct Grid<'a> {
-    cells: &'a str,
-    width: usize,
-    stride: usize,
-    height: usize,
+struct Map {
+    heights: Grid<u8>,
     start: Point,
     minimums: Vec<Point>,
     end: Point,
 }
 
-impl<'a> Grid<'a> {
-    fn new(input: &'a str) -> Self {
-        let mut width = 0;
-        let mut height = 0;
-        let mut start = Point::default();
-        let mut end = Point::default();
-        let mut minimums = Vec::new();
-
-        for (y, line) in input.lines().enumerate() {
-            for (x, c) in line.bytes().enumerate() {
-                let x = x as isize;
-                let y = y as isize;
-                match c {
-                    b'S' => start = Point::new(x, y),
-                    b'E' => end = Point::new(x, y),
-                    b'a' => minimums.push(Point::new(x, y)),
-                    _ => (),
-                };
-            }
-
-            width = line.len();
-            height = y + 1;
-        }
+impl Map {
+    fn new(input: &str) -> Self {
+        let cells = Grid::parse(input, |c| c.is_ascii_alphabetic().then_some(c as u8))
+            .expect("valid grid");
+        let start = cells.position(|&c| c == b'S').expect("start");
+        let end = cells.position(|&c| c == b'E').expect("end");
+        let mut minimums: Vec<_> = cells
+            .iter()
+            .filter(|(_, c)| **c == b'a')
+            .map(|(p, _)| p)
+            .collect();
 
         minimums.push(start);
 
+        let heights = cells.map(|&c| match c {
+            b'S' => b'a',
+            b'E' => b'z',
+            _ => c,
+        });
+
         Self {
-            cells: input,
-            width,
-            stride: width + 1,
-            height,
+            heights,
             start,
             end,
             minimums,
         }
     }
 
-    fn get(&self, p: Point) -> Option<u8> {
-        if p.x < 0 || p.x >= self.width as isize || p.y < 0 || p.y >= self.height as isize {
-            return None;
-        }
-
-        let x = p.x as usize;
-        let y = p.y as usize;
-
-        self.cells
-            .as_bytes()
-            .get(y * self.stride + x)
-            .copied()
-            .map(|c| match c {
-                b'S' => b'a',
-                b'E' => b'z',
-                _ => c,
-            })
-    }
-
     fn search(&self) -> usize {
         let mut visited = HashMap::new();
         let mut haystack = VecDeque::new();
@@ -133,37 +105,12 @@ impl<'a> Grid<'a> {
         point: Point,
         count: usize,
     ) -> impl Iterator<Item = (Point, usize)> + '_ {
-        let me = self.get(point).unwrap();
-
-        let points = [
-            point.adjust(1, 0),
-            point.adjust(-1, 0),
-            point.adjust(0, 1),
-            point.adjust(0, -1),
-        ];
-
-        points
-            .into_iter()
-            .flat_map(move |p| self.get(p).filter(|&v| v <= me + 1).map(|_| (p, count + 1)))
-    }
-}
-
-#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
-struct Point {
-    x: isize,
-    y: isize,
-}
+        let me = self.heights[point];
 
-impl Point {
-    fn new(x: isize, y: isize) -> Self {
-        Self { x, y }
-    }
-
-    fn adjust(&self, x: isize, y: isize) -> Self {
-        Self {
-            x: self.x + x,
-            y: self.y + y,
-        }
+        self.heights
+            .neighbors(point)
+            .filter(move |&p| self.heights[p] <= me + 1)
+            .map(move |p| (p, count + 1))
     }
 }
 
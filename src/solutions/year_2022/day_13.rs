@@ -1,5 +1,7 @@
 use std::cmp::Ordering;
 
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> usize {
     let packets = input.trim().split("\n\n").filter_map(|pairs| {
         let (left, right) = pairs.split_once("\n")?;
@@ -1,8 +1,12 @@
+use crate::grid::{Grid, Point};
+
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> u64 {
-    let mut grid = Grid::new(input, false);
+    let mut cave = Cave::new(input, false);
 
     let mut count = 0;
-    while grid.drop().is_some() {
+    while cave.drop().is_some() {
         count += 1;
     }
 
@@ -10,71 +14,67 @@ pub fn part_one(input: &str) -> u64 {
 }
 
 pub fn part_two(input: &str) -> u64 {
-    let mut grid = Grid::new(input, true);
+    let mut cave = Cave::new(input, true);
 
     let mut count = 0;
-    while grid.drop().is_some() {
+    while cave.drop().is_some() {
         count += 1;
     }
 
     count + 1
 }
 
-struct Grid {
-    cells: Vec<bool>,
-    width: usize,
-    height: usize,
-    drop: (i32, i32),
+struct Cave {
+    cells: Grid<bool>,
+    drop: Point,
     floor: bool,
 }
 
-impl Grid {
+impl Cave {
     fn new(input: &str, floor: bool) -> Self {
         let mut max_y = None;
 
         for line in input.lines() {
-            for (_, y) in walk_line(line) {
-                max_y = Some(max_y.unwrap_or(y).max(y));
+            for p in walk_line(line) {
+                max_y = Some(max_y.unwrap_or(p.y).max(p.y));
             }
         }
 
         let height = max_y.unwrap() as usize + 2;
         let width = height * 2;
-        let mut cells = vec![false; width * height];
+        let mut cells = Grid::new(width, height, false);
+        let offset = Point::new(height as i32 - 500, 0);
 
         for line in input.lines() {
-            for (x, y) in walk_line(line) {
-                let x = ((x - 500) + height as i32) as usize;
-                let y = y as usize;
-
-                cells[(y * width) + x] = true;
+            for p in walk_line(line) {
+                cells[p + offset] = true;
             }
         }
 
         Self {
             cells,
-            width,
-            height,
             floor,
-            drop: (height as i32, 0),
+            drop: Point::new(height as i32, 0),
         }
     }
 
     fn drop(&mut self) -> Option<()> {
-        let (mut x, mut y) = self.drop;
+        let mut p = self.drop;
 
         loop {
-            if !self.get(x, y + 1)? {
-                y += 1;
-            } else if !self.get(x - 1, y + 1)? {
-                x -= 1;
-                y += 1;
-            } else if !self.get(x + 1, y + 1)? {
-                x += 1;
-                y += 1;
+            let down = p + Point::new(0, 1);
+            let down_left = p + Point::new(-1, 1);
+            let down_right = p + Point::new(1, 1);
+
+            if !self.get(down)? {
+                p = down;
+            } else if !self.get(down_left)? {
+                p = down_left;
+            } else if !self.get(down_right)? {
+                p = down_right;
             } else {
-                self.set(x, y);
-                if (x, y) == self.drop {
+                self.cells.set(p, true);
+                if p == self.drop {
                     return None;
                 } else {
                     return Some(());
@@ -83,36 +83,20 @@ impl Grid {
         }
     }
 
-    fn get(&self, x: i32, y: i32) -> Option<bool> {
-        if self.floor && y as usize == self.height {
+    fn get(&self, p: Point) -> Option<bool> {
+        if self.floor && p.y as usize == self.cells.height() {
             Some(true)
-        } else if !(0..self.width as i32).contains(&x) || !(0..self.height as i32).contains(&y) {
-            None
         } else {
-            let x = x as usize;
-            let y = y as usize;
-
-            self.cells.get(y * self.width + x).copied()
+            self.cells.get(p).copied()
         }
     }
-
-    fn set(&mut self, x: i32, y: i32) {
-        if !(0..self.width as i32).contains(&x) || !(0..self.height as i32).contains(&y) {
-            return;
-        }
-
-        let x = x as usize;
-        let y = y as usize;
-
-        self.cells[y * self.width + x] = true;
-    }
 }
 
-fn walk_line(input: &str) -> impl Iterator<Item = (i32, i32)> + '_ {
+fn walk_line(input: &str) -> impl Iterator<Item = Point> + '_ {
     let mut segments = input
         .split(" -> ")
         .filter_map(|pair| pair.split_once(','))
-        .filter_map(|(x, y)| Some((x.parse::<i32>().ok()?, y.parse::<i32>().ok()?)));
+        .filter_map(|(x, y)| Some(Point::new(x.parse().ok()?, y.parse().ok()?)));
 
     let mut point = segments.next().unwrap();
     let mut target = point;
@@ -122,8 +106,7 @@ fn walk_line(input: &str) -> impl Iterator<Item = (i32, i32)> + '_ {
         if done {
             return None;
         } else if point != target {
-            point.0 += (target.0 - point.0).signum();
-            point.1 += (target.1 - point.1).signum();
+            point += (target - point).map(i32::signum);
         } else {
             if let Some(next) = segments.next() {
                 target = next;
@@ -1,14 +1,15 @@
-use crate::HashSet;
+use crate::parse::{self, Parser};
+use crate::{HashSet, ParseError};
 
-pub fn part_one(input: &str) -> i32 {
+crate::solution!(part_one, part_two);
+
+pub fn part_one(input: &str) -> Result<i32, ParseError> {
     solve_part_one::<2000000>(input)
 }
 
-fn solve_part_one<const Y: i32>(input: &str) -> i32 {
-    let sensors = input
-        .trim()
-        .lines()
-        .map(parse)
+fn solve_part_one<const Y: i32>(input: &str) -> Result<i32, ParseError> {
+    let sensors = parse::lines(input, parse_sensor)?
+        .into_iter()
         .filter(|s| s.near_y(Y))
         .collect::<Vec<_>>();
 
@@ -38,15 +39,15 @@ fn solve_part_one<const Y: i32>(input: &str) -> i32 {
 
     count -= target_beacons.len() as i32;
 
-    count
+    Ok(count)
 }
 
-pub fn part_two(input: &str) -> u64 {
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
     solve_part_two::<4000000>(input)
 }
 
-fn solve_part_two<const MAX: i32>(input: &str) -> u64 {
-    let sensors = input.trim().lines().map(parse).collect::<Vec<_>>();
+fn solve_part_two<const MAX: i32>(input: &str) -> Result<u64, ParseError> {
+    let sensors = parse::lines(input, parse_sensor)?;
 
     for sensor in sensors.iter() {
         for (x, y) in sensor
@@ -54,28 +55,32 @@ fn solve_part_two<const MAX: i32>(input: &str) -> u64 {
             .filter(|&(x, y)| x >= 0 && x <= MAX && y >= 0 && y <= MAX)
         {
             if sensors.iter().all(|s| !s.is_covering(x, y)) {
-                return x as u64 * 4000000 + y as u64;
+                return Ok(x as u64 * 4000000 + y as u64);
             }
         }
     }
 
-    0
+    Ok(0)
 }
 
-fn parse(line: &str) -> Sensor {
-    let mut numbers = line
-        .split(|c: char| !c.is_ascii_digit() && c != '-')
-        .filter(|p| !p.is_empty())
-        .filter_map(|p| p.parse::<i32>().ok());
+fn parse_sensor(p: &mut Parser) -> Result<Sensor, ParseError> {
+    p.token("Sensor at x=")?;
+    let x = p.int()?;
+    p.token(", y=")?;
+    let y = p.int()?;
+    p.token(": closest beacon is at x=")?;
+    let beacon_x = p.int()?;
+    p.token(", y=")?;
+    let beacon_y = p.int()?;
 
-    let pos = (numbers.next().unwrap(), numbers.next().unwrap());
-    let beacon = (numbers.next().unwrap(), numbers.next().unwrap());
+    let pos = (x, y);
+    let beacon = (beacon_x, beacon_y);
 
-    Sensor {
+    Ok(Sensor {
         pos,
         beacon,
         distance: distance(pos, beacon),
-    }
+    })
 }
 
 #[derive(Debug)]
@@ -208,6 +213,6 @@ Sensor at x=16, y=7: closest beacon is at x=15, y=3
 Sensor at x=14, y=3: closest beacon is at x=15, y=3
 Sensor at x=20, y=1: closest beacon is at x=15, y=3"#;
 
-    assert_eq!(26, solve_part_one::<10>(input));
-    assert_eq!(56000011, solve_part_two::<20>(input));
+    assert_eq!(Ok(26), solve_part_one::<10>(input));
+    assert_eq!(Ok(56000011), solve_part_two::<20>(input));
 }
@@ -1,6 +1,7 @@
+use crate::search;
 use crate::{HashMap, HashSet};
 
-use std::collections::VecDeque;
+crate::solution!(part_one, part_two);
 
 pub fn part_one(input: &str) -> u64 {
     let mut graph = Graph::new(input);
@@ -125,12 +126,13 @@ impl<'a> Graph<'a> {
             if left.rate == 0 && left.name != "AA" {
                 continue;
             }
+            let steps = search::bfs_reachable(left.id, |&node| self.neighbors(node));
             let start = left.id + 1;
             for right in self.nodes[start..].iter() {
                 if right.rate == 0 && right.name != "AA" {
                     continue;
                 }
-                let cost = self.shortest_path(left.id, right.id);
+                let cost = steps.get(&right.id).map_or(u64::MAX, |&steps| steps as u64);
 
                 let connection_id = ConnectionId::new(left.id, right.id);
                 let edge = Edge { cost };
@@ -156,30 +158,6 @@ impl<'a> Graph<'a> {
         self.routes = new_routes;
     }
 
-    fn shortest_path(&self, start: NodeId, end: NodeId) -> u64 {
-        let mut haystack = VecDeque::new();
-        let mut visited = HashSet::new();
-
-        haystack.extend(self.neighbors(start).map(|n| (n, 1)));
-
-        while let Some((next, cost)) = haystack.pop_front() {
-            if next == end {
-                return cost;
-            }
-
-            for neighbor in self.neighbors(next) {
-                if visited.contains(&neighbor) {
-                    continue;
-                }
-
-                visited.insert(neighbor);
-                haystack.push_back((neighbor, cost + 1));
-            }
-        }
-
-        u64::MAX
-    }
-
     fn neighbors(&self, node: NodeId) -> impl Iterator<Item = NodeId> + '_ {
         self.connections
             .get(&node)
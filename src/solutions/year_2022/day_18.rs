@@ -1,12 +1,16 @@
 use crate::HashSet;
+use crate::geometry::Point3;
+
+crate::solution!(part_one, part_two);
+
+type Point = Point3<i32>;
 
 pub fn part_one(input: &str) -> i64 {
     let mut map = HashSet::new();
 
     let mut faces = 0;
     for point in parse(input) {
-        let matched_faces = point
-            .neighbors()
+        let matched_faces = neighbors(point)
             .into_iter()
             .filter(|p| map.contains(p))
             .count() as i64;
@@ -22,22 +26,21 @@ pub fn part_two(input: &str) -> i64 {
     let mut possibly_trapped = HashSet::new();
     let mut trapped = HashSet::new();
 
-    let mut max = Point(i32::MIN, i32::MIN, i32::MIN);
-    let mut min = Point(i32::MAX, i32::MAX, i32::MAX);
+    let mut max = Point::new(i32::MIN, i32::MIN, i32::MIN);
+    let mut min = Point::new(i32::MAX, i32::MAX, i32::MAX);
 
     let mut faces = 0;
     for point in parse(input) {
-        let matched_faces = point
-            .neighbors()
+        let matched_faces = neighbors(point)
             .into_iter()
             .filter(|p| map.contains(p))
             .count() as i64;
 
         map.insert(point);
-        possibly_trapped.extend(point.neighbors());
+        possibly_trapped.extend(neighbors(point));
 
-        max = max.max(point);
-        min = min.min(point);
+        max = max.max_components(point);
+        min = min.min_components(point);
 
         faces += 6 - (matched_faces * 2);
     }
@@ -47,18 +50,18 @@ pub fn part_two(input: &str) -> i64 {
         .copied()
         .collect();
 
-    for &Point(x, y, z) in possibly_trapped.iter() {
-        if search_range(&map, min.0, x, max.0, |n| Point(n, y, z))
-            && search_range(&map, min.1, y, max.1, |n| Point(x, n, z))
-            && search_range(&map, min.2, z, max.2, |n| Point(x, y, n))
+    for &Point3 { x, y, z } in possibly_trapped.iter() {
+        if search_range(&map, min.x, x, max.x, |n| Point::new(n, y, z))
+            && search_range(&map, min.y, y, max.y, |n| Point::new(x, n, z))
+            && search_range(&map, min.z, z, max.z, |n| Point::new(x, y, n))
         {
-            trapped.insert(Point(x, y, z));
+            trapped.insert(Point::new(x, y, z));
         }
     }
 
     let trapped_faces = map
         .into_iter()
-        .flat_map(|p| p.neighbors())
+        .flat_map(neighbors)
         .filter(|p| trapped.contains(p))
         .count() as i64;
 
@@ -71,7 +74,7 @@ fn parse(input: &str) -> impl Iterator<Item = Point> + '_ {
         let x = p.next()?;
         let y = p.next()?;
         let z = p.next()?;
-        Some(Point(x, y, z))
+        Some(Point::new(x, y, z))
     })
 }
 
@@ -104,38 +107,16 @@ fn search_range<F: Fn(i32) -> Point>(
     false
 }
 
-#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
-struct Point(i32, i32, i32);
-
-impl Point {
-    fn neighbors(&self) -> [Point; 6] {
-        let Point(x, y, z) = self;
-
-        [
-            Point(x + 1, y + 0, z + 0),
-            Point(x - 1, y + 0, z + 0),
-            Point(x + 0, y + 1, z + 0),
-            Point(x + 0, y - 1, z + 0),
-            Point(x + 0, y + 0, z + 1),
-            Point(x + 0, y + 0, z - 1),
-        ]
-    }
-
-    fn max(&self, other: Point) -> Point {
-        let x = self.0.max(other.0);
-        let y = self.1.max(other.1);
-        let z = self.2.max(other.2);
-
-        Point(x, y, z)
-    }
-
-    fn min(&self, other: Point) -> Point {
-        let x = self.0.min(other.0);
-        let y = self.1.min(other.1);
-        let z = self.2.min(other.2);
-
-        Point(x, y, z)
-    }
+fn neighbors(p: Point) -> [Point; 6] {
+    [
+        Point::new(1, 0, 0),
+        Point::new(-1, 0, 0),
+        Point::new(0, 1, 0),
+        Point::new(0, -1, 0),
+        Point::new(0, 0, 1),
+        Point::new(0, 0, -1),
+    ]
+    .map(|offset| p + offset)
 }
 
 #[test]
@@ -1,20 +1,25 @@
-pub fn part_one(input: &str) -> u16 {
-    input
-        .trim()
-        .lines()
-        .filter_map(Blueprint::parse)
+use crate::ParseError;
+use crate::parse::{self, Parser};
+
+crate::solution!(part_one, part_two);
+
+pub fn part_one(input: &str) -> Result<u16, ParseError> {
+    let quality = parse::lines(input, Blueprint::parse)?
+        .into_iter()
         .map(|b| b.find_max_score(24, false))
-        .sum()
+        .sum();
+
+    Ok(quality)
 }
 
-pub fn part_two(input: &str) -> u16 {
-    input
-        .trim()
-        .lines()
-        .filter_map(Blueprint::parse)
+pub fn part_two(input: &str) -> Result<u16, ParseError> {
+    let geodes = parse::lines(input, Blueprint::parse)?
+        .into_iter()
         .take(3)
         .map(|b| b.find_max_score(32, true))
-        .product()
+        .product();
+
+    Ok(geodes)
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -66,25 +71,24 @@ struct Blueprint {
 }
 
 impl Blueprint {
-    fn parse(line: &str) -> Option<Self> {
-        let line = line.trim_start_matches("Blueprint ");
-        let (id, line) = line.split_once(": Each ore robot costs ")?;
-        let (ore_robot_ore, line) = line.split_once(" ore. Each clay robot costs ")?;
-        let (clay_robot_ore, line) = line.split_once(" ore. Each obsidian robot costs ")?;
-        let (obsidian_robot_ore, line) = line.split_once(" ore and ")?;
-        let (obsidian_robot_clay, line) = line.split_once(" clay. Each geode robot costs ")?;
-        let (geode_robot_ore, line) = line.split_once(" ore and ")?;
-        let (geode_robot_obsidian, _line) = line.split_once(" ")?;
-
-        let id = id.parse().ok()?;
-        let ore_robot_ore = ore_robot_ore.parse().ok()?;
-        let clay_robot_ore = clay_robot_ore.parse().ok()?;
-        let obsidian_robot_ore = obsidian_robot_ore.parse().ok()?;
-        let obsidian_robot_clay = obsidian_robot_clay.parse().ok()?;
-        let geode_robot_ore = geode_robot_ore.parse().ok()?;
-        let geode_robot_obsidian = geode_robot_obsidian.parse().ok()?;
-
-        Some(Blueprint {
+    fn parse(p: &mut Parser) -> Result<Self, ParseError> {
+        p.token("Blueprint ")?;
+        let id = p.int()?;
+        p.token(": Each ore robot costs ")?;
+        let ore_robot_ore = p.int()?;
+        p.token(" ore. Each clay robot costs ")?;
+        let clay_robot_ore = p.int()?;
+        p.token(" ore. Each obsidian robot costs ")?;
+        let obsidian_robot_ore = p.int()?;
+        p.token(" ore and ")?;
+        let obsidian_robot_clay = p.int()?;
+        p.token(" clay. Each geode robot costs ")?;
+        let geode_robot_ore = p.int()?;
+        p.token(" ore and ")?;
+        let geode_robot_obsidian = p.int()?;
+        p.token(" obsidian.")?;
+
+        Ok(Blueprint {
             id,
             ore_robot_ore,
             clay_robot_ore,
@@ -210,6 +214,6 @@ fn test() {
     let input = r#"Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
 Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian."#;
 
-    assert_eq!(33, part_one(input));
-    assert_eq!(56 * 62, part_two(input));
+    assert_eq!(Ok(33), part_one(input));
+    assert_eq!(Ok(56 * 62), part_two(input));
 }
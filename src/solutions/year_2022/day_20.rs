@@ -1,3 +1,5 @@
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> isize {
     let mixer = Mixer::<1, 1>::new(input);
     mixer.answer()
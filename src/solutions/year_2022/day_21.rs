@@ -1,21 +1,24 @@
-use crate::HashMap;
+use crate::parse::{self, Parser};
+use crate::{HashMap, ParseError};
 
-pub fn part_one(input: &str) -> i64 {
-    let monkeys = MonkeyTroop::new(input, false);
+crate::solution!(part_one, part_two);
 
-    monkeys
+pub fn part_one(input: &str) -> Result<i64, ParseError> {
+    let monkeys = MonkeyTroop::parse(input, false)?;
+
+    Ok(monkeys
         .get("root")
         .unwrap_or(PartialNum::Num(0))
-        .unwrap_or(0)
+        .unwrap_or(0))
 }
 
-pub fn part_two(input: &str) -> i64 {
-    let monkeys = MonkeyTroop::new(input, true);
+pub fn part_two(input: &str) -> Result<i64, ParseError> {
+    let monkeys = MonkeyTroop::parse(input, true)?;
 
-    monkeys
+    Ok(monkeys
         .get("root")
         .unwrap_or(PartialNum::Num(0))
-        .unwrap_or(0)
+        .unwrap_or(0))
 }
 
 struct MonkeyTroop<'a> {
@@ -23,13 +26,12 @@ struct MonkeyTroop<'a> {
 }
 
 impl<'a> MonkeyTroop<'a> {
-    fn new(input: &'a str, part_2: bool) -> Self {
-        let monkeys = input
-            .lines()
-            .filter_map(|l| Monkey::parse(l, part_2))
+    fn parse(input: &'a str, part_2: bool) -> Result<Self, ParseError> {
+        let monkeys = parse::lines(input, |p| Monkey::parse(p, part_2))?
+            .into_iter()
             .collect();
 
-        Self { monkeys }
+        Ok(Self { monkeys })
     }
 
     fn get(&self, name: &str) -> Option<PartialNum> {
@@ -49,37 +51,35 @@ enum Monkey<'a> {
 }
 
 impl<'a> Monkey<'a> {
-    fn parse(line: &'a str, part_2: bool) -> Option<(&'a str, Self)> {
-        let (name, line) = line.split_once(": ")?;
-
-        let eq = line
-            .split_once(['+', '-', '*', '/'])
-            .filter(|_| part_2 && name == "root")
-            .map(|(l, r)| (l.trim(), r.trim()));
-        let human = (name == "humn" && part_2).then_some(());
-        let add = line.split_once(" + ");
-        let sub = line.split_once(" - ");
-        let mul = line.split_once(" * ");
-        let div = line.split_once(" / ");
-
-        let monkey = if let Some((lhs, rhs)) = eq {
-            Monkey::Eq(lhs, rhs)
-        } else if human.is_some() {
-            Monkey::Human
-        } else if let Some((lhs, rhs)) = add {
-            Monkey::Add(lhs, rhs)
-        } else if let Some((lhs, rhs)) = sub {
-            Monkey::Sub(lhs, rhs)
-        } else if let Some((lhs, rhs)) = mul {
-            Monkey::Mul(lhs, rhs)
-        } else if let Some((lhs, rhs)) = div {
-            Monkey::Div(lhs, rhs)
+    fn parse(p: &mut Parser<'a>, part_2: bool) -> Result<(&'a str, Self), ParseError> {
+        let name = p.take_while(|c| c.is_ascii_alphabetic());
+        p.token(": ")?;
+
+        let monkey = if p.peek().is_some_and(|c| c.is_ascii_alphabetic()) {
+            let lhs = p.take_while(|c| c.is_ascii_alphabetic());
+            p.token(" ")?;
+            let op = *p;
+            let operator = p.char()?;
+            p.token(" ")?;
+            let rhs = p.take_while(|c| c.is_ascii_alphabetic());
+
+            match operator {
+                '+' | '-' | '*' | '/' if part_2 && name == "root" => Monkey::Eq(lhs, rhs),
+                '+' => Monkey::Add(lhs, rhs),
+                '-' => Monkey::Sub(lhs, rhs),
+                '*' => Monkey::Mul(lhs, rhs),
+                '/' => Monkey::Div(lhs, rhs),
+                _ => return Err(op.error("unknown operation")),
+            }
         } else {
-            let n = line.parse().ok()?;
-            Monkey::Num(n)
+            Monkey::Num(p.int()?)
         };
 
-        Some((name, monkey))
+        if part_2 && name == "humn" {
+            Ok((name, Monkey::Human))
+        } else {
+            Ok((name, monkey))
+        }
     }
 
     fn value(&self, monkeys: &MonkeyTroop<'a>) -> Option<PartialNum> {
@@ -242,6 +242,6 @@ lgvd: ljgn * ptdq
 drzm: hmdt - zczc
 hmdt: 32"#;
 
-    assert_eq!(152, part_one(input));
-    assert_eq!(301, part_two(input));
+    assert_eq!(Ok(152), part_one(input));
+    assert_eq!(Ok(301), part_two(input));
 }
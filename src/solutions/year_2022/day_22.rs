@@ -1,6 +1,8 @@
 use crate::{HashMap, HashSet};
 use std::cell::RefCell;
 
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> usize {
     let (map, movement) = input.split_once("\n\n").unwrap();
     let map = FlatMap::new(map);
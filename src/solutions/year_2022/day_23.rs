@@ -1,9 +1,13 @@
+use crate::geometry::Rect;
+use crate::grid::{Direction, Point};
 use crate::{HashMap, HashSet};
 
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> usize {
     let mut map = Map::new(input);
 
-    map.tick(SearchDir::patterns().take(10));
+    map.tick(patterns().take(10));
 
     map.score()
 }
@@ -11,7 +15,7 @@ pub fn part_one(input: &str) -> usize {
 pub fn part_two(input: &str) -> usize {
     let mut map = Map::new(input);
 
-    map.tick(SearchDir::patterns());
+    map.tick(patterns());
 
     map.round
 }
@@ -29,10 +33,7 @@ impl Map {
             for (x, c) in l.chars().enumerate() {
                 match c {
                     '#' => {
-                        elves.insert(Point {
-                            x: x as isize,
-                            y: y as isize,
-                        });
+                        elves.insert(Point::new(x as i32, y as i32));
                     }
                     '.' => (),
                     _ => unreachable!(),
@@ -43,7 +44,7 @@ impl Map {
         Self { elves, round: 0 }
     }
 
-    fn tick<I: Iterator<Item = [SearchDir; 4]>>(&mut self, patterns: I) {
+    fn tick<I: Iterator<Item = [Direction; 4]>>(&mut self, patterns: I) {
         let mut proposals = HashMap::new();
         let mut elf_wishes = HashMap::new();
         let mut new_elves = HashSet::new();
@@ -56,7 +57,7 @@ impl Map {
             'next_elf: for &elf in self.elves.iter() {
                 let mut alone = true;
 
-                'all_dirs: for dir in elf.all_dirs() {
+                'all_dirs: for dir in elf.neighbors_diagonal() {
                     if self.elves.contains(&dir) {
                         alone = false;
                         break 'all_dirs;
@@ -65,8 +66,8 @@ impl Map {
 
                 if !alone {
                     'next_dir: for &dir in pattern.iter() {
-                        let movement = elf.movement(dir);
-                        for point in elf.search(dir) {
+                        let movement = elf.step(dir);
+                        for point in search(elf, dir) {
                             if self.elves.contains(&point) {
                                 continue 'next_dir;
                             }
@@ -112,129 +113,51 @@ impl Map {
 
     fn score(&self) -> usize {
         let count = self.elves.len();
+        let bounds = Rect::bounding(self.elves.iter().copied()).unwrap();
 
-        let mut min = Point {
-            x: isize::MAX,
-            y: isize::MAX,
-        };
-        let mut max = Point {
-            x: isize::MIN,
-            y: isize::MIN,
-        };
-
-        for Point { x, y } in self.elves.iter().copied() {
-            min.x = x.min(min.x);
-            max.x = x.max(max.x);
-            min.y = y.min(min.y);
-            max.y = y.max(max.y);
-        }
-
-        let width = min.x.abs_diff(max.x) + 1;
-        let height = min.y.abs_diff(max.y) + 1;
-
-        width * height - count
+        bounds.area() as usize - count
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-enum SearchDir {
-    North,
-    South,
-    East,
-    West,
-}
+fn search(elf: Point, dir: Direction) -> [Point; 3] {
+    let ahead = elf.step(dir);
 
-#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
-struct Point {
-    x: isize,
-    y: isize,
+    [
+        ahead.step(dir.turn_left()),
+        ahead,
+        ahead.step(dir.turn_right()),
+    ]
 }
 
-impl Point {
-    fn all_dirs(&self) -> [Point; 8] {
-        let &Point { x, y } = self;
-
+fn patterns() -> impl Iterator<Item = [Direction; 4]> {
+    [
         [
-            Point { x: x - 1, y: y - 1 },
-            Point { x: x + 0, y: y - 1 },
-            Point { x: x + 1, y: y - 1 },
-            Point { x: x + 1, y: y + 0 },
-            Point { x: x + 1, y: y + 1 },
-            Point { x: x + 0, y: y + 1 },
-            Point { x: x - 1, y: y + 1 },
-            Point { x: x - 1, y: y + 0 },
-        ]
-    }
-    fn search(&self, dir: SearchDir) -> [Point; 3] {
-        let &Point { x, y } = self;
-
-        match dir {
-            SearchDir::North => [
-                Point { x: x - 1, y: y - 1 },
-                Point { x: x + 0, y: y - 1 },
-                Point { x: x + 1, y: y - 1 },
-            ],
-            SearchDir::South => [
-                Point { x: x - 1, y: y + 1 },
-                Point { x: x + 0, y: y + 1 },
-                Point { x: x + 1, y: y + 1 },
-            ],
-            SearchDir::East => [
-                Point { x: x + 1, y: y - 1 },
-                Point { x: x + 1, y: y + 0 },
-                Point { x: x + 1, y: y + 1 },
-            ],
-            SearchDir::West => [
-                Point { x: x - 1, y: y - 1 },
-                Point { x: x - 1, y: y + 0 },
-                Point { x: x - 1, y: y + 1 },
-            ],
-        }
-    }
-
-    fn movement(&self, dir: SearchDir) -> Point {
-        let &Point { x, y } = self;
-
-        match dir {
-            SearchDir::North => Point { x, y: y - 1 },
-            SearchDir::South => Point { x, y: y + 1 },
-            SearchDir::East => Point { x: x + 1, y },
-            SearchDir::West => Point { x: x - 1, y },
-        }
-    }
-}
-
-impl SearchDir {
-    fn patterns() -> impl Iterator<Item = [SearchDir; 4]> {
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ],
         [
-            [
-                SearchDir::North,
-                SearchDir::South,
-                SearchDir::West,
-                SearchDir::East,
-            ],
-            [
-                SearchDir::South,
-                SearchDir::West,
-                SearchDir::East,
-                SearchDir::North,
-            ],
-            [
-                SearchDir::West,
-                SearchDir::East,
-                SearchDir::North,
-                SearchDir::South,
-            ],
-            [
-                SearchDir::East,
-                SearchDir::North,
-                SearchDir::South,
-                SearchDir::West,
-            ],
-        ]
-        .into_iter()
-        .cycle()
-    }
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+            Direction::Up,
+        ],
+        [
+            Direction::Left,
+            Direction::Right,
+            Direction::Up,
+            Direction::Down,
+        ],
+        [
+            Direction::Right,
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+        ],
+    ]
+    .into_iter()
+    .cycle()
 }
 
 #[test]
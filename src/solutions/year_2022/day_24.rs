@@ -1,13 +1,16 @@
+use crate::grid::{Direction, Grid, Point};
 use crate::{HashMap, HashSet};
 use std::collections::VecDeque;
 
-pub fn part_one(input: &str) -> isize {
+crate::solution!(part_one, part_two);
+
+pub fn part_one(input: &str) -> i32 {
     let mut map = Map::new(input);
 
     map.find_path(Target::End)
 }
 
-pub fn part_two(input: &str) -> isize {
+pub fn part_two(input: &str) -> i32 {
     let mut map = Map::new(input);
 
     map.find_path(Target::Hungry)
@@ -21,12 +24,12 @@ enum Target {
 }
 
 struct Map {
-    width: isize,
-    height: isize,
+    width: i32,
+    height: i32,
     start: Point,
     end: Point,
     blizzards: Vec<Blizzard>,
-    grids: HashMap<isize, Vec<bool>>,
+    grids: HashMap<i32, Grid<bool>>,
 }
 
 impl Map {
@@ -41,38 +44,23 @@ impl Map {
 
         for (y, l) in input.trim().lines().enumerate() {
             for (x, c) in l.chars().enumerate() {
-                let x = x as isize;
-                let y = y as isize;
-
-                match c {
-                    '.' => {
-                        if start.is_none() {
-                            start = Some(Point { x, y });
-                        }
-                        end = Some(Point { x, y });
+                let point = Point::new(x as i32, y as i32);
+
+                if c == '.' {
+                    if start.is_none() {
+                        start = Some(point);
                     }
-                    '<' => blizzards.push(Blizzard {
-                        origin: Point { x, y },
-                        direction: Direction::Left,
-                    }),
-                    '>' => blizzards.push(Blizzard {
-                        origin: Point { x, y },
-                        direction: Direction::Right,
-                    }),
-                    '^' => blizzards.push(Blizzard {
-                        origin: Point { x, y },
-                        direction: Direction::Up,
-                    }),
-                    'v' => blizzards.push(Blizzard {
-                        origin: Point { x, y },
-                        direction: Direction::Down,
-                    }),
-                    _ => (),
+                    end = Some(point);
+                } else if let Some(direction) = Direction::from_char(c) {
+                    blizzards.push(Blizzard {
+                        origin: point,
+                        direction,
+                    });
                 }
             }
 
-            if width < l.len() as isize {
-                width = l.len() as isize;
+            if width < l.len() as i32 {
+                width = l.len() as i32;
             }
 
             height += 1;
@@ -88,7 +76,7 @@ impl Map {
         }
     }
 
-    fn find_path(&mut self, target: Target) -> isize {
+    fn find_path(&mut self, target: Target) -> i32 {
         let mut queue = VecDeque::new();
         let mut visited = HashSet::new();
         let tick = 0;
@@ -106,7 +94,7 @@ impl Map {
             let tick = tick + 1;
 
             queue.extend(self.moves_on_tick(pos, tick).filter_map(|m| {
-                let pos = pos.movement(m);
+                let pos = m.map_or(pos, |dir| pos.step(dir));
                 let val = (pos, tick, target);
 
                 if visited.contains(&val) {
@@ -121,86 +109,49 @@ impl Map {
         0
     }
 
-    fn moves_on_tick(&mut self, player: Point, tick: isize) -> impl Iterator<Item = Movement> {
+    // Each move is a direction, or `None` to wait in place.
+    fn moves_on_tick(
+        &mut self,
+        player: Point,
+        tick: i32,
+    ) -> impl Iterator<Item = Option<Direction>> {
+        let [up, right, down, left] = Direction::ALL.map(|dir| player.step(dir));
+
         let stay_still = self.valid_for_tick(tick, player);
-        let right = player.right().x < self.width - 1
+        let right = right.x < self.width - 1
             && player != self.start
             && player != self.end
-            && self.valid_for_tick(tick, player.right());
-        let left = player.left().x >= 1
+            && self.valid_for_tick(tick, right);
+        let left = left.x >= 1
             && player != self.start
             && player != self.end
-            && self.valid_for_tick(tick, player.left());
-        let up = (player.up() == self.start || player.up().y >= 1)
-            && self.valid_for_tick(tick, player.up());
-        let down = (player.down() == self.end || player.down().y < self.height - 1)
-            && self.valid_for_tick(tick, player.down());
+            && self.valid_for_tick(tick, left);
+        let up = (up == self.start || up.y >= 1) && self.valid_for_tick(tick, up);
+        let down =
+            (down == self.end || down.y < self.height - 1) && self.valid_for_tick(tick, down);
 
         [
-            right.then_some(Movement::Right),
-            down.then_some(Movement::Down),
-            stay_still.then_some(Movement::Wait),
-            up.then_some(Movement::Up),
-            left.then_some(Movement::Left),
+            right.then_some(Some(Direction::Right)),
+            down.then_some(Some(Direction::Down)),
+            stay_still.then_some(None),
+            up.then_some(Some(Direction::Up)),
+            left.then_some(Some(Direction::Left)),
         ]
         .into_iter()
         .flatten()
     }
 
-    fn valid_for_tick(&mut self, tick: isize, pos: Point) -> bool {
+    fn valid_for_tick(&mut self, tick: i32, pos: Point) -> bool {
         let map = self.grids.entry(tick).or_insert_with(|| {
-            let size = self.width as usize * self.height as usize;
-            let mut map = vec![false; size];
+            let mut map = Grid::new(self.width as usize, self.height as usize, false);
             for blizz in self.blizzards.iter() {
-                let pos = blizz.pos_on_tick(tick, self.width, self.height);
-                let idx = pos.y * self.width + pos.x;
-
-                map[idx as usize] = true;
+                map.set(blizz.pos_on_tick(tick, self.width, self.height), true);
             }
 
             map
         });
 
-        let idx = pos.y * self.width + pos.x;
-        !map.get(idx as usize).copied().unwrap_or(false)
-    }
-}
-
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-struct Point {
-    x: isize,
-    y: isize,
-}
-
-impl Point {
-    fn movement(&self, movement: Movement) -> Point {
-        match movement {
-            Movement::Wait => *self,
-            Movement::Left => self.left(),
-            Movement::Right => self.right(),
-            Movement::Up => self.up(),
-            Movement::Down => self.down(),
-        }
-    }
-
-    fn left(&self) -> Point {
-        let &Point { x, y } = self;
-        Point { x: x - 1, y }
-    }
-
-    fn right(&self) -> Point {
-        let &Point { x, y } = self;
-        Point { x: x + 1, y }
-    }
-
-    fn up(&self) -> Point {
-        let &Point { x, y } = self;
-        Point { x, y: y - 1 }
-    }
-
-    fn down(&self) -> Point {
-        let &Point { x, y } = self;
-        Point { x, y: y + 1 }
+        !map.get(pos).copied().unwrap_or(false)
     }
 }
 
@@ -211,53 +162,14 @@ struct Blizzard {
 }
 
 impl Blizzard {
-    fn pos_on_tick(&self, tick: isize, width: isize, height: isize) -> Point {
-        let width = width - 2;
-        let height = height - 2;
-        let x = self.origin.x - 1;
-        let y = self.origin.y - 1;
-        let Point { x, y } = match self.direction {
-            Direction::Left => {
-                let x = (x - tick) % width;
-                let x = if x < 0 { width + x } else { x };
-                Point { x, y }
-            }
-            Direction::Right => {
-                let x = (x + tick) % width;
-                Point { x, y }
-            }
-            Direction::Up => {
-                let y = (y - tick) % height;
-                let y = if y < 0 { height + y } else { y };
-                Point { x, y }
-            }
-            Direction::Down => {
-                let y = (y + tick) % height;
-                Point { x, y }
-            }
-        };
+    fn pos_on_tick(&self, tick: i32, width: i32, height: i32) -> Point {
+        let inner = Point::new(width - 2, height - 2);
+        let p = self.origin - 1 + self.direction.offset() * tick;
 
-        Point { x: x + 1, y: y + 1 }
+        Point::new(p.x.rem_euclid(inner.x), p.y.rem_euclid(inner.y)) + 1
     }
 }
 
-#[derive(Debug, Copy, Clone)]
-enum Direction {
-    Left,
-    Right,
-    Up,
-    Down,
-}
-
-#[derive(Debug, Copy, Clone)]
-enum Movement {
-    Wait,
-    Left,
-    Right,
-    Up,
-    Down,
-}
-
 #[test]
 fn test() {
     let input = r#"#.######
@@ -1,11 +1,15 @@
+use crate::Answer;
+
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> String {
     let sum = input.trim().lines().map(snafu_to_dec).sum();
 
     dec_to_snafu(sum)
 }
 
-pub fn part_two(_input: &str) -> &'static str {
-    "I did it!!!!"
+pub fn part_two(_input: &str) -> Answer {
+    Answer::NotApplicable
 }
 
 fn dec_to_snafu(mut input: i64) -> String {
@@ -1,66 +1,95 @@
-pub fn part_one(input: &str) -> u64 {
+use crate::ParseError;
+use crate::parse::{self, Parser};
+
+crate::solution!(part_one, part_two);
+
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
     const MAX_RED: u64 = 12;
     const MAX_GREEN: u64 = 13;
     const MAX_BLUE: u64 = 14;
 
     let mut valid_games = 0;
-    'line: for line in input.trim().lines() {
-        let line = &line["Game ".len()..];
-        let (id, line) = line.split_once(": ").unwrap();
-
-        for round in line.split("; ") {
-            for sample in round.split(", ") {
-                let (count, color) = sample.split_once(" ").unwrap();
-                let count = count.parse::<u64>().unwrap();
-
-                let valid = match color {
-                    "red" if count > MAX_RED => false,
-                    "green" if count > MAX_GREEN => false,
-                    "blue" if count > MAX_BLUE => false,
-                    _ => true,
-                };
-
-                if !valid {
-                    continue 'line;
-                }
+    'line: for game in parse::lines(input, Game::parse)? {
+        for &(count, color) in &game.samples {
+            let valid = match color {
+                Color::Red => count <= MAX_RED,
+                Color::Green => count <= MAX_GREEN,
+                Color::Blue => count <= MAX_BLUE,
+            };
+
+            if !valid {
+                continue 'line;
             }
         }
 
-        valid_games += id.parse::<u64>().unwrap();
+        valid_games += game.id;
     }
 
-    valid_games
+    Ok(valid_games)
 }
 
-pub fn part_two(input: &str) -> u64 {
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
     let mut power_level = 0;
 
-    for line in input.trim().lines() {
-        let line = &line["Game ".len()..];
-        let (_id, line) = line.split_once(": ").unwrap();
-
+    for game in parse::lines(input, Game::parse)? {
         let mut min_red = 0;
         let mut min_green = 0;
         let mut min_blue = 0;
 
-        for round in line.split("; ") {
-            for sample in round.split(", ") {
-                let (count, color) = sample.split_once(" ").unwrap();
-                let count = count.parse().unwrap();
-
-                match color {
-                    "red" if count > min_red => min_red = count,
-                    "green" if count > min_green => min_green = count,
-                    "blue" if count > min_blue => min_blue = count,
-                    _ => (),
-                }
+        for &(count, color) in &game.samples {
+            match color {
+                Color::Red if count > min_red => min_red = count,
+                Color::Green if count > min_green => min_green = count,
+                Color::Blue if count > min_blue => min_blue = count,
+                _ => (),
             }
         }
 
         power_level += min_red * min_green * min_blue;
     }
 
-    power_level
+    Ok(power_level)
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Color {
+    Red,
+    Green,
+    Blue,
+}
+
+struct Game {
+    id: u64,
+    samples: Vec<(u64, Color)>,
+}
+
+impl Game {
+    fn parse(p: &mut Parser) -> Result<Self, ParseError> {
+        p.token("Game ")?;
+        let id = p.int()?;
+        p.token(": ")?;
+        let rounds = p.separated("; ", |p| p.separated(", ", parse_sample))?;
+        let samples = rounds.into_iter().flatten().collect();
+
+        Ok(Game { id, samples })
+    }
+}
+
+fn parse_sample(p: &mut Parser) -> Result<(u64, Color), ParseError> {
+    let count = p.int()?;
+    p.token(" ")?;
+
+    let color = if p.try_token("red") {
+        Color::Red
+    } else if p.try_token("green") {
+        Color::Green
+    } else if p.try_token("blue") {
+        Color::Blue
+    } else {
+        return Err(p.error("unknown color"));
+    };
+
+    Ok((count, color))
 }
 
 #[test]
@@ -71,6 +100,6 @@ Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
 Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"#;
 
-    assert_eq!(8, part_one(input));
-    assert_eq!(2286, part_two(input));
+    assert_eq!(Ok(8), part_one(input));
+    assert_eq!(Ok(2286), part_two(input));
 }
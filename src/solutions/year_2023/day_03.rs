@@ -1,4 +1,7 @@
 use crate::HashSet;
+use crate::grid::{Grid, Point};
+
+crate::solution!(part_one, part_two);
 
 pub fn part_one(input: &str) -> u64 {
     let map = Map::new(input);
@@ -32,82 +35,53 @@ impl Cell {
 }
 
 struct Map {
-    cells: Vec<Cell>,
-    width: usize,
-    height: usize,
+    cells: Grid<Cell>,
     numbers: Vec<u64>,
 }
 
 impl Map {
     fn new(input: &str) -> Self {
-        let lines = input.trim();
-        let mut cells = Vec::new();
-        let mut width = None;
-        let mut x = 0;
-        let mut y = 0;
-
-        let mut value = 0;
-
+        let chars = Grid::parse(input.trim(), |c| c.is_ascii().then_some(c as u8))
+            .expect("valid grid");
+        let mut cells = Vec::with_capacity(chars.cells().len());
         let mut numbers = Vec::new();
-        let mut was_in_num = false;
 
-        for b in lines.as_bytes() {
+        for row in chars.rows() {
             let mut in_num = false;
-            let cell = match b {
-                b'0'..=b'9' => {
-                    value *= 10;
-                    value += (b - b'0') as u64;
-                    in_num = true;
-                    Cell::Number(numbers.len())
-                }
-                b'.' => Cell::Empty,
-                b'\n' => {
-                    if width.is_none() {
-                        width = Some(x);
+            for &b in row {
+                let cell = match b {
+                    b'0'..=b'9' => {
+                        if !in_num {
+                            numbers.push(0);
+                        }
+                        let value = numbers.last_mut().unwrap();
+                        *value = *value * 10 + (b - b'0') as u64;
+                        Cell::Number(numbers.len() - 1)
                     }
-                    x = 0;
-                    y += 1;
+                    b'.' => Cell::Empty,
+                    b'*' => Cell::Gear,
+                    _ => Cell::Symbol,
+                };
 
-                    if was_in_num {
-                        numbers.push(value);
-                    }
-
-                    value = 0;
-                    was_in_num = false;
-                    continue;
-                }
-                b'*' => Cell::Gear,
-                _ => Cell::Symbol,
-            };
-
-            cells.push(cell);
-            if !in_num && was_in_num {
-                numbers.push(value);
-                value = 0;
+                in_num = matches!(cell, Cell::Number(_));
+                cells.push(cell);
             }
-            was_in_num = in_num;
-            x += 1;
         }
-        let height = (y + 1) as usize;
-        let width = width.unwrap() as usize;
 
         Self {
-            cells,
-            width,
-            height,
+            cells: Grid::from_vec(chars.width(), chars.height(), cells),
             numbers,
         }
     }
 
     fn part_one(&self) -> u64 {
         let mut matches = HashSet::new();
-        for y in 0..self.height as i32 {
-            for x in 0..self.width as i32 {
-                if let Some(Cell::Number(n)) = self.get(x, y) {
-                    if !matches.contains(&n) && self.neighbors(x, y).any(Cell::is_symbolic) {
-                        matches.insert(n);
-                    }
-                }
+        for (p, cell) in self.cells.iter() {
+            if let Cell::Number(n) = *cell
+                && !matches.contains(&n)
+                && self.neighbors(p).any(Cell::is_symbolic)
+            {
+                matches.insert(n);
             }
         }
 
@@ -117,52 +91,23 @@ impl Map {
     fn part_two(&self) -> u64 {
         let mut total = 0;
         let mut matches = HashSet::new();
-        for y in 0..self.height as i32 {
-            for x in 0..self.width as i32 {
-                if let Some(Cell::Gear) = self.get(x, y) {
-                    matches.extend(self.neighbors(x, y).filter_map(Cell::number));
-
-                    if matches.len() == 2 {
-                        total += matches.iter().map(|n| self.numbers[*n]).product::<u64>();
-                    }
+        for (p, cell) in self.cells.iter() {
+            if let Cell::Gear = cell {
+                matches.extend(self.neighbors(p).filter_map(Cell::number));
 
-                    matches.clear();
+                if matches.len() == 2 {
+                    total += matches.iter().map(|n| self.numbers[*n]).product::<u64>();
                 }
+
+                matches.clear();
             }
         }
 
         total
     }
 
-    fn get(&self, x: i32, y: i32) -> Option<Cell> {
-        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
-            None
-        } else {
-            let idx = y as usize * self.width + x as usize;
-            Some(self.cells[idx])
-        }
-    }
-
-    fn neighbors(&self, x: i32, y: i32) -> impl Iterator<Item = Cell> + '_ {
-        let mut i = 0;
-        std::iter::from_fn(move || loop {
-            let (x, y) = match i {
-                0 => (x - 1, y - 1),
-                1 => (x - 1, y),
-                2 => (x - 1, y + 1),
-                3 => (x, y - 1),
-                4 => (x, y + 1),
-                5 => (x + 1, y - 1),
-                6 => (x + 1, y),
-                7 => (x + 1, y + 1),
-                _ => return None,
-            };
-            i += 1;
-
-            if let Some(cell) = self.get(x, y) {
-                return Some(cell);
-            }
-        })
+    fn neighbors(&self, p: Point) -> impl Iterator<Item = Cell> + '_ {
+        self.cells.neighbors_diagonal(p).map(|n| self.cells[n])
     }
 }
 
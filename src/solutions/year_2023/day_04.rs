@@ -1,19 +1,22 @@
-use crate::{HashMap, HashSet};
-pub fn part_one(input: &str) -> u32 {
-    input
-        .trim()
-        .lines()
-        .map(|s| s.parse::<Line>().unwrap().winning_count)
+use crate::parse::{self, Parser};
+use crate::{HashMap, HashSet, ParseError};
+
+crate::solution!(part_one, part_two);
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    let score = parse::lines(input, Line::parse)?
+        .into_iter()
+        .map(|line| line.winning_count)
         .filter(|c| *c > 0)
         .map(|c| 2u32.pow(c - 1))
-        .sum()
+        .sum();
+
+    Ok(score)
 }
 
-pub fn part_two(input: &str) -> u32 {
-    let lines = input.trim().lines();
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
     let mut cards = HashMap::new();
 
-    for line in lines.map(|s| s.parse::<Line>().unwrap()) {
+    for line in parse::lines(input, Line::parse)? {
         let count = line.winning_count + 1;
         let multiplier = *cards.entry(line.id).or_insert(1);
 
@@ -23,7 +26,7 @@ pub fn part_two(input: &str) -> u32 {
         }
     }
 
-    cards.values().sum()
+    Ok(cards.values().sum())
 }
 
 struct Line {
@@ -31,20 +34,18 @@ struct Line {
     winning_count: u32,
 }
 
-#[derive(Debug, Copy, Clone)]
-struct LineParseErr;
-
-impl std::str::FromStr for Line {
-    type Err = LineParseErr;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (_, s) = s.split_once(" ").ok_or(LineParseErr)?;
-        let (id, s) = s.split_once(": ").ok_or(LineParseErr)?;
-        let (winners, mine) = s.split_once(" | ").ok_or(LineParseErr)?;
-
-        let id = id.trim().parse().map_err(|_| LineParseErr)?;
-        let winners = collect_numbers(winners).collect::<HashSet<_>>();
-        let winning_count = collect_numbers(mine)
+impl Line {
+    fn parse(p: &mut Parser) -> Result<Self, ParseError> {
+        p.token("Card")?;
+        p.skip_whitespace();
+        let id = p.int()?;
+        p.token(":")?;
+        let winners = p.many(number).into_iter().collect::<HashSet<u32>>();
+        p.skip_whitespace();
+        p.token("|")?;
+        let winning_count = p
+            .many(number)
+            .into_iter()
             .filter(|n| winners.contains(n))
             .count() as u32;
 
@@ -52,8 +53,9 @@ impl std::str::FromStr for Line {
     }
 }
 
-fn collect_numbers(s: &str) -> impl Iterator<Item = u32> + '_ {
-    s.split(" ").filter_map(|s| s.parse().ok())
+fn number(p: &mut Parser) -> Result<u32, ParseError> {
+    p.skip_whitespace();
+    p.int()
 }
 
 #[test]
@@ -66,6 +68,6 @@ Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
 "#;
 
-    assert_eq!(13, part_one(input));
-    assert_eq!(30, part_two(input));
+    assert_eq!(Ok(13), part_one(input));
+    assert_eq!(Ok(30), part_two(input));
 }
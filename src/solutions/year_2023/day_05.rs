@@ -1,33 +1,19 @@
-pub fn part_one(input: &str) -> i64 {
-    let (seeds, rest) = input.trim().split_once("\n\n").unwrap();
-    let mut seeds: Vec<_> = seeds["seeds: ".len()..]
-        .split(" ")
-        .filter_map(|s| s.parse::<i64>().ok())
-        .collect();
-
-    let mut forwards = Vec::new();
-    let mut mappings = Vec::new();
+use crate::ParseError;
+use crate::parse::Parser;
 
-    for map in rest.split("\n\n") {
-        let mut map_lines = map.lines();
-        let _names = map_lines.next();
+crate::solution!(part_one, part_two);
 
-        mappings.clear();
-        for mapping in map_lines {
-            let mut mapping = mapping.split(" ").map(|n| n.parse::<i64>().unwrap());
+pub fn part_one(input: &str) -> Result<i64, ParseError> {
+    let (mut seeds, maps) = parse(input)?;
 
-            let dst = mapping.next().unwrap();
-            let src = mapping.next().unwrap();
-            let len = mapping.next().unwrap();
-
-            mappings.push((dst, src, len));
-        }
+    let mut forwards = Vec::new();
 
+    for mappings in &maps {
         'seed: for seed in seeds.iter().copied() {
-            for (dst, src, len) in mappings.iter().copied() {
-                let s = seed - src;
-                if s < len && s >= 0 {
-                    forwards.push(s + dst);
+            for mapping in mappings {
+                let s = seed - mapping.src;
+                if s < mapping.len && s >= 0 {
+                    forwards.push(s + mapping.dst);
                     continue 'seed;
                 }
             }
@@ -39,40 +25,24 @@ pub fn part_one(input: &str) -> i64 {
         forwards.clear();
     }
 
-    seeds.into_iter().min().unwrap_or(0)
+    Ok(seeds.into_iter().min().unwrap_or(0))
 }
 
-pub fn part_two(input: &str) -> i64 {
-    let (seeds, rest) = input.trim().split_once("\n\n").unwrap();
-    let mut seeds = seeds["seeds: ".len()..]
-        .split(" ")
-        .filter_map(|s| s.parse::<i64>().ok());
+pub fn part_two(input: &str) -> Result<i64, ParseError> {
+    let (seeds, maps) = parse(input)?;
 
-    let mut ranges = Vec::new();
-    while let Some(start) = seeds.next() {
-        let len = seeds.next().unwrap();
-        ranges.push(Range { start, len })
-    }
+    let mut ranges: Vec<_> = seeds
+        .chunks_exact(2)
+        .map(|pair| Range {
+            start: pair[0],
+            len: pair[1],
+        })
+        .collect();
 
     let mut forwards = Vec::new();
-    let mut mappings = Vec::new();
     let mut remains = Vec::new();
 
-    for map in rest.split("\n\n") {
-        let mut map_lines = map.lines();
-        let _name = map_lines.next();
-
-        mappings.clear();
-        for mapping in map_lines {
-            let mut mapping = mapping.split(" ").map(|n| n.parse::<i64>().unwrap());
-
-            let dst = mapping.next().unwrap();
-            let src = mapping.next().unwrap();
-            let len = mapping.next().unwrap();
-
-            mappings.push(Mapping { src, dst, len });
-        }
-
+    for mappings in &maps {
         let mut changed = true;
         while changed {
             changed = false;
@@ -95,7 +65,25 @@ pub fn part_two(input: &str) -> i64 {
         ranges.append(&mut forwards);
     }
 
-    ranges.into_iter().map(|r| r.start).min().unwrap_or(0)
+    Ok(ranges.into_iter().map(|r| r.start).min().unwrap_or(0))
+}
+
+fn parse(input: &str) -> Result<(Vec<i64>, Vec<Vec<Mapping>>), ParseError> {
+    let mut p = Parser::new(input);
+    let mut header = p.section().ok_or_else(|| p.error("expected the seeds"))?;
+    header.token("seeds: ")?;
+    let seeds = header.separated(" ", Parser::int)?;
+    header.end()?;
+
+    let maps = p
+        .sections()
+        .map(|mut map| {
+            let _name = map.line();
+            map.parse_lines(Mapping::parse)
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok((seeds, maps))
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -118,6 +106,16 @@ struct Mapping {
 }
 
 impl Mapping {
+    fn parse(p: &mut Parser) -> Result<Self, ParseError> {
+        let dst = p.int()?;
+        p.token(" ")?;
+        let src = p.int()?;
+        p.token(" ")?;
+        let len = p.int()?;
+
+        Ok(Mapping { src, dst, len })
+    }
+
     fn src_end(&self) -> i64 {
         self.src + self.len
     }
@@ -195,6 +193,6 @@ humidity-to-location map:
 56 93 4
 "#;
 
-    assert_eq!(35, part_one(input));
-    assert_eq!(46, part_two(input));
+    assert_eq!(Ok(35), part_one(input));
+    assert_eq!(Ok(46), part_two(input));
 }
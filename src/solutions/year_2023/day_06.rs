@@ -1,17 +1,14 @@
-pub fn part_one(input: &str) -> u64 {
-    let (times, distances) = input.trim().split_once("\n").unwrap();
-    let times = times["Time:".len()..]
-        .split(' ')
-        .filter(|n| !n.is_empty())
-        .map(|n| n.parse::<u64>().unwrap());
-    let distances = distances["Distance:".len()..]
-        .split(' ')
-        .filter(|n| !n.is_empty())
-        .map(|n| n.parse::<u64>().unwrap());
+use crate::ParseError;
+use crate::parse::Parser;
+
+crate::solution!(part_one, part_two);
+
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
+    let (times, distances) = parse(input)?;
 
     let mut counts = 1;
 
-    for (time, distance) in times.zip(distances) {
+    for (time, distance) in times.into_iter().zip(distances) {
         let mut count = 0;
         for hold_time in 1..time {
             let remaining_time = time - hold_time;
@@ -25,22 +22,13 @@ pub fn part_one(input: &str) -> u64 {
         counts *= count;
     }
 
-    counts
+    Ok(counts)
 }
 
-pub fn part_two(input: &str) -> u64 {
-    let (time, distance) = input.trim().split_once("\n").unwrap();
-    let time = time
-        .chars()
-        .filter(char::is_ascii_digit)
-        .collect::<String>();
-    let distance = distance
-        .chars()
-        .filter(char::is_ascii_digit)
-        .collect::<String>();
-
-    let time = time.parse::<u64>().unwrap();
-    let distance = distance.parse::<u64>().unwrap();
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
+    let (times, distances) = parse(input)?;
+    let time = concat(&times);
+    let distance = concat(&distances);
 
     let mut count = 0;
     for hold_time in 1..time {
@@ -53,7 +41,32 @@ pub fn part_two(input: &str) -> u64 {
         }
     }
 
-    count
+    Ok(count)
+}
+
+fn parse(input: &str) -> Result<(Vec<u64>, Vec<u64>), ParseError> {
+    let mut p = Parser::new(input);
+    p.token("Time:")?;
+    let times = p.many(number);
+    p.skip_whitespace();
+    p.token("Distance:")?;
+    let distances = p.many(number);
+    p.skip_whitespace();
+    p.end()?;
+
+    Ok((times, distances))
+}
+
+fn number(p: &mut Parser) -> Result<u64, ParseError> {
+    p.skip_whitespace();
+    p.int()
+}
+
+// part two reads each line as a single number with the spaces removed
+fn concat(numbers: &[u64]) -> u64 {
+    numbers.iter().fold(0, |acc, &n| {
+        acc * 10u64.pow(n.checked_ilog10().unwrap_or(0) + 1) + n
+    })
 }
 
 #[test]
@@ -62,6 +75,6 @@ fn test() {
 Distance:  9  40  200
 "#;
 
-    assert_eq!(288, part_one(input));
-    assert_eq!(71503, part_two(input));
+    assert_eq!(Ok(288), part_one(input));
+    assert_eq!(Ok(71503), part_two(input));
 }
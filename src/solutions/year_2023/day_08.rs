@@ -1,15 +1,14 @@
-use crate::HashMap;
+use crate::math::lcm_iter;
+use crate::parse::Parser;
+use crate::{HashMap, ParseError};
 
-pub fn part_one(input: &str) -> usize {
-    let (directions, chart) = input.split_once("\n\n").unwrap();
-    let directions = directions.chars().map(Direction::from).cycle();
+crate::solution!(part_one, part_two);
 
-    let route_map: HashMap<_, _> = chart
-        .trim()
-        .lines()
-        .map(Node::new)
-        .map(|n| (n.name, n.values))
-        .collect();
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    let (directions, chart) = parse(input)?;
+    let directions = directions.into_iter().cycle();
+
+    let route_map: HashMap<_, _> = chart.into_iter().map(|n| (n.name, n.values)).collect();
 
     let mut node = NodeName::start_node();
 
@@ -22,21 +21,21 @@ pub fn part_one(input: &str) -> usize {
         };
 
         if node.is_final_node() {
-            return n + 1;
+            return Ok(n + 1);
         }
     }
 
     unreachable!()
 }
 
-pub fn part_two(input: &str) -> usize {
-    let (directions, chart) = input.split_once("\n\n").unwrap();
-    let directions = directions.chars().map(Direction::from).cycle();
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    let (directions, chart) = parse(input)?;
+    let directions = directions.into_iter().cycle();
 
     let mut route_map = HashMap::new();
     let mut nodes = Vec::new();
 
-    for node in chart.trim().lines().map(Node::new) {
+    for node in chart {
         if node.name.is_start_node() {
             nodes.push(node.name);
         }
@@ -61,7 +60,7 @@ pub fn part_two(input: &str) -> usize {
             if node.is_end_node() {
                 distances.push(n + 1);
                 if distances.len() == target_count {
-                    return lcm_iter(distances);
+                    return Ok(lcm_iter(distances));
                 }
             }
         }
@@ -70,18 +69,32 @@ pub fn part_two(input: &str) -> usize {
     unreachable!()
 }
 
+fn parse(input: &str) -> Result<(Vec<Direction>, Vec<Node<'_>>), ParseError> {
+    let mut p = Parser::new(input);
+    let mut header = p
+        .section()
+        .ok_or_else(|| p.error("expected the directions"))?;
+    let chart = p.section().ok_or_else(|| p.error("expected the nodes"))?;
+
+    let directions = header.many(Direction::parse);
+    header.end()?;
+
+    Ok((directions, chart.parse_lines(Node::parse)?))
+}
+
 #[derive(Debug, Copy, Clone)]
 enum Direction {
     Left,
     Right,
 }
 
-impl From<char> for Direction {
-    fn from(value: char) -> Self {
-        match value {
-            'L' => Direction::Left,
-            'R' => Direction::Right,
-            _ => unreachable!(),
+impl Direction {
+    fn parse(p: &mut Parser) -> Result<Self, ParseError> {
+        let start = *p;
+        match p.char()? {
+            'L' => Ok(Direction::Left),
+            'R' => Ok(Direction::Right),
+            _ => Err(start.error("expected a direction")),
         }
     }
 }
@@ -93,16 +106,18 @@ struct Node<'a> {
 }
 
 impl<'a> Node<'a> {
-    fn new(line: &'a str) -> Self {
-        let (name, rest) = line.split_once(" = ").unwrap();
-        let (left, right) = rest.split_once(", ").unwrap();
-        let left = NodeName(&left[1..4]);
-        let right = NodeName(&right[0..3]);
-
-        Self {
-            name: NodeName(name),
+    fn parse(p: &mut Parser<'a>) -> Result<Self, ParseError> {
+        let name = NodeName::parse(p);
+        p.token(" = (")?;
+        let left = NodeName::parse(p);
+        p.token(", ")?;
+        let right = NodeName::parse(p);
+        p.token(")")?;
+
+        Ok(Self {
+            name,
             values: Route { left, right },
-        }
+        })
     }
 }
 
@@ -110,6 +125,10 @@ impl<'a> Node<'a> {
 struct NodeName<'a>(&'a str);
 
 impl<'a> NodeName<'a> {
+    fn parse(p: &mut Parser<'a>) -> Self {
+        NodeName(p.take_while(|c| c.is_ascii_alphanumeric()))
+    }
+
     fn start_node() -> Self {
         NodeName("AAA")
     }
@@ -133,22 +152,6 @@ struct Route<'a> {
     right: NodeName<'a>,
 }
 
-fn gcd(a: usize, b: usize) -> usize {
-    let mut n = a.min(b);
-    while n > 1 {
-        if a % n == 0 && b % n == 0 {
-            return n;
-        }
-        n -= 1;
-    }
-
-    1
-}
-
-fn lcm_iter<I: IntoIterator<Item = usize>>(iter: I) -> usize {
-    iter.into_iter().fold(1, |acc, n| (acc * n) / gcd(acc, n))
-}
-
 #[test]
 fn test() {
     let input = r#"RL
@@ -161,7 +164,7 @@ EEE = (EEE, EEE)
 GGG = (GGG, GGG)
 ZZZ = (ZZZ, ZZZ)
 "#;
-    assert_eq!(2, part_one(input));
+    assert_eq!(Ok(2), part_one(input));
 
     let input = r#"LLR
 
@@ -169,7 +172,7 @@ AAA = (BBB, BBB)
 BBB = (AAA, ZZZ)
 ZZZ = (ZZZ, ZZZ)
 "#;
-    assert_eq!(6, part_one(input));
+    assert_eq!(Ok(6), part_one(input));
 
     let input = r#"LR
 
@@ -182,5 +185,5 @@ ZZZ = (ZZZ, ZZZ)
 22Z = (22B, 22B)
 XXX = (XXX, XXX)
 "#;
-    assert_eq!(6, part_two(input));
+    assert_eq!(Ok(6), part_two(input));
 }
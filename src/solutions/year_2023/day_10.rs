@@ -1,4 +1,7 @@
 use crate::HashSet;
+use crate::grid::{Direction, Grid, Point};
+
+crate::solution!(part_one, part_two);
 
 pub fn part_one(input: &str) -> i32 {
     let map = Map::new(input);
@@ -10,65 +13,49 @@ pub fn part_two(input: &str) -> i32 {
     map.count_internal_grounds()
 }
 
+// the order neighbors are searched in
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Right,
+    Direction::Left,
+];
+
 struct Map {
-    cells: Vec<Cell>,
-    width: usize,
-    height: usize,
-    start: (i32, i32),
+    cells: Grid<Cell>,
+    start: Point,
 }
 
 impl Map {
     fn new(input: &str) -> Self {
-        let mut height = 0;
-        let mut width = 0;
-        let mut cells = Vec::new();
-        let mut start = None;
-        for l in input.lines() {
-            if l.trim().is_empty() {
-                continue;
-            }
-            width = 0;
-            for c in l.chars() {
-                let cell = match c {
-                    'S' => {
-                        start = Some((width as i32, height as i32));
-                        Cell::Start
-                    }
-                    '.' => Cell::Ground,
-                    '|' => Cell::Pipe(Pipe::Vertical),
-                    '-' => Cell::Pipe(Pipe::Horizontal),
-                    'L' => Cell::Pipe(Pipe::NorthEast),
-                    'J' => Cell::Pipe(Pipe::NorthWest),
-                    '7' => Cell::Pipe(Pipe::SouthWest),
-                    'F' => Cell::Pipe(Pipe::SouthEast),
-                    _ => unreachable!(),
-                };
-
-                cells.push(cell);
-
-                width += 1;
-            }
-            height += 1;
-        }
-
-        let start = start.unwrap();
+        let cells = Grid::parse(input.trim(), |c| {
+            let cell = match c {
+                'S' => Cell::Start,
+                '.' => Cell::Ground,
+                '|' => Cell::Pipe(Pipe::Vertical),
+                '-' => Cell::Pipe(Pipe::Horizontal),
+                'L' => Cell::Pipe(Pipe::NorthEast),
+                'J' => Cell::Pipe(Pipe::NorthWest),
+                '7' => Cell::Pipe(Pipe::SouthWest),
+                'F' => Cell::Pipe(Pipe::SouthEast),
+                _ => return None,
+            };
+            Some(cell)
+        })
+        .expect("valid grid");
 
-        let mut map = Self {
-            cells,
-            width,
-            height,
-            start,
-        };
+        let start = cells.position(|c| *c == Cell::Start).unwrap();
 
-        let east = map.get_pipe(start.0 + 1, start.1);
-        let west = map.get_pipe(start.0 - 1, start.1);
-        let north = map.get_pipe(start.0, start.1 - 1);
-        let south = map.get_pipe(start.0, start.1 + 1);
+        let mut map = Self { cells, start };
 
-        let east = east.map(|e| e.west()).unwrap_or(false);
-        let west = west.map(|w| w.east()).unwrap_or(false);
-        let north = north.map(|n| n.south()).unwrap_or(false);
-        let south = south.map(|s| s.north()).unwrap_or(false);
+        let connects = |direction: Direction| {
+            map.get_pipe(start.step(direction))
+                .is_some_and(|pipe| pipe.connects(direction.reverse()))
+        };
+        let east = connects(Direction::Right);
+        let west = connects(Direction::Left);
+        let north = connects(Direction::Up);
+        let south = connects(Direction::Down);
 
         let start_pipe = if north && south {
             Pipe::Vertical
@@ -83,92 +70,35 @@ impl Map {
         } else if south && west {
             Pipe::SouthWest
         } else {
-            panic!("invalid start position: ({},{})", start.0, start.1);
+            panic!("invalid start position: {}", start);
         };
 
-        map.set(start.0, start.1, Cell::Pipe(start_pipe));
+        map.cells[start] = Cell::Pipe(start_pipe);
 
         map
     }
 
-    fn set(&mut self, x: i32, y: i32, value: Cell) {
-        if x < 0 || y < 0 {
-            return;
-        }
-
-        let x = x as usize;
-        let y = y as usize;
-
-        if x < self.width && y < self.height {
-            let idx = y * self.width + x;
-            if let Some(cell) = self.cells.get_mut(idx) {
-                *cell = value;
-            }
-        }
-    }
-
-    fn get(&self, x: i32, y: i32) -> Option<Cell> {
-        if x < 0 || y < 0 {
-            return None;
-        }
-
-        let x = x as usize;
-        let y = y as usize;
-
-        if x >= self.width || y >= self.height {
-            None
-        } else {
-            let idx = y * self.width + x;
-            self.cells.get(idx).copied()
-        }
-    }
-
-    fn get_pipe(&self, x: i32, y: i32) -> Option<Pipe> {
-        if let Some(Cell::Pipe(pipe)) = self.get(x, y) {
-            Some(pipe)
+    fn get_pipe(&self, p: Point) -> Option<Pipe> {
+        if let Some(Cell::Pipe(pipe)) = self.cells.get(p) {
+            Some(*pipe)
         } else {
             None
         }
     }
 
-    fn neighbors(&self, x: i32, y: i32) -> impl Iterator<Item = (i32, i32)> + '_ {
-        let mut i = 0;
-
-        let cur_pipe = self.get_pipe(x, y);
-
-        std::iter::from_fn(move || loop {
-            let cur_pipe = cur_pipe?;
-
-            let dir = match i {
-                0 => Direction::North,
-                1 => Direction::South,
-                2 => Direction::East,
-                3 => Direction::West,
-                _ => return None,
-            };
+    fn neighbors(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        let cur_pipe = self.get_pipe(p);
 
-            let (x_off, y_off) = dir.offset();
-            let point = (x + x_off, y + y_off);
+        DIRECTIONS.into_iter().filter_map(move |dir| {
+            let point = p.step(dir);
+            let next_pipe = self.get_pipe(point)?;
+            let connected = cur_pipe?.connects(dir) && next_pipe.connects(dir.reverse());
 
-            i += 1;
-
-            if let Some(next_pipe) = self.get_pipe(point.0, point.1) {
-                let connected = match dir {
-                    Direction::North if cur_pipe.north() && next_pipe.south() => true,
-                    Direction::South if cur_pipe.south() && next_pipe.north() => true,
-                    Direction::East if cur_pipe.east() && next_pipe.west() => true,
-                    Direction::West if cur_pipe.west() && next_pipe.east() => true,
-                    _ => false,
-                };
-
-                if connected {
-                    return Some(point);
-                }
-            }
+            connected.then_some(point)
         })
     }
 
-    fn walk_route(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+    fn walk_route(&self) -> impl Iterator<Item = Point> + '_ {
         let mut next = None;
         let mut last = None;
 
@@ -180,10 +110,7 @@ impl Map {
             let current = next.unwrap_or(self.start);
 
             if let Some(last) = last {
-                let path = self
-                    .neighbors(current.0, current.1)
-                    .find(|p| *p != last)
-                    .unwrap();
+                let path = self.neighbors(current).find(|p| *p != last).unwrap();
 
                 next = Some(path);
             }
@@ -198,25 +125,21 @@ impl Map {
         count as i32 / 2
     }
 
-    fn clean_clutter(&mut self, route: &HashSet<(i32, i32)>) {
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let (x, y) = (x as i32, y as i32);
-                if !route.contains(&(x, y)) {
-                    self.set(x, y, Cell::Ground);
-                }
+    fn clean_clutter(&mut self, route: &HashSet<Point>) {
+        for p in self.cells.points() {
+            if !route.contains(&p) {
+                self.cells[p] = Cell::Ground;
             }
         }
     }
 
-    fn get_inflated(&self, x: i32, y: i32) -> Option<InflatedCell> {
-        let real_x = x / 2;
-        let real_y = y / 2;
-        let off_x = x % 2;
-        let off_y = y % 2;
+    fn get_inflated(&self, p: Point) -> Option<InflatedCell> {
+        let real = Point::new(p.x / 2, p.y / 2);
+        let off_x = p.x % 2;
+        let off_y = p.y % 2;
         let on_grid = off_x == 0 && off_y == 0;
 
-        match self.get(real_x, real_y) {
+        match self.cells.get(real).copied() {
             Some(cell) if on_grid => Some(InflatedCell::from(cell)),
             Some(Cell::Pipe(p)) => match p {
                 Pipe::Vertical | Pipe::SouthWest | Pipe::SouthEast if off_x == 0 && off_y == 1 => {
@@ -234,31 +157,14 @@ impl Map {
         }
     }
 
-    fn inflated_neighbors(
-        &self,
-        x: i32,
-        y: i32,
-    ) -> impl Iterator<Item = ((i32, i32), InflatedCell)> + '_ {
-        let mut i = 0;
-
-        std::iter::from_fn(move || loop {
-            let dir = match i {
-                0 => Direction::North,
-                1 => Direction::South,
-                2 => Direction::East,
-                3 => Direction::West,
-                _ => return None,
-            };
-
-            let (x_off, y_off) = dir.offset();
-            let (x, y) = (x + x_off, y + y_off);
+    fn inflated_neighbors(&self, p: Point) -> impl Iterator<Item = (Point, InflatedCell)> + '_ {
+        DIRECTIONS.into_iter().filter_map(move |dir| {
+            let p = p.step(dir);
 
-            i += 1;
-
-            match self.get_inflated(x, y) {
-                Some(InflatedCell::Wall) => continue,
-                Some(cell) => return Some(((x, y), cell)),
-                None => return Some(((x, y), InflatedCell::Outside)),
+            match self.get_inflated(p) {
+                Some(InflatedCell::Wall) => None,
+                Some(cell) => Some((p, cell)),
+                None => Some((p, InflatedCell::Outside)),
             }
         })
     }
@@ -268,15 +174,13 @@ impl Map {
         self.clean_clutter(&route);
 
         let mut haystack = Vec::new();
-        let mut visited = HashSet::with_capacity(self.width * self.height - route.len());
+        let mut visited = HashSet::with_capacity(self.cells.cells().len() - route.len());
 
-        let grounds = (0..self.height)
-            .flat_map(|y| {
-                (0..self.width)
-                    .map(move |x| (x as i32, y as i32))
-                    .filter(|p| !route.contains(p))
-            })
-            .map(|(x, y)| (x * 2, y * 2));
+        let grounds = self
+            .cells
+            .points()
+            .filter(|p| !route.contains(p))
+            .map(|p| p * 2);
 
         for next in grounds {
             if visited.contains(&next) {
@@ -287,8 +191,8 @@ impl Map {
             haystack.clear();
             haystack.push((next, InflatedCell::Ground));
 
-            while let Some(((x, y), kind)) = haystack.pop() {
-                if !visited.insert((x, y)) {
+            while let Some((p, kind)) = haystack.pop() {
+                if !visited.insert(p) {
                     continue;
                 }
 
@@ -306,7 +210,7 @@ impl Map {
                     _ => (),
                 }
 
-                haystack.extend(self.inflated_neighbors(x, y));
+                haystack.extend(self.inflated_neighbors(p));
             }
 
             if let Some(count) = count {
@@ -336,25 +240,6 @@ impl From<Cell> for InflatedCell {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-enum Direction {
-    North,
-    South,
-    East,
-    West,
-}
-
-impl Direction {
-    fn offset(&self) -> (i32, i32) {
-        match self {
-            Direction::North => (0, -1),
-            Direction::South => (0, 1),
-            Direction::East => (1, 0),
-            Direction::West => (-1, 0),
-        }
-    }
-}
-
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 enum Pipe {
     Vertical,
@@ -373,6 +258,15 @@ enum Cell {
 }
 
 impl Pipe {
+    fn connects(&self, direction: Direction) -> bool {
+        match direction {
+            Direction::Up => self.north(),
+            Direction::Right => self.east(),
+            Direction::Down => self.south(),
+            Direction::Left => self.west(),
+        }
+    }
+
     fn north(&self) -> bool {
         matches!(self, Pipe::Vertical | Pipe::NorthWest | Pipe::NorthEast)
     }
@@ -1,3 +1,7 @@
+use crate::grid::{Grid, Point};
+
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> i32 {
     let (vert, horz) = input
         .trim()
@@ -33,105 +37,61 @@ enum Cell {
 }
 
 struct Map {
-    cells: Vec<Cell>,
-    width: usize,
-    height: usize,
+    cells: Grid<Cell>,
 }
 
 impl Map {
     fn new(input: &str) -> Self {
-        let mut cells = Vec::with_capacity(input.len());
-        let mut width = 0;
-        let mut height = 0;
-
-        for line in input.lines() {
-            height += 1;
-            width = line.len();
-
-            for c in line.chars() {
-                let cell = match c {
-                    '#' => Cell::Rock,
-                    '.' => Cell::Ash,
-                    _ => unreachable!(),
-                };
-
-                cells.push(cell);
-            }
-        }
-
-        Self {
-            cells,
-            width,
-            height,
-        }
+        let cells = Grid::parse(input, |c| match c {
+            '#' => Some(Cell::Rock),
+            '.' => Some(Cell::Ash),
+            _ => None,
+        })
+        .expect("valid grid");
+
+        Self { cells }
     }
 
-    fn get(&self, (x, y): (i32, i32)) -> Option<Cell> {
-        if x < 0 || y < 0 {
-            return None;
-        }
-
-        let x = x as usize;
-        let y = y as usize;
-
-        if x >= self.width || y >= self.height {
-            None
+    fn find_mirroring<const ERRORS: i32>(&self) -> Mirroring {
+        // a vertical mirror is a horizontal one once the columns become rows
+        if let Some(x) = mirrored_row::<ERRORS>(&self.cells.transpose()) {
+            Mirroring::Vertical(x)
+        } else if let Some(y) = mirrored_row::<ERRORS>(&self.cells) {
+            Mirroring::Horizontal(y)
         } else {
-            let idx = y * self.width + x;
-            self.cells.get(idx).copied()
+            unreachable!("no mirroring found");
         }
     }
+}
 
-    fn find_mirroring<const ERRORS: i32>(&self) -> Mirroring {
-        let width = self.width as i32;
-        let height = self.height as i32;
-
-        'outer: for x in 1..width {
-            let mut error_count = 0;
-            for x_off in 0..width {
-                for y in 0..height {
-                    let a = self.get((x - 1 - x_off, y));
-                    let b = self.get((x + x_off, y));
-
-                    if let Some((a, b)) = a.zip(b) {
-                        if a != b {
-                            error_count += 1;
-
-                            if error_count > ERRORS {
-                                continue 'outer;
-                            }
-                        }
-                    } else if error_count == ERRORS {
-                        return Mirroring::Vertical(x);
-                    }
-                }
-            }
-        }
+// the number of rows above the first horizontal mirror with exactly `ERRORS` mismatched cells
+fn mirrored_row<const ERRORS: i32>(cells: &Grid<Cell>) -> Option<i32> {
+    let width = cells.width() as i32;
+    let height = cells.height() as i32;
 
-        'outer: for y in 1..height {
-            let mut error_count = 0;
-            for y_off in 0..height {
-                for x in 0..width {
-                    let a = self.get((x, y - 1 - y_off));
-                    let b = self.get((x, y + y_off));
+    'outer: for y in 1..height {
+        let mut error_count = 0;
+        for y_off in 0..height {
+            for x in 0..width {
+                let a = cells.get(Point::new(x, y - 1 - y_off));
+                let b = cells.get(Point::new(x, y + y_off));
 
-                    if let Some((a, b)) = a.zip(b) {
-                        if a != b {
-                            error_count += 1;
+                if let Some((a, b)) = a.zip(b) {
+                    if a != b {
+                        error_count += 1;
 
-                            if error_count > ERRORS {
-                                continue 'outer;
-                            }
+                        if error_count > ERRORS {
+                            continue 'outer;
                         }
-                    } else if error_count == ERRORS {
-                        return Mirroring::Horizontal(y);
                     }
+                } else if error_count == ERRORS {
+                    return Some(y);
                 }
             }
         }
-
-        unreachable!("no mirroring found");
     }
+
+    None
 }
 
 #[derive(Debug, Copy, Clone)]
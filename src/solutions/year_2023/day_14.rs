@@ -1,3 +1,7 @@
+use crate::grid::{Grid, Point};
+
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> usize {
     let lines = input.trim().lines();
 
@@ -43,77 +47,46 @@ enum Cell {
     Blocked,
 }
 
+impl std::fmt::Display for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Cell::Empty => '.',
+            Cell::Rolling => 'O',
+            Cell::Blocked => '#',
+        };
+
+        write!(f, "{}", c)
+    }
+}
+
 struct Map {
-    cells: Vec<Cell>,
-    width: usize,
-    height: usize,
+    grid: Grid<Cell>,
     min_heights: Vec<i32>,
 }
 
 impl Map {
     fn new(input: &str) -> Self {
-        let mut width = 0;
-        let mut height = 0;
-        let mut cells = Vec::with_capacity(input.len());
+        let grid = Grid::parse(input.trim(), |c| match c {
+            '.' => Some(Cell::Empty),
+            'O' => Some(Cell::Rolling),
+            '#' => Some(Cell::Blocked),
+            _ => None,
+        })
+        .expect("valid map");
 
-        for line in input.trim().lines() {
-            if width < line.len() {
-                width = line.len();
-            }
-            height += 1;
+        let min_heights = vec![0; grid.width()];
 
-            for c in line.chars() {
-                let cell = match c {
-                    '.' => Cell::Empty,
-                    'O' => Cell::Rolling,
-                    '#' => Cell::Blocked,
-                    _ => unreachable!(),
-                };
-
-                cells.push(cell);
-            }
-        }
-
-        let min_heights = vec![0; width];
-
-        Map {
-            cells,
-            width,
-            height,
-            min_heights,
-        }
+        Map { grid, min_heights }
     }
 
     fn get<P: IntoPoint>(&self, point: P) -> Option<Cell> {
-        let (x, y) = point.into(self.width, self.height);
-
-        if x < 0 || y < 0 {
-            return None;
-        }
-
-        let (x, y) = (x as usize, y as usize);
-
-        if x >= self.width || y >= self.height {
-            None
-        } else {
-            let idx = y * self.width + x;
-            self.cells.get(idx).copied()
-        }
+        let (x, y) = point.into(self.grid.width(), self.grid.height());
+        self.grid.get(Point::new(x, y)).copied()
     }
 
     fn set<P: IntoPoint>(&mut self, point: P, cell: Cell) {
-        let (x, y) = point.into(self.width, self.height);
-
-        if x < 0 || y < 0 {
-            return;
-        }
-
-        let (x, y) = (x as usize, y as usize);
-
-        if x < self.width && y < self.height {
-            let idx = y * self.width + x;
-            self.cells[idx] = cell;
-        }
+        let (x, y) = point.into(self.grid.width(), self.grid.height());
+        self.grid.set(Point::new(x, y), cell);
     }
 
     fn move_to<S: IntoPoint, D: IntoPoint>(&mut self, src: S, dst: D) {
@@ -128,8 +101,8 @@ impl Map {
     }
 
     fn tilt(&mut self, origin: Orientation) {
-        let width = self.width as i32;
-        let height = self.height as i32;
+        let width = self.grid.width() as i32;
+        let height = self.grid.height() as i32;
         self.min_heights.fill(0);
 
         for y in 0..height {
@@ -165,7 +138,7 @@ impl Map {
             if !dupe_found {
                 use std::hash::{Hash, Hasher};
                 let mut h = crate::Hasher::default();
-                self.cells.hash(&mut h);
+                self.grid.hash(&mut h);
                 let hash = h.finish();
 
                 if let Some(dupe) = dupes.insert(hash, i) {
@@ -185,8 +158,8 @@ impl Map {
     }
 
     fn north_load(&self) -> i32 {
-        let width = self.width as i32;
-        let height = self.height as i32;
+        let width = self.grid.width() as i32;
+        let height = self.grid.height() as i32;
 
         let mut sum = 0;
         let mut count = 0;
@@ -207,22 +180,7 @@ impl Map {
 
     #[allow(dead_code)]
     fn dump(&self) {
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let (x, y) = (x as i32, y as i32);
-                let cell = self.get(Orientation::North(x, y));
-
-                let c = match cell {
-                    Some(Cell::Empty) => '.',
-                    Some(Cell::Rolling) => 'O',
-                    Some(Cell::Blocked) => '#',
-                    None => todo!(),
-                };
-
-                print!("{c}");
-            }
-            println!();
-        }
+        print!("{}", self.grid);
     }
 }
 
@@ -1,8 +1,11 @@
 use crate::HashSet;
+use crate::grid::{Direction, Grid, Point};
+
+crate::solution!(part_one, part_two);
 
 pub fn part_one(input: &str) -> usize {
     let map = Map::new(input);
-    map.solve(Ray::East(0, 0))
+    map.solve(Ray::new(Point::new(0, 0), Direction::Right))
 }
 
 pub fn part_two(input: &str) -> usize {
@@ -21,53 +24,42 @@ enum Cell {
 
 impl Cell {
     fn interact(&self, ray: Ray) -> Interation {
-        match (*self, ray) {
-            (Cell::Empty, ray) => Interation::Ignore(ray),
-            (Cell::UpwardMirror, Ray::North(x, y)) => Interation::Reflect(Ray::East(x, y)),
-            (Cell::UpwardMirror, Ray::South(x, y)) => Interation::Reflect(Ray::West(x, y)),
-            (Cell::UpwardMirror, Ray::East(x, y)) => Interation::Reflect(Ray::North(x, y)),
-            (Cell::UpwardMirror, Ray::West(x, y)) => Interation::Reflect(Ray::South(x, y)),
-            (Cell::DownwardMirror, Ray::North(x, y)) => Interation::Reflect(Ray::West(x, y)),
-            (Cell::DownwardMirror, Ray::South(x, y)) => Interation::Reflect(Ray::East(x, y)),
-            (Cell::DownwardMirror, Ray::East(x, y)) => Interation::Reflect(Ray::South(x, y)),
-            (Cell::DownwardMirror, Ray::West(x, y)) => Interation::Reflect(Ray::North(x, y)),
-            (Cell::VertSplitter, Ray::East(x, y) | Ray::West(x, y)) => {
-                Interation::Split(Ray::North(x, y), Ray::South(x, y))
-            }
-            (Cell::VertSplitter, ray) => Interation::Ignore(ray),
-            (Cell::HorzSplitter, Ray::North(x, y) | Ray::South(x, y)) => {
-                Interation::Split(Ray::East(x, y), Ray::West(x, y))
-            }
-            (Cell::HorzSplitter, ray) => Interation::Ignore(ray),
+        use Direction::*;
+
+        let turn = |direction| Interation::Reflect(Ray::new(ray.point, direction));
+        let split = |a, b| Interation::Split(Ray::new(ray.point, a), Ray::new(ray.point, b));
+
+        match (*self, ray.direction) {
+            (Cell::Empty, _) => Interation::Ignore(ray),
+            (Cell::UpwardMirror, Up) => turn(Right),
+            (Cell::UpwardMirror, Down) => turn(Left),
+            (Cell::UpwardMirror, Right) => turn(Up),
+            (Cell::UpwardMirror, Left) => turn(Down),
+            (Cell::DownwardMirror, Up) => turn(Left),
+            (Cell::DownwardMirror, Down) => turn(Right),
+            (Cell::DownwardMirror, Right) => turn(Down),
+            (Cell::DownwardMirror, Left) => turn(Up),
+            (Cell::VertSplitter, Right | Left) => split(Up, Down),
+            (Cell::VertSplitter, _) => Interation::Ignore(ray),
+            (Cell::HorzSplitter, Up | Down) => split(Right, Left),
+            (Cell::HorzSplitter, _) => Interation::Ignore(ray),
         }
     }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-enum Ray {
-    North(i32, i32),
-    South(i32, i32),
-    East(i32, i32),
-    West(i32, i32),
+struct Ray {
+    point: Point,
+    direction: Direction,
 }
 
 impl Ray {
-    fn step(&self) -> Ray {
-        match *self {
-            Ray::North(x, y) => Ray::North(x, y - 1),
-            Ray::South(x, y) => Ray::South(x, y + 1),
-            Ray::East(x, y) => Ray::East(x + 1, y),
-            Ray::West(x, y) => Ray::West(x - 1, y),
-        }
+    fn new(point: Point, direction: Direction) -> Self {
+        Self { point, direction }
     }
 
-    fn point(&self) -> (i32, i32) {
-        match *self {
-            Ray::North(x, y) => (x, y),
-            Ray::South(x, y) => (x, y),
-            Ray::East(x, y) => (x, y),
-            Ray::West(x, y) => (x, y),
-        }
+    fn step(&self) -> Ray {
+        Ray::new(self.point.step(self.direction), self.direction)
     }
 }
 
@@ -79,59 +71,22 @@ enum Interation {
 }
 
 struct Map {
-    cells: Vec<Cell>,
-    width: usize,
-    height: usize,
+    cells: Grid<Cell>,
 }
 
 impl Map {
     fn new(input: &str) -> Self {
-        let mut width = 0;
-        let mut height = 0;
-        let mut cells = Vec::with_capacity(input.len());
-
-        for line in input.trim().lines() {
-            if width < line.len() {
-                width = line.len();
-            }
-
-            height += 1;
-
-            for c in line.chars() {
-                let cell = match c {
-                    '.' => Cell::Empty,
-                    '/' => Cell::UpwardMirror,
-                    '\\' => Cell::DownwardMirror,
-                    '|' => Cell::VertSplitter,
-                    '-' => Cell::HorzSplitter,
-                    _ => unreachable!(),
-                };
-
-                cells.push(cell)
-            }
-        }
-
-        Self {
-            cells,
-            width,
-            height,
-        }
-    }
-
-    fn get(&self, (x, y): (i32, i32)) -> Option<Cell> {
-        if x < 0 || y < 0 {
-            return None;
-        }
-
-        let (x, y) = (x as usize, y as usize);
-
-        if x >= self.width || y >= self.height {
-            None
-        } else {
-            let idx = y * self.width + x;
-
-            self.cells.get(idx).copied()
-        }
+        let cells = Grid::parse(input.trim(), |c| match c {
+            '.' => Some(Cell::Empty),
+            '/' => Some(Cell::UpwardMirror),
+            '\\' => Some(Cell::DownwardMirror),
+            '|' => Some(Cell::VertSplitter),
+            '-' => Some(Cell::HorzSplitter),
+            _ => None,
+        })
+        .expect("valid grid");
+
+        Self { cells }
     }
 
     fn solve(&self, initial: Ray) -> usize {
@@ -139,7 +94,7 @@ impl Map {
         let mut visited = HashSet::new();
 
         while let Some(ray) = rays.pop() {
-            if let Some(cell) = self.get(ray.point()) {
+            if let Some(cell) = self.cells.get(ray.point) {
                 if !visited.insert(ray) {
                     continue;
                 }
@@ -155,20 +110,21 @@ impl Map {
             }
         }
 
-        let unique_points: HashSet<_> = visited.into_iter().map(|r| r.point()).collect();
+        let unique_points: HashSet<_> = visited.into_iter().map(|r| r.point).collect();
 
         unique_points.len()
     }
 
     fn max(&self) -> usize {
-        let width = self.width as i32 - 1;
-        let height = self.height as i32 - 1;
+        let width = self.cells.width() as i32 - 1;
+        let height = self.cells.height() as i32 - 1;
+        let ray = |x, y, direction| Ray::new(Point::new(x, y), direction);
 
         (0..=width)
-            .map(|x| Ray::South(x, 0))
-            .chain((0..=width).map(|x| Ray::North(x, height)))
-            .chain((0..=height).map(|y| Ray::East(0, y)))
-            .chain((0..=height).map(|y| Ray::West(width, y)))
+            .map(|x| ray(x, 0, Direction::Down))
+            .chain((0..=width).map(|x| ray(x, height, Direction::Up)))
+            .chain((0..=height).map(|y| ray(0, y, Direction::Right)))
+            .chain((0..=height).map(|y| ray(width, y, Direction::Left)))
             .map(|ray| self.solve(ray))
             .max()
             .unwrap()
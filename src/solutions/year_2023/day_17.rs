@@ -1,6 +1,7 @@
-use std::collections::BinaryHeap;
+use crate::grid::{Direction, Grid, Point};
+use crate::search;
 
-use crate::HashSet;
+crate::solution!(part_one, part_two);
 
 pub fn part_one(input: &str) -> i32 {
     let map = Map::new(input);
@@ -13,96 +14,40 @@ pub fn part_two(input: &str) -> i32 {
 }
 
 struct Map {
-    cells: Vec<i32>,
-    width: usize,
-    height: usize,
+    cells: Grid<i32>,
 }
 
 impl Map {
     fn new(input: &str) -> Self {
-        let mut cells = Vec::with_capacity(input.len());
-        let mut width = 0;
-        let mut height = 0;
+        let cells =
+            Grid::parse(input.trim(), |c| c.to_digit(10).map(|n| n as i32)).expect("valid grid");
 
-        for line in input.trim().lines() {
-            if width < line.len() {
-                width = line.len();
-            }
-
-            height += 1;
-
-            for c in line.bytes() {
-                let n = match c {
-                    b'0'..=b'9' => c - b'0',
-                    _ => unreachable!(),
-                };
-
-                cells.push(n as i32);
-            }
-        }
-
-        Self {
-            cells,
-            width,
-            height,
-        }
-    }
-
-    fn get(&self, (x, y): (i32, i32)) -> Option<i32> {
-        if x < 0 || y < 0 {
-            return None;
-        }
-
-        let (x, y) = (x as usize, y as usize);
-
-        if x >= self.width || y >= self.height {
-            None
-        } else {
-            let idx = y * self.width + x;
-            self.cells.get(idx).copied()
-        }
+        Self { cells }
     }
 
     fn find_route<const MIN: u32, const MAX: u32>(&self) -> i32 {
-        let cursor = Cursor::<MIN, MAX>::new();
-        let end = (self.width as i32 - 1, self.height as i32 - 1);
-
-        let mut visited = HashSet::new();
-        let mut haystack = BinaryHeap::new();
-        haystack.push(Sorted(0, cursor));
-
-        while let Some(Sorted(cost, next)) = haystack.pop() {
-            if !visited.insert(next) {
-                continue;
-            }
-
-            if next.point == end && next.dir_moves >= MIN {
-                return cost;
-            }
-
-            let moves = [
-                Direction::Left,
-                Direction::Right,
-                Direction::Up,
-                Direction::Down,
-            ]
-            .iter()
-            .filter_map(|&dir| next.make_move(dir))
-            .filter_map(|cursor| {
-                self.get(cursor.point)
-                    .map(|cell_cost| Sorted(cell_cost + cost, cursor))
-            });
-
-            haystack.extend(moves);
-        }
-
-        0
+        let end = Point::new(
+            self.cells.width() as i32 - 1,
+            self.cells.height() as i32 - 1,
+        );
+
+        search::dijkstra(
+            [Cursor::<MIN, MAX>::new()],
+            |&cursor| {
+                Direction::ALL
+                    .into_iter()
+                    .filter_map(move |dir| cursor.make_move(dir))
+                    .filter_map(|cursor| self.cells.get(cursor.point).map(|&cost| (cursor, cost)))
+            },
+            |cursor| cursor.point == end && cursor.dir_moves >= MIN,
+        )
+        .map_or(0, |(_, cost)| cost)
     }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 struct Cursor<const MIN: u32, const MAX: u32> {
-    point: (i32, i32),
+    point: Point,
     dir_moves: u32,
     last_move: Option<Direction>,
 }
@@ -110,7 +55,7 @@ struct Cursor<const MIN: u32, const MAX: u32> {
 impl<const MIN: u32, const MAX: u32> Cursor<MIN, MAX> {
     fn new() -> Self {
         Cursor {
-            point: (0, 0),
+            point: Point::new(0, 0),
             dir_moves: 0,
             last_move: None,
         }
@@ -120,7 +65,7 @@ impl<const MIN: u32, const MAX: u32> Cursor<MIN, MAX> {
         let mut dir_moves = self.dir_moves;
 
         if let Some(last_move) = self.last_move {
-            if dir == last_move.opposite() {
+            if dir == last_move.reverse() {
                 return None;
             }
         }
@@ -137,17 +82,8 @@ impl<const MIN: u32, const MAX: u32> Cursor<MIN, MAX> {
             dir_moves = 1;
         }
 
-        let (x, y) = self.point;
-
-        let point = match dir {
-            Direction::Left => (x - 1, y),
-            Direction::Right => (x + 1, y),
-            Direction::Up => (x, y - 1),
-            Direction::Down => (x, y + 1),
-        };
-
         let cursor = Cursor {
-            point,
+            point: self.point.step(dir),
             dir_moves,
             last_move: Some(dir),
         };
@@ -156,40 +92,6 @@ impl<const MIN: u32, const MAX: u32> Cursor<MIN, MAX> {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-enum Direction {
-    Left,
-    Right,
-    Up,
-    Down,
-}
-
-impl Direction {
-    fn opposite(&self) -> Direction {
-        match self {
-            Direction::Left => Direction::Right,
-            Direction::Right => Direction::Left,
-            Direction::Up => Direction::Down,
-            Direction::Down => Direction::Up,
-        }
-    }
-}
-
-#[derive(Copy, Clone, PartialEq, Eq)]
-struct Sorted<T>(i32, T);
-
-impl<T: PartialEq + Eq> std::cmp::Ord for Sorted<T> {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.0.cmp(&self.0)
-    }
-}
-
-impl<T: PartialEq + Eq> std::cmp::PartialOrd for Sorted<T> {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
 #[test]
 fn test() {
     let input = r#"2413432311323
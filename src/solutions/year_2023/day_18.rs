@@ -1,5 +1,12 @@
 use std::collections::BTreeSet;
 
+use crate::geometry::Point2;
+use crate::grid::Direction;
+
+crate::solution!(part_one, part_two);
+
+type Point = Point2<i64>;
+
 pub fn part_one(input: &str) -> i64 {
     solve(input, parse_part_one)
 }
@@ -19,9 +26,9 @@ fn solve(input: &str, parse: fn(&str) -> (Direction, i64)) -> i64 {
         .cycle()
         .peekable();
 
-    let mut point = Point(0, 0);
-    let mut min = Point(i64::MAX, i64::MAX);
-    let mut max = Point(i64::MIN, i64::MIN);
+    let mut point = Point::new(0, 0);
+    let mut min = Point::new(i64::MAX, i64::MAX);
+    let mut max = Point::new(i64::MIN, i64::MIN);
     let mut prev_dir = None;
 
     while let Some((idx, (dir, dist))) = moves.next() {
@@ -30,18 +37,18 @@ fn solve(input: &str, parse: fn(&str) -> (Direction, i64)) -> i64 {
             continue;
         };
 
-        let end = dir.jump(point, dist);
+        let end = jump(point, dir, dist);
 
-        min = min.min(point);
-        max = max.max(point);
+        min = min.min_components(point);
+        max = max.max_components(point);
 
-        let x = point.0;
-        let y = point.1;
+        let x = point.x;
+        let y = point.y;
 
-        let y1 = point.1.min(end.1);
-        let y2 = point.1.max(end.1);
-        let x1 = point.0.min(end.0);
-        let x2 = point.0.max(end.0);
+        let y1 = point.y.min(end.y);
+        let y2 = point.y.max(end.y);
+        let x1 = point.x.min(end.x);
+        let x2 = point.x.max(end.x);
 
         let segment = match dir {
             Direction::Left | Direction::Right => {
@@ -65,10 +72,10 @@ fn solve(input: &str, parse: fn(&str) -> (Direction, i64)) -> i64 {
     segments.sort_unstable();
 
     let mut count = 0;
-    let mut y = min.1;
-    while y <= max.1 {
+    let mut y = min.y;
+    while y <= max.y {
         let mut line_count = 0;
-        let mut x = min.0 - 1;
+        let mut x = min.x - 1;
         let mut inside = false;
         let mut skip_to = None;
         for seg in segments.iter() {
@@ -184,34 +191,11 @@ impl VertSegment {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-struct Point(i64, i64);
-
-impl Point {
-    fn min(&self, Point(x2, y2): Point) -> Point {
-        let Point(x1, y1) = *self;
-
-        Point(x1.min(x2), y1.min(y2))
-    }
-
-    fn max(&self, Point(x2, y2): Point) -> Point {
-        let Point(x1, y1) = *self;
-
-        Point(x1.max(x2), y1.max(y2))
-    }
-}
-
 fn parse_part_one(line: &str) -> (Direction, i64) {
     let (dir, rest) = line.split_once(" ").unwrap();
     let (dist, _color) = rest.split_once(" ").unwrap();
 
-    let dir = match dir {
-        "R" => Direction::Right,
-        "D" => Direction::Down,
-        "L" => Direction::Left,
-        "U" => Direction::Up,
-        _ => unreachable!(),
-    };
+    let dir = dir.chars().next().and_then(Direction::from_char).unwrap();
 
     let dist = dist.parse::<i64>().unwrap();
     (dir, dist)
@@ -238,29 +222,8 @@ fn parse_part_two(line: &str) -> (Direction, i64) {
     (dir, value)
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-enum Direction {
-    Left,
-    Right,
-    Up,
-    Down,
-}
-
-impl Direction {
-    fn offset(&self) -> Point {
-        match *self {
-            Direction::Left => Point(-1, 0),
-            Direction::Right => Point(1, 0),
-            Direction::Up => Point(0, -1),
-            Direction::Down => Point(0, 1),
-        }
-    }
-
-    fn jump(&self, Point(x, y): Point, dist: i64) -> Point {
-        let Point(d_x, d_y) = self.offset();
-
-        Point(x + (d_x * dist), y + (d_y * dist))
-    }
+fn jump(point: Point, dir: Direction, dist: i64) -> Point {
+    point + dir.offset().map(i64::from) * dist
 }
 
 #[test]
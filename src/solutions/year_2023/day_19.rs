@@ -1,13 +1,14 @@
-use crate::HashMap;
+use crate::parse::Parser;
+use crate::{HashMap, ParseError};
 
-pub fn part_one(input: &str) -> i64 {
-    let (rules, items) = input.trim().split_once("\n\n").unwrap();
+crate::solution!(part_one, part_two);
 
-    let mut score = 0;
+pub fn part_one(input: &str) -> Result<i64, ParseError> {
+    let (workflows, items) = parse(input)?;
 
-    let workflows = WorkflowMap::new(rules);
+    let mut score = 0;
 
-    'items: for item in items.lines().map(|l| l.parse::<Item>().unwrap()) {
+    'items: for item in items {
         let mut workflow = "in";
         loop {
             for rule in workflows.get(workflow) {
@@ -30,12 +31,11 @@ pub fn part_one(input: &str) -> i64 {
         }
     }
 
-    score
+    Ok(score)
 }
 
-pub fn part_two(input: &str) -> i64 {
-    let (rules, _items) = input.trim().split_once("\n\n").unwrap();
-    let workflows = WorkflowMap::new(rules);
+pub fn part_two(input: &str) -> Result<i64, ParseError> {
+    let (workflows, _items) = parse(input)?;
 
     let mut paths = Vec::new();
     paths.push((ItemRange::new(), "in"));
@@ -66,7 +66,20 @@ pub fn part_two(input: &str) -> i64 {
         }
     }
 
-    count
+    Ok(count)
+}
+
+fn parse(input: &str) -> Result<(WorkflowMap<'_>, Vec<Item>), ParseError> {
+    let mut p = Parser::new(input);
+    let workflows = p
+        .section()
+        .ok_or_else(|| p.error("expected the workflows"))?;
+    let items = p.section().ok_or_else(|| p.error("expected the parts"))?;
+
+    Ok((
+        WorkflowMap::parse(workflows)?,
+        items.parse_lines(Item::parse)?,
+    ))
 }
 
 struct WorkflowMap<'a> {
@@ -75,19 +88,25 @@ struct WorkflowMap<'a> {
 }
 
 impl<'a> WorkflowMap<'a> {
-    fn new(input: &'a str) -> Self {
+    fn parse(input: Parser<'a>) -> Result<Self, ParseError> {
         let mut workflows = HashMap::new();
         let mut rules = Vec::new();
 
-        for line in input.trim().lines() {
-            let (name, rest) = line.trim_end_matches('}').split_once('{').unwrap();
+        let lines = input.parse_lines(|p| {
+            let name = p.take_until(|c| c == '{');
+            p.token("{")?;
+            let rules = p.separated(",", Rule::parse)?;
+            p.token("}")?;
+            Ok((name, rules))
+        })?;
 
+        for (name, workflow) in lines {
             let start_idx = rules.len();
-            rules.extend(rest.split(",").map(|r| Rule::try_from(r).unwrap()));
+            rules.extend(workflow);
             workflows.insert(name, (start_idx, rules.len()));
         }
 
-        WorkflowMap { workflows, rules }
+        Ok(WorkflowMap { workflows, rules })
     }
 
     fn get(&self, workflow: &str) -> impl Iterator<Item = Rule<'_>> + '_ {
@@ -110,34 +129,6 @@ impl<'a> WorkflowMap<'a> {
     }
 }
 
-#[derive(Default)]
-struct ItemBuilder {
-    x: Option<i64>,
-    m: Option<i64>,
-    a: Option<i64>,
-    s: Option<i64>,
-}
-
-impl ItemBuilder {
-    fn add(&mut self, param: Param, value: i64) {
-        match param {
-            Param::X => self.x = Some(value),
-            Param::M => self.m = Some(value),
-            Param::A => self.a = Some(value),
-            Param::S => self.s = Some(value),
-        }
-    }
-
-    fn build(self) -> Item {
-        Item {
-            x: self.x.unwrap(),
-            m: self.m.unwrap(),
-            a: self.a.unwrap(),
-            s: self.s.unwrap(),
-        }
-    }
-}
-
 struct Item {
     x: i64,
     m: i64,
@@ -146,10 +137,18 @@ struct Item {
 }
 
 impl Item {
-    fn builder() -> ItemBuilder {
-        ItemBuilder {
-            ..Default::default()
-        }
+    fn parse(p: &mut Parser) -> Result<Self, ParseError> {
+        p.token("{x=")?;
+        let x = p.int()?;
+        p.token(",m=")?;
+        let m = p.int()?;
+        p.token(",a=")?;
+        let a = p.int()?;
+        p.token(",s=")?;
+        let s = p.int()?;
+        p.token("}")?;
+
+        Ok(Item { x, m, a, s })
     }
 
     fn get(&self, param: Param) -> i64 {
@@ -166,29 +165,6 @@ impl Item {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
-struct ItemParseErr;
-
-impl std::str::FromStr for Item {
-    type Err = ItemParseErr;
-
-    fn from_str(line: &str) -> Result<Self, Self::Err> {
-        let line = line.trim_start_matches('{').trim_end_matches('}');
-
-        let mut item = Item::builder();
-        for param in line.split(",") {
-            let (param, value) = param.split_once('=').ok_or(ItemParseErr)?;
-
-            let param = param.parse().map_err(|_| ItemParseErr)?;
-            let value = value.parse().map_err(|_| ItemParseErr)?;
-
-            item.add(param, value);
-        }
-
-        Ok(item.build())
-    }
-}
-
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 enum Param {
     X,
@@ -197,22 +173,17 @@ enum Param {
     S,
 }
 
-#[derive(Debug, Copy, Clone)]
-struct ParamParseErr;
-
-impl std::str::FromStr for Param {
-    type Err = ParamParseErr;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let param = match s {
+impl Param {
+    fn from_name(name: &str) -> Option<Self> {
+        let param = match name {
             "x" => Param::X,
             "m" => Param::M,
             "a" => Param::A,
             "s" => Param::S,
-            _ => return Err(ParamParseErr),
+            _ => return None,
         };
 
-        Ok(param)
+        Some(param)
     }
 }
 
@@ -268,6 +239,30 @@ struct Rule<'a> {
 }
 
 impl<'a> Rule<'a> {
+    fn parse(p: &mut Parser<'a>) -> Result<Self, ParseError> {
+        let start = *p;
+        let name = p.take_while(|c| c.is_ascii_alphabetic());
+
+        let op = if p.try_token("<") {
+            Operation::Less
+        } else if p.try_token(">") {
+            Operation::Greater
+        } else {
+            let condition = Condition::Default;
+            let outcome = name.into();
+
+            return Ok(Rule { condition, outcome });
+        };
+
+        let param = Param::from_name(name).ok_or_else(|| start.error("unknown category"))?;
+        let value = p.int()?;
+        p.token(":")?;
+        let outcome = p.take_while(|c| c.is_ascii_alphabetic()).into();
+        let condition = Condition::Apply(param, op, value);
+
+        Ok(Rule { condition, outcome })
+    }
+
     fn apply(&self, item: &Item) -> Option<Outcome<'a>> {
         match self.condition {
             Condition::Apply(p, op, v) => {
@@ -309,35 +304,6 @@ impl<'a> Rule<'a> {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
-struct RuleParseErr;
-
-impl<'a> TryFrom<&'a str> for Rule<'a> {
-    type Error = RuleParseErr;
-
-    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
-        let less = value.split_once('<').map(|(p, r)| (p, r, Operation::Less));
-        let greater = value
-            .split_once('>')
-            .map(|(p, r)| (p, r, Operation::Greater));
-
-        if let Some((param, rest, op)) = less.or(greater) {
-            let (value, outcome) = rest.split_once(':').ok_or(RuleParseErr)?;
-            let value = value.parse().map_err(|_| RuleParseErr)?;
-            let outcome = outcome.into();
-            let param = param.parse().map_err(|_| RuleParseErr)?;
-            let condition = Condition::Apply(param, op, value);
-
-            Ok(Rule { condition, outcome })
-        } else {
-            let condition = Condition::Default;
-            let outcome = value.into();
-
-            Ok(Rule { condition, outcome })
-        }
-    }
-}
-
 #[derive(Debug, Copy, Clone)]
 struct Range {
     lo: i64,
@@ -452,6 +418,6 @@ hdj{m>838:A,pv}
 {x=2127,m=1623,a=2188,s=1013}
 "#;
 
-    assert_eq!(19114, part_one(input));
-    assert_eq!(167409079868000, part_two(input));
+    assert_eq!(Ok(19114), part_one(input));
+    assert_eq!(Ok(167409079868000), part_two(input));
 }
@@ -1,6 +1,9 @@
 use std::collections::VecDeque;
 
 use crate::HashMap;
+use crate::math::lcm_iter;
+
+crate::solution!(part_one, part_two);
 
 pub fn part_one(input: &str) -> usize {
     let mut modules = ModuleNetwork::new(input.trim());
@@ -190,22 +193,6 @@ impl<'a> Module<'a> {
     }
 }
 
-fn gcd(a: u64, b: u64) -> u64 {
-    let mut n = a.min(b);
-    while n > 1 {
-        if a % n == 0 && b % n == 0 {
-            return n;
-        }
-        n -= 1;
-    }
-
-    1
-}
-
-fn lcm_iter<I: IntoIterator<Item = u64>>(iter: I) -> u64 {
-    iter.into_iter().fold(1, |acc, n| (acc * n) / gcd(acc, n))
-}
-
 #[test]
 fn test() {
     let input = r#"broadcaster -> a, b, c
@@ -1,5 +1,8 @@
+use crate::grid::{Grid, Point};
 use crate::{HashMap, HashSet};
 
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> usize {
     solve_part_one::<64>(input)
 }
@@ -18,7 +21,7 @@ pub fn part_two(input: &str) -> i64 {
     let steps = 26501365;
 
     let map = Map::new(input);
-    let width = map.width as i64;
+    let width = map.cells.width() as i64;
     let min_steps = (steps % width) + width + width;
     let grid_results = map.visited_squares_per_grid(min_steps, true);
 
@@ -32,20 +35,20 @@ pub fn part_two(input: &str) -> i64 {
     }
     a += 1;
 
-    let center = grid_results.get(&(0, 0)).copied().unwrap();
-    let center_odd = grid_results.get(&(0, 1)).copied().unwrap();
-    let north = grid_results.get(&(0, -2)).copied().unwrap();
-    let south = grid_results.get(&(0, 2)).copied().unwrap();
-    let west = grid_results.get(&(-2, 0)).copied().unwrap();
-    let east = grid_results.get(&(2, 0)).copied().unwrap();
-    let north_west = grid_results.get(&(-2, -1)).copied().unwrap();
-    let north_west_odd = grid_results.get(&(-1, -1)).copied().unwrap();
-    let north_east = grid_results.get(&(2, -1)).copied().unwrap();
-    let north_east_odd = grid_results.get(&(1, -1)).copied().unwrap();
-    let south_west = grid_results.get(&(-2, 1)).copied().unwrap();
-    let south_west_odd = grid_results.get(&(-1, 1)).copied().unwrap();
-    let south_east = grid_results.get(&(2, 1)).copied().unwrap();
-    let south_east_odd = grid_results.get(&(1, 1)).copied().unwrap();
+    let center = grid_results.get(&Point::new(0, 0)).copied().unwrap();
+    let center_odd = grid_results.get(&Point::new(0, 1)).copied().unwrap();
+    let north = grid_results.get(&Point::new(0, -2)).copied().unwrap();
+    let south = grid_results.get(&Point::new(0, 2)).copied().unwrap();
+    let west = grid_results.get(&Point::new(-2, 0)).copied().unwrap();
+    let east = grid_results.get(&Point::new(2, 0)).copied().unwrap();
+    let north_west = grid_results.get(&Point::new(-2, -1)).copied().unwrap();
+    let north_west_odd = grid_results.get(&Point::new(-1, -1)).copied().unwrap();
+    let north_east = grid_results.get(&Point::new(2, -1)).copied().unwrap();
+    let north_east_odd = grid_results.get(&Point::new(1, -1)).copied().unwrap();
+    let south_west = grid_results.get(&Point::new(-2, 1)).copied().unwrap();
+    let south_west_odd = grid_results.get(&Point::new(-1, 1)).copied().unwrap();
+    let south_east = grid_results.get(&Point::new(2, 1)).copied().unwrap();
+    let south_east_odd = grid_results.get(&Point::new(1, 1)).copied().unwrap();
 
     let corners: i64 = [north, south, west, east].into_iter().sum();
     let long_edge: i64 = [north_west, north_east, south_west, south_east]
@@ -81,113 +84,48 @@ enum Cell {
 }
 
 struct Map {
-    cells: Vec<Cell>,
-    start: (i64, i64),
-    width: usize,
-    height: usize,
+    cells: Grid<Cell>,
+    start: Point,
 }
 
 impl Map {
     fn new(input: &str) -> Self {
-        let mut cells = Vec::with_capacity(input.len());
-        let mut width = 0;
-        let mut height = 0;
-
-        let mut start = None;
-
-        for (y, line) in input.trim().lines().enumerate() {
-            if width < line.len() {
-                width = line.len();
-            }
-            height += 1;
-
-            for (x, c) in line.chars().enumerate() {
-                let cell = match c {
-                    '.' => Cell::Open,
-                    '#' => Cell::Rock,
-                    'S' => {
-                        start = Some((x as i64, y as i64));
-                        Cell::Open
-                    }
-                    _ => unreachable!(),
-                };
-
-                cells.push(cell);
-            }
-        }
-
-        let start = start.unwrap();
-
-        Self {
-            cells,
-            start,
-            width,
-            height,
-        }
+        let chars =
+            Grid::parse(input.trim(), |c| "#.S".contains(c).then_some(c)).expect("valid grid");
+        let start = chars.position(|&c| c == 'S').unwrap();
+        let cells = chars.map(|&c| match c {
+            '#' => Cell::Rock,
+            _ => Cell::Open,
+        });
+
+        Self { cells, start }
     }
 
-    fn get(&self, (x, y): (i64, i64), infinite: bool) -> Option<Cell> {
-        let (x, y) = if infinite {
-            self.normalize((x, y))
-        } else {
-            (x, y)
-        };
-
-        if x < 0 || y < 0 {
-            return None;
-        }
-
-        let (x, y) = (x as usize, y as usize);
-
-        if x >= self.width || y >= self.height {
-            None
-        } else {
-            let idx = y * self.width + x;
-            self.cells.get(idx).copied()
-        }
+    fn get(&self, p: Point, infinite: bool) -> Option<Cell> {
+        let p = if infinite { self.normalize(p) } else { p };
+        self.cells.get(p).copied()
     }
 
-    fn neighbors(
-        &self,
-        (x, y): (i64, i64),
-        infinite: bool,
-    ) -> impl Iterator<Item = (i64, i64)> + '_ {
-        let mut i = 0;
-
-        std::iter::from_fn(move || loop {
-            let p = match i {
-                0 => (x + 1, y),
-                1 => (x - 1, y),
-                2 => (x, y + 1),
-                3 => (x, y - 1),
-                _ => return None,
-            };
-
-            i += 1;
-
-            if let Some(cell) = self.get(p, infinite) {
-                if cell == Cell::Open {
-                    return Some(p);
-                }
-            }
-        })
+    fn neighbors(&self, p: Point, infinite: bool) -> impl Iterator<Item = Point> + '_ {
+        p.neighbors()
+            .filter(move |&n| self.get(n, infinite) == Some(Cell::Open))
     }
 
-    fn normalize(&self, (x, y): (i64, i64)) -> (i64, i64) {
-        (
-            x.rem_euclid(self.width as i64),
-            y.rem_euclid(self.height as i64),
+    fn normalize(&self, p: Point) -> Point {
+        Point::new(
+            p.x.rem_euclid(self.cells.width() as i32),
+            p.y.rem_euclid(self.cells.height() as i32),
         )
     }
 
-    fn grid_position(&self, (x, y): (i64, i64)) -> (i64, i64) {
-        (
-            x.div_euclid(self.width as i64),
-            y.div_euclid(self.height as i64),
+    fn grid_position(&self, p: Point) -> Point {
+        Point::new(
+            p.x.div_euclid(self.cells.width() as i32),
+            p.y.div_euclid(self.cells.height() as i32),
         )
     }
 
-    fn visited_squares(&self, steps: i64, infinite: bool) -> HashSet<(i64, i64)> {
+    fn visited_squares(&self, steps: i64, infinite: bool) -> HashSet<Point> {
         let mut recent_new = HashSet::new();
         let mut recent_old = HashSet::new();
         let mut map_new = HashSet::new();
@@ -212,7 +150,7 @@ impl Map {
         map_old
     }
 
-    fn visited_squares_per_grid(&self, steps: i64, infinite: bool) -> HashMap<(i64, i64), i64> {
+    fn visited_squares_per_grid(&self, steps: i64, infinite: bool) -> HashMap<Point, i64> {
         let visits = self.visited_squares(steps, infinite);
         let mut result = HashMap::new();
 
@@ -1,7 +1,10 @@
-use crate::{HashMap, HashSet};
+use crate::parse::{self, Parser};
+use crate::{HashMap, HashSet, ParseError};
 
-pub fn part_one(input: &str) -> usize {
-    let mut pile = BrickStack::new(input);
+crate::solution!(part_one, part_two);
+
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    let mut pile = BrickStack::parse(input)?;
     pile.compress();
     let supporters = pile.find_supporters();
 
@@ -12,13 +15,13 @@ pub fn part_one(input: &str) -> usize {
         }
     }
 
-    pile.bricks.len() - required.len()
+    Ok(pile.bricks.len() - required.len())
 }
 
-pub fn part_two(input: &str) -> usize {
-    let mut pile = BrickStack::new(input);
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    let mut pile = BrickStack::parse(input)?;
     pile.compress();
-    pile.find_impact()
+    Ok(pile.find_impact())
 }
 
 struct BrickStack {
@@ -26,13 +29,12 @@ struct BrickStack {
 }
 
 impl BrickStack {
-    fn new(input: &str) -> Self {
-        let lines = input.trim().lines();
-        let mut bricks: Vec<_> = lines.map(Brick::from_str).collect();
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut bricks = parse::lines(input, Brick::parse)?;
 
         bricks.sort_unstable_by_key(|b| std::cmp::Reverse(b.min.2));
 
-        BrickStack { bricks }
+        Ok(BrickStack { bricks })
     }
 
     fn compress(&mut self) {
@@ -129,28 +131,19 @@ struct Brick {
 }
 
 impl Brick {
-    fn from_str(line: &str) -> Self {
-        let (a, b) = line.split_once('~').unwrap();
-
-        let a_dims = a.split(',').map(|a| a.parse::<i32>().unwrap());
-        let b_dims = b.split(',').map(|b| b.parse::<i32>().unwrap());
-
-        let mut dims = a_dims
-            .zip(b_dims)
-            .map(|(a, b)| if a > b { (b, a) } else { (a, b) });
+    fn parse(p: &mut Parser) -> Result<Self, ParseError> {
+        let a = parse_coords(p)?;
+        p.token("~")?;
+        let b = parse_coords(p)?;
 
-        let x_dim = dims.next().unwrap();
-        let y_dim = dims.next().unwrap();
-        let z_dim = dims.next().unwrap();
+        let min = (a.0.min(b.0), a.1.min(b.1), a.2.min(b.2));
+        let max = (a.0.max(b.0), a.1.max(b.1), a.2.max(b.2));
 
-        let min = (x_dim.0, y_dim.0, z_dim.0);
-        let max = (x_dim.1, y_dim.1, z_dim.1);
-
-        Self {
+        Ok(Self {
             min,
             max,
             z_offset: 0,
-        }
+        })
     }
 
     fn on_floor(&self) -> bool {
@@ -206,6 +199,16 @@ impl Brick {
     }
 }
 
+fn parse_coords(p: &mut Parser) -> Result<(i32, i32, i32), ParseError> {
+    let x = p.int()?;
+    p.token(",")?;
+    let y = p.int()?;
+    p.token(",")?;
+    let z = p.int()?;
+
+    Ok((x, y, z))
+}
+
 #[test]
 fn test() {
     let input = r#"1,0,1~1,2,1
@@ -217,6 +220,6 @@ fn test() {
 1,1,8~1,1,9
 "#;
 
-    assert_eq!(5, part_one(input));
-    assert_eq!(7, part_two(input));
+    assert_eq!(Ok(5), part_one(input));
+    assert_eq!(Ok(7), part_two(input));
 }
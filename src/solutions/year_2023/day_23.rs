@@ -1,6 +1,9 @@
 use std::collections::VecDeque;
 
 use crate::HashSet;
+use crate::grid::{Direction, Grid, Point};
+
+crate::solution!(part_one, part_two);
 
 pub fn part_one(input: &str) -> u32 {
     let map = Map::new(input, false);
@@ -24,147 +27,83 @@ enum Cell {
     Down,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-enum Direction {
-    Left,
-    Right,
-    Up,
-    Down,
-}
-
-impl Direction {
-    fn apply(&self, (x, y): (i32, i32)) -> (i32, i32) {
-        match self {
-            Direction::Left => (x - 1, y),
-            Direction::Right => (x + 1, y),
-            Direction::Up => (x, y - 1),
-            Direction::Down => (x, y + 1),
-        }
-    }
-
-    fn allowed(&self, cell: Cell) -> bool {
-        match (self, cell) {
-            (_, Cell::Open) => true,
-            (Direction::Left, Cell::Left) => true,
-            (Direction::Right, Cell::Right) => true,
-            (Direction::Up, Cell::Up) => true,
-            (Direction::Down, Cell::Down) => true,
-            _ => false,
-        }
+impl Cell {
+    fn allows(self, direction: Direction) -> bool {
+        matches!(
+            (direction, self),
+            (_, Cell::Open)
+                | (Direction::Left, Cell::Left)
+                | (Direction::Right, Cell::Right)
+                | (Direction::Up, Cell::Up)
+                | (Direction::Down, Cell::Down)
+        )
     }
 }
 
 struct Map {
-    cells: Vec<Cell>,
-    width: usize,
-    height: usize,
-    start: (i32, i32),
-    end: (i32, i32),
+    cells: Grid<Cell>,
+    start: Point,
+    end: Point,
 }
 
 impl Map {
     fn new(input: &str, dry_trail: bool) -> Self {
-        let mut cells = Vec::with_capacity(input.len());
-        let mut width = 0;
-        let mut height = 0;
-        let mut start = None;
-        let mut end = (0, 0);
-
-        for (y, line) in input.trim().lines().enumerate() {
-            if width < line.len() {
-                width = line.len();
-            }
-            height += 1;
-
-            for (x, c) in line.chars().enumerate() {
-                let (x, y) = (x as i32, y as i32);
-                let cell = match c {
-                    '#' => Cell::Forest,
-                    '<' | '>' | 'v' | '^' if dry_trail => Cell::Open,
-                    '.' => {
-                        if start.is_none() {
-                            start = Some((x, y));
-                        }
-                        end = (x, y);
-                        Cell::Open
-                    }
-                    '<' => Cell::Left,
-                    '>' => Cell::Right,
-                    '^' => Cell::Up,
-                    'v' => Cell::Down,
-                    _ => unreachable!(),
-                };
-
-                cells.push(cell)
-            }
-        }
-
-        let start = start.unwrap();
-
-        Self {
-            cells,
-            width,
-            height,
-            start,
-            end,
-        }
+        let chars =
+            Grid::parse(input.trim(), |c| "#.<>^v".contains(c).then_some(c)).expect("valid grid");
+        let start = chars.position(|&c| c == '.').unwrap();
+        let end = chars
+            .iter()
+            .filter(|&(_, &c)| c == '.')
+            .map(|(p, _)| p)
+            .last()
+            .unwrap();
+
+        let cells = chars.map(|&c| match c {
+            '#' => Cell::Forest,
+            '<' | '>' | 'v' | '^' if dry_trail => Cell::Open,
+            '<' => Cell::Left,
+            '>' => Cell::Right,
+            '^' => Cell::Up,
+            'v' => Cell::Down,
+            _ => Cell::Open,
+        });
+
+        Self { cells, start, end }
     }
 
-    fn get(&self, (x, y): (i32, i32)) -> Option<Cell> {
-        if x < 0 || y < 0 {
-            return None;
-        }
-
-        let (x, y) = (x as usize, y as usize);
-
-        if x >= self.width || y >= self.height {
-            None
-        } else {
-            let idx = y * self.width + x;
-            self.cells.get(idx).copied()
-        }
+    fn get(&self, p: Point) -> Option<Cell> {
+        self.cells.get(p).copied()
     }
 
-    fn neighbors(
-        &self,
-        (x, y): (i32, i32),
-    ) -> impl Iterator<Item = (Direction, (i32, i32), Cell)> + '_ {
-        let mut i = 0;
-
-        std::iter::from_fn(move || loop {
-            let dir = match i {
-                0 => Direction::Left,
-                1 => Direction::Right,
-                2 => Direction::Up,
-                3 => Direction::Down,
-                _ => return None,
-            };
-
-            i += 1;
-
-            let p = dir.apply((x, y));
-
-            if let Some(cell) = self.get(p) {
-                if cell != Cell::Forest {
-                    return Some((dir, p, cell));
-                }
-            }
+    fn neighbors(&self, p: Point) -> impl Iterator<Item = (Direction, Point, Cell)> + '_ {
+        [
+            Direction::Left,
+            Direction::Right,
+            Direction::Up,
+            Direction::Down,
+        ]
+        .into_iter()
+        .filter_map(move |dir| {
+            let p = p.step(dir);
+            self.get(p)
+                .filter(|&cell| cell != Cell::Forest)
+                .map(|cell| (dir, p, cell))
         })
     }
 
     fn allowed_neighbor(
         &self,
-        current_point: (i32, i32),
+        current_point: Point,
         direction: Direction,
-        target_point: (i32, i32),
+        target_point: Point,
         target_cell: Cell,
         ignore_slopes: bool,
-    ) -> Option<(i32, i32)> {
+    ) -> Option<Point> {
         let current_cell = self.get(current_point);
         if ignore_slopes {
             Some(target_point)
         } else if let Some(current_cell) = current_cell {
-            if direction.allowed(target_cell) && direction.allowed(current_cell) {
+            if target_cell.allows(direction) && current_cell.allows(direction) {
                 Some(target_point)
             } else {
                 None
@@ -176,10 +115,10 @@ impl Map {
 
     fn intersection_distance(
         &self,
-        start: (i32, i32),
+        start: Point,
         cross_intersections: bool,
         ignore_slopes: bool,
-    ) -> impl Iterator<Item = (u32, (i32, i32))> + '_ {
+    ) -> impl Iterator<Item = (u32, Point)> + '_ {
         let mut visited = HashSet::new();
         let mut haystack = VecDeque::new();
         let mut temp = Vec::new();
@@ -229,7 +168,7 @@ impl Map {
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 enum Node {
     Start,
-    Intersection((i32, i32)),
+    Intersection(Point),
     End,
 }
 
@@ -339,11 +278,7 @@ impl Graph {
         std::iter::from_fn(move || {
             if let Some(edge) = self.edges.get(i) {
                 i += 1;
-                if edge.left == node {
-                    Some(*edge)
-                } else {
-                    None
-                }
+                if edge.left == node { Some(*edge) } else { None }
             } else {
                 None
             }
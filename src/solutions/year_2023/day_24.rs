@@ -1,9 +1,14 @@
-pub fn part_one(input: &str) -> u64 {
+use crate::parse::{self, Parser};
+use crate::{Answer, ParseError};
+
+crate::solution!(part_one, part_two);
+
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
     solve_part_one::<200000000000000, 400000000000000>(input)
 }
 
-fn solve_part_one<const LOW: i128, const HIGH: i128>(input: &str) -> u64 {
-    let stones: Vec<_> = input.trim().lines().map(Hailstone::new).collect();
+fn solve_part_one<const LOW: i128, const HIGH: i128>(input: &str) -> Result<u64, ParseError> {
+    let stones = parse::lines(input, Hailstone::parse)?;
     let mut count = 0;
 
     for (i, &stone) in stones.iter().enumerate() {
@@ -14,7 +19,7 @@ fn solve_part_one<const LOW: i128, const HIGH: i128>(input: &str) -> u64 {
         }
     }
 
-    count
+    Ok(count)
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -24,23 +29,12 @@ struct Hailstone {
 }
 
 impl Hailstone {
-    fn new(line: &str) -> Self {
-        let (pos, vel) = line.trim().split_once(" @ ").unwrap();
-        let mut pos = pos.split(',').map(|n| n.trim().parse::<i128>().unwrap());
-        let mut vel = vel.split(',').map(|n| n.trim().parse::<i128>().unwrap());
-
-        let x = pos.next().unwrap();
-        let y = pos.next().unwrap();
-        let z = pos.next().unwrap();
-
-        let a = vel.next().unwrap();
-        let b = vel.next().unwrap();
-        let c = vel.next().unwrap();
-
-        let position = (x, y, z);
-        let velocity = (a, b, c);
+    fn parse(p: &mut Parser) -> Result<Self, ParseError> {
+        let position = parse_triple(p)?;
+        p.token(" @")?;
+        let velocity = parse_triple(p)?;
 
-        Self { position, velocity }
+        Ok(Self { position, velocity })
     }
 
     fn intersect<const LOW: i128, const HIGH: i128>(self, other: Self) -> bool {
@@ -91,8 +85,23 @@ impl Hailstone {
     }
 }
 
-pub fn part_two(_input: &str) -> &'static str {
-    "incomplete"
+fn parse_triple(p: &mut Parser) -> Result<(i128, i128, i128), ParseError> {
+    let x = number(p)?;
+    p.token(",")?;
+    let y = number(p)?;
+    p.token(",")?;
+    let z = number(p)?;
+
+    Ok((x, y, z))
+}
+
+fn number(p: &mut Parser) -> Result<i128, ParseError> {
+    p.skip_whitespace();
+    p.int()
+}
+
+pub fn part_two(_input: &str) -> Answer {
+    Answer::Unsolved
 }
 
 #[test]
@@ -104,6 +113,6 @@ fn test() {
 20, 19, 15 @  1, -5, -3
 "#;
 
-    assert_eq!(2, solve_part_one::<7, 27>(input));
+    assert_eq!(Ok(2), solve_part_one::<7, 27>(input));
     //assert_eq!(47, part_two(input));
 }
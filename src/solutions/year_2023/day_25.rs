@@ -1,6 +1,8 @@
 use std::collections::VecDeque;
 
-use crate::{HashMap, HashSet};
+use crate::{Answer, HashMap, HashSet};
+
+crate::solution!(part_one, part_two);
 
 pub fn part_one(input: &str) -> usize {
     let mut graph = Graph::new(input);
@@ -196,8 +198,8 @@ impl<'a> Graph<'a> {
     }
 }
 
-pub fn part_two(_input: &str) -> &'static str {
-    "Almost there..."
+pub fn part_two(_input: &str) -> Answer {
+    Answer::NotApplicable
 }
 
 #[test]
@@ -1,14 +1,21 @@
-use crate::HashMap;
-
-pub fn part_one(input: &str) -> u32 {
-    let (mut left, mut right): (Vec<_>, Vec<_>) = input
-        .trim()
-        .lines()
-        .filter_map(|l| l.trim().split_once(" "))
-        .map(|(l, r)| (l.trim(), r.trim()))
-        .map(|(l, r)| (l.parse::<i32>().ok(), r.parse::<i32>().ok()))
-        .filter_map(|(l, r)| l.zip(r))
-        .unzip();
+use crate::parse;
+use crate::{Example, HashMap, ParseError};
+
+crate::solution!(parse => part_one, part_two; examples);
+
+pub fn parse(input: &str) -> Result<(Vec<i32>, Vec<i32>), ParseError> {
+    let pairs: Vec<(i32, i32)> = parse::lines(input, |line| {
+        let left = line.int()?;
+        line.skip_whitespace();
+        Ok((left, line.int()?))
+    })?;
+
+    Ok(pairs.into_iter().unzip())
+}
+
+pub fn part_one((left, right): &(Vec<i32>, Vec<i32>)) -> u32 {
+    let mut left = left.clone();
+    let mut right = right.clone();
 
     left.sort_unstable();
     right.sort_unstable();
@@ -19,29 +26,18 @@ pub fn part_one(input: &str) -> u32 {
         .sum()
 }
 
-pub fn part_two(input: &str) -> i32 {
-    let lines = input
-        .trim()
-        .lines()
-        .filter_map(|l| l.trim().split_once(" "))
-        .map(|(l, r)| (l.trim(), r.trim()))
-        .map(|(l, r)| (l.parse::<i32>().ok(), r.parse::<i32>().ok()))
-        .filter_map(|(l, r)| l.zip(r));
-
-    let mut left = Vec::new();
-    let mut right = HashMap::new();
-    for (l, r) in lines {
-        left.push(l);
-        *right.entry(r).or_insert(0) += 1;
+pub fn part_two((left, right): &(Vec<i32>, Vec<i32>)) -> i32 {
+    let mut counts = HashMap::new();
+    for &r in right {
+        *counts.entry(r).or_insert(0) += 1;
     }
 
-    left.into_iter()
-        .map(|l| l * right.get(&l).copied().unwrap_or_default())
+    left.iter()
+        .map(|l| l * counts.get(l).copied().unwrap_or_default())
         .sum()
 }
 
-#[test]
-fn test() {
+pub fn examples() -> Vec<Example> {
     let input = r#"3   4
 4   3
 2   5
@@ -49,6 +45,5 @@ fn test() {
 3   9
 3   3"#;
 
-    assert_eq!(11, part_one(input));
-    assert_eq!(31, part_two(input));
+    vec![Example::new(input).part_one(11).part_two(31)]
 }
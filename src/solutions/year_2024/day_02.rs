@@ -1,27 +1,23 @@
-pub fn part_one(input: &str) -> usize {
-    input
-        .trim()
-        .lines()
-        .filter(|l| {
-            l.trim()
-                .split(' ')
-                .filter_map(|n| n.parse::<i32>().ok())
-                .report()
-                .verify()
-        })
-        .count()
+use crate::ParseError;
+use crate::parse::{self, Parser};
+
+crate::solution!(part_one, part_two);
+
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    let safe_count = parse(input)?
+        .iter()
+        .filter(|levels| levels.iter().copied().report().verify())
+        .count();
+
+    Ok(safe_count)
 }
 
-pub fn part_two(input: &str) -> usize {
-    let reports = input.trim().lines().map(|l| {
-        l.trim()
-            .split(' ')
-            .filter_map(|n| n.parse::<i32>().ok())
-            .report()
-    });
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    let reports = parse(input)?;
 
     let mut safe_count = 0;
-    for report in reports {
+    for levels in &reports {
+        let report = levels.iter().copied().report();
         let level_count = report.clone().count();
         for idx in 0..=level_count {
             let mut report = report.clone();
@@ -34,7 +30,11 @@ pub fn part_two(input: &str) -> usize {
         }
     }
 
-    safe_count
+    Ok(safe_count)
+}
+
+fn parse(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+    parse::lines(input, |p| p.separated(" ", Parser::int))
 }
 
 trait ReportIterExt: Iterator + Sized {
@@ -130,6 +130,6 @@ fn test() {
 1 3 6 7 9
 "#;
 
-    assert_eq!(2, part_one(input));
-    assert_eq!(4, part_two(input));
+    assert_eq!(Ok(2), part_one(input));
+    assert_eq!(Ok(4), part_two(input));
 }
@@ -1,3 +1,5 @@
+crate::solution!(part_one, part_two);
+
 pub fn part_one(mut input: &str) -> u32 {
     let mut sum = 0;
     while !input.is_empty() {
@@ -1,193 +1,47 @@
+use crate::grid::{Grid, Point};
+
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> usize {
-    Grid::new(input).search_xmas()
+    let grid = parse(input);
+    grid.points().map(|p| xmas_count(&grid, p)).sum()
 }
 
 pub fn part_two(input: &str) -> usize {
-    Grid::new(input).search_mas()
+    let grid = parse(input);
+    grid.points().filter(|&p| is_mas(&grid, p)).count()
 }
 
-struct Grid {
-    cells: Vec<u8>,
-    width: usize,
-    height: usize,
+fn parse(input: &str) -> Grid<u8> {
+    Grid::parse(input.trim(), |c| c.is_ascii().then_some(c as u8)).expect("valid grid")
 }
 
-impl Grid {
-    fn new(input: &str) -> Self {
-        let input = input.trim();
-        let mut cells = Vec::with_capacity(input.len());
-        let mut width = 0;
-        let mut height = 1;
-        for b in input.bytes() {
-            match b {
-                b'\n' => {
-                    height += 1;
-                    width = 0;
-                }
-                b => {
-                    width += 1;
-                    cells.push(b)
-                }
-            }
-        }
-
-        Self {
-            cells,
-            width,
-            height,
-        }
-    }
-
-    fn get(&self, (x, y): (i32, i32)) -> Option<u8> {
-        if x < 0 || y < 0 {
-            return None;
-        }
-        let x = x as usize;
-        let y = y as usize;
-
-        if x >= self.width || y >= self.height {
-            None
-        } else {
-            let idx = y * self.width + x;
-            self.cells.get(idx).copied()
-        }
-    }
-
-    fn xmas_count(&self, p: (i32, i32)) -> usize {
-        let mut sum = 0;
-        for dir in Direction::all() {
-            if OffsetIter::new(*dir, p)
+fn xmas_count(grid: &Grid<u8>, p: Point) -> usize {
+    Point::default()
+        .neighbors_diagonal()
+        .filter(|&dir| {
+            (0..)
                 .zip(b"XMAS")
-                .map(|(off, c)| (self.get(off), c))
-                .all(|(m, c)| m == Some(*c))
-            {
-                sum += 1;
-            }
-        }
-
-        sum
-    }
-
-    fn is_mas(&self, p: (i32, i32)) -> bool {
-        let Some(b'A') = self.get(p) else {
-            return false;
-        };
-
-        let corners = [
-            Direction::UpLeft,
-            Direction::UpRight,
-            Direction::DownLeft,
-            Direction::DownRight,
-        ]
-        .map(|dir| self.get(dir.offset(p)));
-
-        let [Some(ul), Some(ur), Some(dl), Some(dr)] = corners else {
-            return false;
-        };
-
-        let falling = ul == b'M' && dr == b'S' || ul == b'S' && dr == b'M';
-        let rising = ur == b'M' && dl == b'S' || ur == b'S' && dl == b'M';
-
-        falling && rising
-    }
-
-    fn points(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
-        let mut x = 0;
-        let mut y = 0;
-
-        std::iter::from_fn(move || {
-            if y >= self.height {
-                return None;
-            }
-
-            let val = (x as i32, y as i32);
-
-            x += 1;
-            if x >= self.width {
-                x = 0;
-                y += 1;
-            }
-
-            Some(val)
+                .all(|(n, c)| grid.get(p + dir * n) == Some(c))
         })
-    }
-
-    fn search_xmas(&self) -> usize {
-        self.points().map(|p| self.xmas_count(p)).sum()
-    }
-
-    fn search_mas(&self) -> usize {
-        self.points().filter(|p| self.is_mas(*p)).count()
-    }
-}
-
-#[derive(Debug, Copy, Clone)]
-enum Direction {
-    Right,
-    Left,
-    Up,
-    Down,
-    UpLeft,
-    UpRight,
-    DownLeft,
-    DownRight,
+        .count()
 }
 
-impl Direction {
-    fn offset(self, (x, y): (i32, i32)) -> (i32, i32) {
-        let (off_x, off_y) = match self {
-            Direction::Right => (1, 0),
-            Direction::Left => (-1, 0),
-            Direction::Up => (0, -1),
-            Direction::Down => (0, 1),
-            Direction::UpLeft => (-1, -1),
-            Direction::UpRight => (1, -1),
-            Direction::DownLeft => (-1, 1),
-            Direction::DownRight => (1, 1),
-        };
+fn is_mas(grid: &Grid<u8>, p: Point) -> bool {
+    let Some(b'A') = grid.get(p) else {
+        return false;
+    };
 
-        (x + off_x, y + off_y)
-    }
-
-    fn all() -> &'static [Direction] {
-        &[
-            Direction::Right,
-            Direction::Left,
-            Direction::Up,
-            Direction::Down,
-            Direction::UpLeft,
-            Direction::UpRight,
-            Direction::DownLeft,
-            Direction::DownRight,
-        ]
-    }
-}
-
-struct OffsetIter {
-    origin: (i32, i32),
-    offset: (i32, i32),
-    dir: Direction,
-}
-
-impl OffsetIter {
-    fn new(dir: Direction, origin: (i32, i32)) -> Self {
-        Self {
-            origin,
-            offset: (0, 0),
-            dir,
-        }
-    }
-}
+    let corners = [(-1, -1), (1, -1), (-1, 1), (1, 1)].map(|(x, y)| grid.get(p + Point::new(x, y)));
 
-impl Iterator for OffsetIter {
-    type Item = (i32, i32);
+    let [Some(&ul), Some(&ur), Some(&dl), Some(&dr)] = corners else {
+        return false;
+    };
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let val = (self.offset.0 + self.origin.0, self.offset.1 + self.origin.1);
-        self.offset = self.dir.offset(self.offset);
+    let falling = ul == b'M' && dr == b'S' || ul == b'S' && dr == b'M';
+    let rising = ur == b'M' && dl == b'S' || ur == b'S' && dl == b'M';
 
-        Some(val)
-    }
+    falling && rising
 }
 
 #[test]
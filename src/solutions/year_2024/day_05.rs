@@ -1,34 +1,31 @@
-use crate::HashMap;
+use crate::parse::Parser;
+use crate::{HashMap, ParseError};
 
-pub fn part_one(input: &str) -> u32 {
-    let mut lines = input.trim().lines();
+crate::solution!(part_one, part_two);
 
-    let rules = Rules::new(&mut lines);
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    let (rules, mut updates) = parse(input)?;
 
     let mut sum = 0;
-    let mut buf = Vec::new();
 
-    for line in lines {
-        let update = Update::new(line, &mut buf);
+    for pages in updates.iter_mut() {
+        let update = Update::new(pages);
 
         if update.in_order(&rules) {
             sum += update.mid();
         }
     }
 
-    sum
+    Ok(sum)
 }
 
-pub fn part_two(input: &str) -> u32 {
-    let mut lines = input.trim().lines();
-
-    let rules = Rules::new(&mut lines);
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
+    let (rules, mut updates) = parse(input)?;
 
     let mut sum = 0;
-    let mut buf = Vec::new();
 
-    for line in lines {
-        let mut update = Update::new(line, &mut buf);
+    for pages in updates.iter_mut() {
+        let mut update = Update::new(pages);
 
         if !update.in_order(&rules) {
             update.sort(&rules);
@@ -36,7 +33,22 @@ pub fn part_two(input: &str) -> u32 {
         }
     }
 
-    sum
+    Ok(sum)
+}
+
+fn parse(input: &str) -> Result<(Rules, Vec<Vec<u32>>), ParseError> {
+    let mut p = Parser::new(input);
+    let rules = p.section().ok_or_else(|| p.error("expected the rules"))?;
+    let updates = p.section().ok_or_else(|| p.error("expected the updates"))?;
+
+    let rules = rules.parse_lines(|p| {
+        let pre = p.int()?;
+        p.token("|")?;
+        Ok((pre, p.int()?))
+    })?;
+    let updates = updates.parse_lines(|p| p.separated(",", Parser::int))?;
+
+    Ok((Rules::new(rules), updates))
 }
 
 struct Rules {
@@ -44,24 +56,9 @@ struct Rules {
 }
 
 impl Rules {
-    fn new(lines: &mut std::str::Lines) -> Self {
+    fn new(pairs: Vec<(u32, u32)>) -> Self {
         let mut rules = HashMap::new();
-        for line in lines {
-            let line = line.trim();
-            if line.is_empty() {
-                break;
-            }
-            let Some((pre, post)) = line.trim().split_once("|") else {
-                continue;
-            };
-
-            let pre = pre.parse::<u32>().ok();
-            let post = post.parse::<u32>().ok();
-
-            let Some((pre, post)) = pre.zip(post) else {
-                continue;
-            };
-
+        for (pre, post) in pairs {
             let entry = rules.entry(post).or_insert(Vec::new());
             entry.push(pre);
         }
@@ -83,10 +80,7 @@ struct Update<'a> {
 }
 
 impl<'a> Update<'a> {
-    fn new(line: &str, buf: &'a mut Vec<u32>) -> Self {
-        buf.clear();
-        buf.extend(line.trim().split(',').filter_map(|n| n.parse::<u32>().ok()));
-
+    fn new(buf: &'a mut Vec<u32>) -> Self {
         Self { buf }
     }
 
@@ -161,6 +155,6 @@ fn test() {
 97,13,75,29,47
 "#;
 
-    assert_eq!(143, part_one(input));
-    assert_eq!(123, part_two(input));
+    assert_eq!(Ok(143), part_one(input));
+    assert_eq!(Ok(123), part_two(input));
 }
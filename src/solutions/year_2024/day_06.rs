@@ -1,4 +1,7 @@
 use crate::HashSet;
+use crate::grid::{Direction, Grid, Point};
+
+crate::solution!(part_one, part_two);
 
 pub fn part_one(input: &str) -> usize {
     let mut map = Map::new(input);
@@ -54,52 +57,31 @@ pub fn part_two(input: &str) -> u64 {
 }
 
 struct Map {
-    cells: Vec<Cell>,
+    cells: Grid<Cell>,
     start: Player,
     player: Player,
-    width: usize,
-    height: usize,
 }
 
 impl Map {
     fn new(input: &str) -> Self {
-        let input = input.trim().as_bytes();
-        let mut width = 0;
-        let mut height = 0;
-        let mut player = None;
-        let mut cells = Vec::with_capacity(input.len());
-
-        for b in input {
-            width += 1;
-            match b {
-                b'.' => cells.push(Cell::Empty),
-                b'#' => cells.push(Cell::Occupied),
-                b'\n' => {
-                    height += 1;
-                    width = 0;
-                }
-                b'^' | b'<' | b'>' | b'v' => {
-                    cells.push(Cell::Empty);
-                    let Ok(direction) = Direction::try_from(*b) else {
-                        unreachable!("only valid directions possible")
-                    };
-
-                    player = Some(Player {
-                        position: (width - 1, height),
-                        direction,
-                    });
-                }
-                _ => unreachable!("invalid input"),
-            }
-        }
-
-        height += 1;
-        let player = player.expect("valid player in input");
+        let chars =
+            Grid::parse(input.trim(), |c| "#.^<>v".contains(c).then_some(c)).expect("valid grid");
+        let player = chars
+            .iter()
+            .find_map(|(position, &c)| {
+                Direction::from_char(c).map(|direction| Player {
+                    position,
+                    direction,
+                })
+            })
+            .expect("valid player in input");
+        let cells = chars.map(|&c| match c {
+            '#' => Cell::Occupied,
+            _ => Cell::Empty,
+        });
 
         Self {
             cells,
-            width: width as usize,
-            height: height as usize,
             start: player,
             player,
         }
@@ -109,47 +91,14 @@ impl Map {
         self.player = self.start;
     }
 
-    fn get(&self, (x, y): (i32, i32)) -> Option<Cell> {
-        if x < 0 || y < 0 {
-            return None;
-        }
-        let x = x as usize;
-        let y = y as usize;
-
-        if x >= self.width || y >= self.height {
-            return None;
-        }
-
-        let idx = y * self.width + x;
-        self.cells.get(idx).copied()
-    }
-
-    fn get_mut(&mut self, (x, y): (i32, i32)) -> Option<&mut Cell> {
-        if x < 0 || y < 0 {
-            return None;
-        }
-        let x = x as usize;
-        let y = y as usize;
-
-        if x >= self.width || y >= self.height {
-            return None;
-        }
-
-        let idx = y * self.width + x;
-        self.cells.get_mut(idx)
-    }
-
-    fn set(&mut self, pos: (i32, i32), new_cell: Cell) {
-        let Some(cell) = self.get_mut(pos) else {
-            return;
-        };
-        *cell = new_cell;
+    fn set(&mut self, pos: Point, new_cell: Cell) {
+        self.cells.set(pos, new_cell);
     }
 
     fn make_move(&mut self) -> bool {
         let new_position = self.player.next_move();
 
-        match self.get(new_position) {
+        match self.cells.get(new_position) {
             Some(Cell::Empty) => {
                 self.player.position = new_position;
                 true
@@ -162,7 +111,7 @@ impl Map {
         }
     }
 
-    fn position(&self) -> (i32, i32) {
+    fn position(&self) -> Point {
         self.player.position
     }
 }
@@ -175,59 +124,13 @@ enum Cell {
 
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
 struct Player {
-    position: (i32, i32),
+    position: Point,
     direction: Direction,
 }
 
 impl Player {
-    fn next_move(&self) -> (i32, i32) {
-        let offset = self.direction.offset();
-        (offset.0 + self.position.0, offset.1 + self.position.1)
-    }
-}
-
-#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
-enum Direction {
-    North,
-    West,
-    South,
-    East,
-}
-
-impl Direction {
-    fn offset(self) -> (i32, i32) {
-        match self {
-            Direction::North => (0, -1),
-            Direction::West => (-1, 0),
-            Direction::South => (0, 1),
-            Direction::East => (1, 0),
-        }
-    }
-
-    fn turn_right(self) -> Self {
-        match self {
-            Direction::North => Direction::East,
-            Direction::West => Direction::North,
-            Direction::South => Direction::West,
-            Direction::East => Direction::South,
-        }
-    }
-}
-
-#[derive(Debug, Copy, Clone)]
-struct DirectionFromU8Err;
-
-impl TryFrom<u8> for Direction {
-    type Error = DirectionFromU8Err;
-
-    fn try_from(value: u8) -> Result<Self, Self::Error> {
-        match value {
-            b'^' => Ok(Direction::North),
-            b'>' => Ok(Direction::East),
-            b'v' => Ok(Direction::South),
-            b'<' => Ok(Direction::West),
-            _ => Err(DirectionFromU8Err),
-        }
+    fn next_move(&self) -> Point {
+        self.position.step(self.direction)
     }
 }
 
@@ -1,34 +1,31 @@
-pub fn part_one(input: &str) -> u64 {
+use crate::ParseError;
+use crate::parse::{self, Parser};
+
+crate::solution!(part_one, part_two);
+
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
     solve(Evaluator::part_one(), input)
 }
 
-pub fn part_two(input: &str) -> u64 {
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
     solve(Evaluator::part_two(), input)
 }
 
-fn solve(mut eval: Evaluator, input: &str) -> u64 {
-    let lines = input.trim().lines();
+fn solve(mut eval: Evaluator, input: &str) -> Result<u64, ParseError> {
+    let equations = parse::lines(input, |p| {
+        let test = p.int()?;
+        p.token(": ")?;
+        Ok((test, p.separated(" ", Parser::int)?))
+    })?;
 
-    let mut all_nums = Vec::new();
     let mut valid = 0;
-    for line in lines {
-        let Some((test, nums)) = line.split_once(": ") else {
-            continue;
-        };
-
-        let Some(test) = test.parse::<u64>().ok() else {
-            continue;
-        };
-
-        all_nums.clear();
-        all_nums.extend(nums.split(" ").filter_map(|n| n.parse::<u64>().ok()));
-
-        if eval.evaluate(test, &all_nums) {
+    for (test, nums) in equations {
+        if eval.evaluate(test, &nums) {
             valid += test;
         }
     }
 
-    valid
+    Ok(valid)
 }
 
 struct Evaluator {
@@ -109,6 +106,6 @@ fn test() {
 21037: 9 7 18 13
 292: 11 6 16 20"#;
 
-    assert_eq!(3749, part_one(input));
-    assert_eq!(11387, part_two(input));
+    assert_eq!(Ok(3749), part_one(input));
+    assert_eq!(Ok(11387), part_two(input));
 }
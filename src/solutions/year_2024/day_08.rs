@@ -1,4 +1,8 @@
 use crate::HashSet;
+use crate::geometry::Rect;
+use crate::grid::{Grid, Point};
+
+crate::solution!(part_one, part_two);
 
 pub fn part_one(input: &str) -> usize {
     AntennaMap::new(input).part_one()
@@ -9,35 +13,25 @@ pub fn part_two(input: &str) -> usize {
 }
 
 struct AntennaMap {
-    width: i32,
-    height: i32,
+    bounds: Rect<i32>,
     antennas: Vec<Vec<Point>>,
 }
 
 impl AntennaMap {
     fn new(input: &str) -> Self {
-        let input = input.trim().as_bytes();
+        let cells =
+            Grid::parse(input.trim(), |c| c.is_ascii().then_some(c as u8)).expect("valid grid");
         let mut antennas = vec![Vec::new(); 10 + 26 + 26];
-        let mut width = 0;
-        let mut height = 0;
-        for &b in input {
-            if b == b'\n' {
-                height += 1;
-                width = 0;
-                continue;
-            }
-
+        for (p, &b) in cells.iter() {
             if let Some(b) = antenna_idx(b) {
-                antennas[b].push(Point(width, height));
+                antennas[b].push(p);
             }
-
-            width += 1;
         }
-        height += 1;
+
+        let size = Point::new(cells.width() as i32, cells.height() as i32);
 
         Self {
-            width,
-            height,
+            bounds: Rect::new(Point::new(0, 0), size - 1),
             antennas,
         }
     }
@@ -58,12 +52,12 @@ impl AntennaMap {
             let b_diff = b - a;
 
             let a_node = a + a_diff;
-            if a_node.in_bounds(self.width, self.height) {
+            if self.bounds.contains(a_node) {
                 antinodes.insert(a_node);
             }
 
             let b_node = b + b_diff;
-            if b_node.in_bounds(self.width, self.height) {
+            if self.bounds.contains(b_node) {
                 antinodes.insert(b_node);
             }
         }
@@ -78,13 +72,13 @@ impl AntennaMap {
             let b_diff = b - a;
 
             let mut a_node = a;
-            while a_node.in_bounds(self.width, self.height) {
+            while self.bounds.contains(a_node) {
                 antinodes.insert(a_node);
                 a_node += a_diff;
             }
 
             let mut b_node = b;
-            while b_node.in_bounds(self.width, self.height) {
+            while self.bounds.contains(b_node) {
                 antinodes.insert(b_node);
                 b_node += b_diff;
             }
@@ -105,39 +99,6 @@ fn antenna_idx(b: u8) -> Option<usize> {
     v.map(|b| b as usize)
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-struct Point(i32, i32);
-
-impl Point {
-    fn in_bounds(&self, width: i32, height: i32) -> bool {
-        self.0 >= 0 && self.0 < width && self.1 >= 0 && self.1 < height
-    }
-}
-
-impl std::ops::Add for Point {
-    type Output = Point;
-
-    fn add(self, Point(b_x, b_y): Self) -> Self::Output {
-        let Point(a_x, a_y) = self;
-        Point(a_x + b_x, a_y + b_y)
-    }
-}
-
-impl std::ops::AddAssign for Point {
-    fn add_assign(&mut self, rhs: Self) {
-        *self = *self + rhs;
-    }
-}
-
-impl std::ops::Sub for Point {
-    type Output = Point;
-
-    fn sub(self, Point(b_x, b_y): Self) -> Self::Output {
-        let Point(a_x, a_y) = self;
-        Point(a_x - b_x, a_y - b_y)
-    }
-}
-
 #[test]
 fn test() {
     let input = r#"............
@@ -1,3 +1,5 @@
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> u64 {
     let file_list = input.trim().chars().filter_map(|n| n.to_digit(10));
 
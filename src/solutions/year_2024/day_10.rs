@@ -1,4 +1,7 @@
 use crate::HashSet;
+use crate::grid::{Grid, Point};
+
+crate::solution!(part_one, part_two);
 
 pub fn part_one(input: &str) -> u64 {
     Map::new(input).part_one()
@@ -9,65 +12,25 @@ pub fn part_two(input: &str) -> u64 {
 }
 
 struct Map {
-    cells: Vec<u8>,
-    width: usize,
-    height: usize,
-    trailheads: Vec<(i32, i32)>,
+    cells: Grid<u8>,
+    trailheads: Vec<Point>,
 }
 
 impl Map {
     fn new(input: &str) -> Self {
-        let input = input.trim().as_bytes();
-        let mut height = 0;
-        let mut width = 0;
-        let mut cells = Vec::with_capacity(input.len());
-        let mut trailheads = Vec::new();
-
-        for b in input {
-            match b {
-                b'0'..=b'9' => {
-                    let n = b - b'0';
-                    cells.push(n);
-                    if n == 0 {
-                        trailheads.push((width as i32, height as i32));
-                    }
-                    width += 1;
-                }
-                b'\n' => {
-                    height += 1;
-                    width = 0;
-                }
-                _ => unreachable!("invalid map"),
-            }
-        }
-        height += 1;
-
-        Self {
-            cells,
-            width,
-            height,
-            trailheads,
-        }
-    }
+        let cells =
+            Grid::parse(input.trim(), |c| c.to_digit(10).map(|n| n as u8)).expect("valid grid");
+        let trailheads = cells
+            .iter()
+            .filter(|&(_, &n)| n == 0)
+            .map(|(p, _)| p)
+            .collect();
 
-    fn get(&self, (x, y): (i32, i32)) -> Option<u8> {
-        if x < 0 || y < 0 {
-            return None;
-        }
-        let x = x as usize;
-        let y = y as usize;
-        if x >= self.width || y >= self.height {
-            return None;
-        }
-        let idx = y * self.width + x;
-        self.cells.get(idx).copied()
+        Self { cells, trailheads }
     }
 
-    fn neighbors(&self, (x, y): (i32, i32)) -> impl Iterator<Item = (u8, (i32, i32))> + '_ {
-        [(-1, 0), (1, 0), (0, -1), (0, 1)]
-            .into_iter()
-            .map(move |(o_x, o_y)| (x + o_x, y + o_y))
-            .filter_map(|p| self.get(p).zip(Some(p)))
+    fn neighbors(&self, p: Point) -> impl Iterator<Item = (u8, Point)> + '_ {
+        self.cells.neighbors(p).map(|p| (self.cells[p], p))
     }
 
     fn score<S: Scorer>(&self, mut scorer: S) -> u64 {
@@ -105,7 +68,7 @@ impl Map {
 }
 
 struct PartOne {
-    routes: HashSet<((i32, i32), (i32, i32))>,
+    routes: HashSet<(Point, Point)>,
 }
 
 impl PartOne {
@@ -117,7 +80,7 @@ impl PartOne {
 }
 
 impl Scorer for PartOne {
-    fn add(&mut self, start: (i32, i32), end: (i32, i32)) {
+    fn add(&mut self, start: Point, end: Point) {
         self.routes.insert((start, end));
     }
 
@@ -137,7 +100,7 @@ impl PartTwo {
 }
 
 impl Scorer for PartTwo {
-    fn add(&mut self, _: (i32, i32), _: (i32, i32)) {
+    fn add(&mut self, _: Point, _: Point) {
         self.count += 1;
     }
 
@@ -147,7 +110,7 @@ impl Scorer for PartTwo {
 }
 
 trait Scorer {
-    fn add(&mut self, start: (i32, i32), end: (i32, i32));
+    fn add(&mut self, start: Point, end: Point);
     fn score(self) -> u64;
 }
 
@@ -1,4 +1,7 @@
 use crate::HashSet;
+use crate::grid::{Direction, Grid, Point};
+
+crate::solution!(part_one, part_two);
 
 pub fn part_one(input: &str) -> usize {
     let map = Map::new(input);
@@ -15,61 +18,24 @@ pub fn part_two(input: &str) -> usize {
 }
 
 struct Map {
-    cells: Vec<u8>,
-    width: usize,
-    height: usize,
+    cells: Grid<u8>,
 }
 
 impl Map {
     fn new(input: &str) -> Self {
-        let input = input.trim().as_bytes();
-        let mut width = 0;
-        let mut height = 0;
-        let mut cells = Vec::new();
-
-        for &b in input {
-            match b {
-                b'\n' => {
-                    height += 1;
-                    width = 0;
-                }
-                _ => {
-                    cells.push(b);
-                    width += 1;
-                }
-            }
-        }
+        let cells =
+            Grid::parse(input.trim(), |c| c.is_ascii().then_some(c as u8)).expect("valid grid");
 
-        height += 1;
-
-        Self {
-            cells,
-            width,
-            height,
-        }
+        Self { cells }
     }
 
-    fn get(&self, (x, y): (i32, i32)) -> Option<u8> {
-        if x < 0 || y < 0 {
-            return None;
-        }
-        let x = x as usize;
-        let y = y as usize;
-        if x >= self.width || y >= self.height {
-            return None;
-        }
-
-        let idx = y * self.width + x;
-        self.cells.get(idx).copied()
+    fn get(&self, p: Point) -> Option<u8> {
+        self.cells.get(p).copied()
     }
 
-    fn neighbors(
-        &self,
-        (x, y): (i32, i32),
-    ) -> impl Iterator<Item = (Option<u8>, (i32, i32), Direction)> + '_ {
-        Direction::all().into_iter().copied().map(move |dir| {
-            let (x_off, y_off) = dir.offset();
-            let p = (x + x_off, y + y_off);
+    fn neighbors(&self, p: Point) -> impl Iterator<Item = (Option<u8>, Point, Direction)> + '_ {
+        Direction::ALL.into_iter().map(move |dir| {
+            let p = p.step(dir);
             (self.get(p), p, dir)
         })
     }
@@ -77,7 +43,7 @@ impl Map {
     fn find_regions(&self) -> Vec<Region> {
         let mut regions = Vec::new();
 
-        let first = (0, 0);
+        let first = Point::new(0, 0);
         let Some(mut current) = self.get(first) else {
             return regions;
         };
@@ -189,38 +155,11 @@ impl Region {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    fn all() -> &'static [Direction] {
-        &[
-            Direction::Up,
-            Direction::Down,
-            Direction::Left,
-            Direction::Right,
-        ]
-    }
-
-    fn offset(&self) -> (i32, i32) {
-        match self {
-            Direction::Up => (0, -1),
-            Direction::Down => (0, 1),
-            Direction::Left => (-1, 0),
-            Direction::Right => (1, 0),
-        }
-    }
-
-    fn fixed_flex(&self, (x, y): (i32, i32)) -> (i32, i32) {
-        match self {
-            Direction::Up | Direction::Down => (y, x),
-            Direction::Left | Direction::Right => (x, y),
-        }
+fn fixed_flex(dir: Direction, p: Point) -> (i32, i32) {
+    if dir.is_vertical() {
+        (p.y, p.x)
+    } else {
+        (p.x, p.y)
     }
 }
 
@@ -233,8 +172,8 @@ struct Edge {
 }
 
 impl Edge {
-    fn new(p: (i32, i32), dir: Direction) -> Self {
-        let (fixed, flex) = dir.fixed_flex(p);
+    fn new(p: Point, dir: Direction) -> Self {
+        let (fixed, flex) = fixed_flex(dir, p);
 
         Self {
             dir,
@@ -244,12 +183,12 @@ impl Edge {
         }
     }
 
-    fn extend(&mut self, p: (i32, i32), dir: Direction) -> bool {
+    fn extend(&mut self, p: Point, dir: Direction) -> bool {
         if self.dir != dir {
             return false;
         }
 
-        let (fixed, flex) = dir.fixed_flex(p);
+        let (fixed, flex) = fixed_flex(dir, p);
 
         if self.fixed != fixed {
             return false;
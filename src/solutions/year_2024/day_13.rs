@@ -1,19 +1,28 @@
-pub fn part_one(input: &str) -> i64 {
-    input
-        .trim()
-        .split("\n\n")
-        .map(|s| s.parse().unwrap())
-        .filter_map(|s| solve::<0>(&s))
-        .sum()
+use crate::ParseError;
+use crate::geometry::Point2;
+use crate::parse::Parser;
+
+crate::solution!(part_one, part_two);
+
+type Point = Point2<i64>;
+
+pub fn part_one(input: &str) -> Result<i64, ParseError> {
+    let cost = parse(input)?.iter().filter_map(solve::<0>).sum();
+
+    Ok(cost)
+}
+
+pub fn part_two(input: &str) -> Result<i64, ParseError> {
+    let cost = parse(input)?
+        .iter()
+        .filter_map(solve::<10000000000000>)
+        .sum();
+
+    Ok(cost)
 }
 
-pub fn part_two(input: &str) -> i64 {
-    input
-        .trim()
-        .split("\n\n")
-        .map(|s| s.parse().unwrap())
-        .filter_map(|s| solve::<10000000000000>(&s))
-        .sum()
+fn parse(input: &str) -> Result<Vec<Scenario>, ParseError> {
+    Parser::new(input).sections().map(Scenario::parse).collect()
 }
 
 fn solve<const OFFSET: i64>(scenario: &Scenario) -> Option<i64> {
@@ -71,25 +80,19 @@ struct Scenario {
     target: Point,
 }
 
-#[derive(Debug, Copy, Clone)]
-struct ParseErr;
-
-impl std::str::FromStr for Scenario {
-    type Err = ParseErr;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let s = s.trim();
-        let mut lines = s.lines();
-        let button = lines.next().ok_or(ParseErr)?;
-        let button_a = button.parse()?;
-        let button = lines.next().ok_or(ParseErr)?;
-        let button_b = button.parse()?;
-        let target = lines.next().ok_or(ParseErr)?;
-        let target = target.get("Prize: X=".len()..).ok_or(ParseErr)?;
-        let (x, y) = target.split_once(", Y=").ok_or(ParseErr)?;
-
-        let x = x.parse().map_err(|_| ParseErr)?;
-        let y = y.parse().map_err(|_| ParseErr)?;
+impl Scenario {
+    fn parse(mut p: Parser) -> Result<Self, ParseError> {
+        p.token("Button A: ")?;
+        let button_a = Button::parse(&mut p)?;
+        p.skip_whitespace();
+        p.token("Button B: ")?;
+        let button_b = Button::parse(&mut p)?;
+        p.skip_whitespace();
+        p.token("Prize: X=")?;
+        let x = p.int()?;
+        p.token(", Y=")?;
+        let y = p.int()?;
+        p.end()?;
 
         Ok(Scenario {
             button_a,
@@ -104,16 +107,12 @@ struct Button {
     offset: Point,
 }
 
-impl std::str::FromStr for Button {
-    type Err = ParseErr;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let s = s.trim();
-        let s = s.get("Button A: X+".len()..).ok_or(ParseErr)?;
-        let (x, y) = s.split_once(", Y+").ok_or(ParseErr)?;
-
-        let x = x.parse().map_err(|_| ParseErr)?;
-        let y = y.parse().map_err(|_| ParseErr)?;
+impl Button {
+    fn parse(p: &mut Parser) -> Result<Self, ParseError> {
+        p.token("X+")?;
+        let x = p.int()?;
+        p.token(", Y+")?;
+        let y = p.int()?;
 
         Ok(Button {
             offset: Point::new(x, y),
@@ -121,35 +120,6 @@ impl std::str::FromStr for Button {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-struct Point {
-    x: i64,
-    y: i64,
-}
-
-impl Point {
-    fn new(x: i64, y: i64) -> Self {
-        Self { x, y }
-    }
-}
-
-impl std::ops::Add for Point {
-    type Output = Self;
-
-    fn add(self, Point { x, y }: Self) -> Self::Output {
-        Point::new(self.x + x, self.y + y)
-    }
-}
-
-impl std::ops::Mul<i64> for Point {
-    type Output = Self;
-
-    fn mul(self, rhs: i64) -> Self::Output {
-        let Point { x, y } = self;
-        Point::new(x * rhs, y * rhs)
-    }
-}
-
 #[test]
 fn test() {
     let input = r#"Button A: X+94, Y+34
@@ -169,5 +139,5 @@ Button B: X+27, Y+71
 Prize: X=18641, Y=10279
 "#;
 
-    assert_eq!(480, part_one(input));
+    assert_eq!(Ok(480), part_one(input));
 }
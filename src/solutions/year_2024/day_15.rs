@@ -1,9 +1,12 @@
+use crate::grid::{Direction, Grid, Point};
+
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> usize {
     let (map, moves) = input.trim().split_once("\n\n").unwrap();
     let mut map = Map::new(map);
-    let moves = Moves::new(moves);
 
-    for dir in moves {
+    for dir in moves.chars().filter_map(Direction::from_char) {
         map.make_move(dir);
     }
 
@@ -13,105 +16,51 @@ pub fn part_one(input: &str) -> usize {
 pub fn part_two(input: &str) -> u64 {
     let (map, moves) = input.trim().split_once("\n\n").unwrap();
     let mut map = WideMap::new(map);
-    let moves = Moves::new(moves);
 
-    for dir in moves {
+    for dir in moves.chars().filter_map(Direction::from_char) {
         map.make_move(dir);
     }
 
     map.score()
 }
 
+fn parse(input: &str) -> (Grid<char>, Point) {
+    let chars = Grid::parse(input.trim(), |c| "#O.@".contains(c).then_some(c)).expect("valid grid");
+    let bot = chars.position(|&c| c == '@').unwrap();
+
+    (chars, bot)
+}
+
 struct Map {
-    cells: Vec<Cell>,
-    width: usize,
-    height: usize,
-    bot: (i32, i32),
+    cells: Grid<Cell>,
+    bot: Point,
 }
 
 impl Map {
     fn new(input: &str) -> Self {
-        let input = input.trim().as_bytes();
-        let mut cells = Vec::with_capacity(input.len());
-        let mut width = 0;
-        let mut height = 0;
-        let mut bot = None;
-
-        for &b in input {
-            let cell = match b {
-                b'\n' => {
-                    height += 1;
-                    width = 0;
-                    continue;
-                }
-                b'#' => Cell::Wall,
-                b'O' => Cell::Box,
-                b'.' => Cell::Empty,
-                b'@' => {
-                    bot = Some((width as i32, height as i32));
-                    Cell::Empty
-                }
-                _ => unreachable!(),
-            };
-            cells.push(cell);
-            width += 1;
-        }
-
-        height += 1;
-
-        Self {
-            cells,
-            width,
-            height,
-            bot: bot.unwrap(),
-        }
-    }
-
-    fn get(&self, (x, y): (i32, i32)) -> Option<Cell> {
-        if x < 0 || y < 0 {
-            return None;
-        }
-        let x = x as usize;
-        let y = y as usize;
-        if x >= self.width || y >= self.height {
-            return None;
-        }
-        let ind = y * self.width + x;
-
-        self.cells.get(ind).copied()
-    }
-
-    fn set(&mut self, (x, y): (i32, i32), cell: Cell) {
-        if x < 0 || y < 0 {
-            return;
-        }
-        let x = x as usize;
-        let y = y as usize;
-        if x >= self.width || y >= self.height {
-            return;
-        }
-        let ind = y * self.width + x;
+        let (chars, bot) = parse(input);
+        let cells = chars.map(|&c| match c {
+            '#' => Cell::Wall,
+            'O' => Cell::Box,
+            _ => Cell::Empty,
+        });
 
-        if let Some(c) = self.cells.get_mut(ind) {
-            *c = cell
-        }
+        Self { cells, bot }
     }
 
-    fn nudge(&mut self, p: (i32, i32), dir: Direction) -> bool {
-        let target = dir.apply(p);
+    fn nudge(&mut self, p: Point, dir: Direction) -> bool {
+        let target = p.step(dir);
 
-        let space = match self.get(target) {
+        let space = match self.cells.get(target) {
             Some(Cell::Empty) => true,
             Some(Cell::Wall) => false,
             Some(Cell::Box) => self.nudge(target, dir),
             None => false,
         };
 
-        if space {
-            if let Some(Cell::Box) = self.get(p) {
-                self.set(target, Cell::Box);
-                self.set(p, Cell::Empty);
-            };
+        if space && let Some(Cell::Box) = self.cells.get(p) {
+            self.cells.set(target, Cell::Box);
+            self.cells.set(p, Cell::Empty);
         }
 
         space
@@ -120,21 +69,16 @@ impl Map {
     fn make_move(&mut self, dir: Direction) {
         let bot = self.bot;
         if self.nudge(bot, dir) {
-            self.bot = dir.apply(bot);
+            self.bot = bot.step(dir);
         }
     }
 
     fn score(&self) -> usize {
-        let mut score = 0;
-        for y in 0..self.height {
-            for x in 0..self.width {
-                if let Some(Cell::Box) = self.get((x as i32, y as i32)) {
-                    score += 100 * y + x;
-                }
-            }
-        }
-
-        score
+        self.cells
+            .iter()
+            .filter(|&(_, &cell)| cell == Cell::Box)
+            .map(|(p, _)| 100 * p.y as usize + p.x as usize)
+            .sum()
     }
 }
 
@@ -146,76 +90,33 @@ enum Cell {
 }
 
 struct WideMap {
-    walls: Vec<bool>,
+    walls: Grid<bool>,
     boxes: Vec<Box>,
-    width: usize,
-    height: usize,
-    bot: (i32, i32),
+    bot: Point,
 }
 
 impl WideMap {
     fn new(input: &str) -> Self {
-        let input = input.trim().as_bytes();
-        let mut walls = Vec::with_capacity(input.len() * 2);
-        let mut width = 0;
-        let mut height = 0;
-        let mut bot = None;
-        let mut boxes = Vec::new();
-
-        for &b in input {
-            let cell = match b {
-                b'\n' => {
-                    height += 1;
-                    width = 0;
-                    continue;
-                }
-                b'#' => true,
-                b'O' => {
-                    boxes.push(Box {
-                        x: width as i32,
-                        y: height as i32,
-                    });
-                    false
-                }
-                b'.' => false,
-                b'@' => {
-                    bot = Some((width as i32, height as i32));
-                    false
-                }
-                _ => unreachable!(),
-            };
-            walls.push(cell);
-            walls.push(cell);
-            width += 2;
-        }
-
-        height += 1;
+        let (chars, bot) = parse(input);
+        let walls = chars.cells().iter().flat_map(|&c| [c == '#'; 2]).collect();
+        let walls = Grid::from_vec(chars.width() * 2, chars.height(), walls);
+        let boxes = chars
+            .iter()
+            .filter(|&(_, &c)| c == 'O')
+            .map(|(p, _)| Box {
+                left: Point::new(p.x * 2, p.y),
+            })
+            .collect();
 
         Self {
             walls,
             boxes,
-            width,
-            height,
-            bot: bot.unwrap(),
-        }
-    }
-
-    fn get(&self, (x, y): (i32, i32)) -> Option<bool> {
-        if x < 0 || y < 0 {
-            return None;
-        }
-        let x = x as usize;
-        let y = y as usize;
-        if x >= self.width || y >= self.height {
-            return None;
+            bot: Point::new(bot.x * 2, bot.y),
         }
-        let ind = y * self.width + x;
-
-        self.walls.get(ind).copied()
     }
 
-    fn can_nudge(&self, p: (i32, i32), dir: Direction) -> bool {
-        if self.get(p).unwrap_or(true) {
+    fn can_nudge(&self, p: Point, dir: Direction) -> bool {
+        if self.walls.get(p).copied().unwrap_or(true) {
             false
         } else {
             let mut nudge = true;
@@ -236,7 +137,7 @@ impl WideMap {
         }
     }
 
-    fn nudge(&mut self, p: (i32, i32), dir: Direction) {
+    fn nudge(&mut self, p: Point, dir: Direction) {
         for b in 0..self.boxes.len() {
             if self.boxes[b].collides(p) {
                 let (l, r) = self.boxes[b].neighbors(dir);
@@ -246,14 +147,14 @@ impl WideMap {
                 if let Some(r) = r {
                     self.nudge(r, dir);
                 }
-                self.boxes[b].make_move(dir);
+                self.boxes[b].left = self.boxes[b].left.step(dir);
                 break;
             }
         }
     }
 
     fn make_move(&mut self, dir: Direction) {
-        let bot = dir.apply(self.bot);
+        let bot = self.bot.step(dir);
         if self.can_nudge(bot, dir) {
             self.nudge(bot, dir);
             self.bot = bot;
@@ -263,7 +164,7 @@ impl WideMap {
     fn score(&self) -> u64 {
         let mut score = 0;
         for b in &self.boxes {
-            score += 100 * (b.y as u64) + (b.x as u64);
+            score += 100 * (b.left.y as u64) + (b.left.x as u64);
         }
 
         score
@@ -272,82 +173,26 @@ impl WideMap {
 
 #[derive(Debug, Copy, Clone)]
 struct Box {
-    x: i32,
-    y: i32,
+    left: Point,
 }
 
 impl Box {
-    fn make_move(&mut self, dir: Direction) {
-        let p = (self.x, self.y);
-        let (x, y) = dir.apply(p);
-        self.x = x;
-        self.y = y;
+    fn right(self) -> Point {
+        self.left.step(Direction::Right)
     }
 
-    fn neighbors(self, dir: Direction) -> (Option<(i32, i32)>, Option<(i32, i32)>) {
-        let l = (self.x, self.y);
-        let r = (self.x + 1, self.y);
+    fn neighbors(self, dir: Direction) -> (Option<Point>, Option<Point>) {
+        let l = self.left.step(dir);
+        let r = self.right().step(dir);
         match dir {
-            Direction::Up | Direction::Down => (Some(dir.apply(l)), Some(dir.apply(r))),
-            Direction::Left => (Some(dir.apply(l)), None),
-            Direction::Right => (None, Some(dir.apply(r))),
+            Direction::Up | Direction::Down => (Some(l), Some(r)),
+            Direction::Left => (Some(l), None),
+            Direction::Right => (None, Some(r)),
         }
     }
 
-    fn collides(&self, (x, y): (i32, i32)) -> bool {
-        (self.x == x && self.y == y) || (self.x + 1 == x && self.y == y)
-    }
-}
-
-#[derive(Debug, Copy, Clone)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    fn apply(&self, (x, y): (i32, i32)) -> (i32, i32) {
-        match self {
-            Direction::Up => (x, y - 1),
-            Direction::Down => (x, y + 1),
-            Direction::Left => (x - 1, y),
-            Direction::Right => (x + 1, y),
-        }
-    }
-}
-
-struct Moves<'a> {
-    input: &'a [u8],
-    cursor: usize,
-}
-
-impl<'a> Moves<'a> {
-    fn new(input: &'a str) -> Self {
-        let input = input.trim().as_bytes();
-        Self { input, cursor: 0 }
-    }
-}
-
-impl<'a> Iterator for Moves<'a> {
-    type Item = Direction;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            let v = self.input.get(self.cursor)?;
-            self.cursor += 1;
-            let dir = match &v {
-                b'^' => Direction::Up,
-                b'v' => Direction::Down,
-                b'<' => Direction::Left,
-                b'>' => Direction::Right,
-                b'\n' => continue,
-                _ => unreachable!(),
-            };
-
-            return Some(dir);
-        }
+    fn collides(&self, p: Point) -> bool {
+        self.left == p || self.right() == p
     }
 }
 
@@ -1,7 +1,11 @@
-use std::collections::BinaryHeap;
+use crate::ParseError;
+use crate::parse::Parser;
+use crate::search;
 
-pub fn part_one(input: &str) -> String {
-    let (mut machine, program) = parse(input).unwrap();
+crate::solution!(part_one, part_two);
+
+pub fn part_one(input: &str) -> Result<String, ParseError> {
+    let (mut machine, program) = parse(input)?;
 
     let mut output = String::new();
     while let Interrupt::Output(b) = machine.run(&program) {
@@ -12,45 +16,33 @@ pub fn part_one(input: &str) -> String {
         output.push(c);
     }
 
-    output
+    Ok(output)
 }
 
-pub fn part_two(input: &str) -> u64 {
-    let (machine, program) = parse(input).unwrap();
-
-    let mut output = Vec::with_capacity(program.len());
-    let mut heap = BinaryHeap::new();
-    heap.push(Search(0, 0));
-
-    while let Some(Search(base_a, valid)) = heap.pop() {
-        'outer: for n in 0..0x400 {
-            let mut machine = machine.clone();
-            output.clear();
-
-            let a = n << (valid * 3) | base_a;
-            machine.a = a;
-
-            while let Interrupt::Output(b) = machine.run(&program) {
-                if program.get(output.len()) == Some(&b) {
-                    output.push(b);
-
-                    if output.len() > valid {
-                        let valid = output.len();
-                        let mask = (1 << (valid * 3)) - 1;
-                        heap.push(Search(a & mask, valid));
-                    }
-                } else {
-                    continue 'outer;
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
+    let (machine, program) = parse(input)?;
+
+    // candidates are the low bits of register a that make the program output the first
+    // `valid` values of itself, the cost of reaching one is the value of those bits so
+    // the lowest quine is found first
+    let quine = search::dijkstra(
+        [(0, 0)],
+        |&(base_a, valid): &(u64, usize)| {
+            let mut candidates = Vec::new();
+            for n in 0..0x400 {
+                let a = n << (valid * 3) | base_a;
+                let (matched, _) = machine.quine_prefix(&program, a);
+                for valid in valid + 1..=matched {
+                    let mask = (1 << (valid * 3)) - 1;
+                    candidates.push(((a & mask, valid), (a & mask) - base_a));
                 }
             }
+            candidates
+        },
+        |&(a, valid)| valid == program.len() && machine.quine_prefix(&program, a).1,
+    );
 
-            if output.len() == program.len() {
-                return a;
-            }
-        }
-    }
-
-    0
+    Ok(quine.map_or(0, |((a, _), _)| a))
 }
 
 #[derive(Debug, Clone)]
@@ -62,6 +54,23 @@ struct Machine {
 }
 
 impl Machine {
+    /// The number of leading outputs for register `a` that match `program`, and whether the
+    /// output is exactly `program`.
+    fn quine_prefix(&self, program: &[u8], a: u64) -> (usize, bool) {
+        let mut machine = self.clone();
+        machine.a = a;
+
+        let mut matched = 0;
+        while let Interrupt::Output(b) = machine.run(program) {
+            if program.get(matched) != Some(&b) {
+                return (matched, false);
+            }
+            matched += 1;
+        }
+
+        (matched, matched == program.len())
+    }
+
     fn run(&mut self, mem: &[u8]) -> Interrupt {
         while let Some((opcode, operand)) = self.read_instruction(mem) {
             let combo = self.combo(operand);
@@ -117,49 +126,25 @@ enum Interrupt {
     Output(u8),
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-struct Search(u64, usize);
-
-impl std::cmp::Ord for Search {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        match self.1.cmp(&other.1) {
-            std::cmp::Ordering::Equal => other.0.cmp(&self.0),
-            c => c,
-        }
-    }
-}
-
-impl std::cmp::PartialOrd for Search {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-fn parse(input: &str) -> Option<(Machine, Vec<u8>)> {
-    let mut lines = input.lines();
-    let a = take_token("Register A: ", lines.next()?)?.parse().ok()?;
-    let b = take_token("Register B: ", lines.next()?)?.parse().ok()?;
-    let c = take_token("Register C: ", lines.next()?)?.parse().ok()?;
-    let _ = lines.next()?;
-    let program = take_token("Program: ", lines.next()?)?
-        .as_bytes()
-        .iter()
-        .copied()
-        .filter(|&b| b != b',')
-        .map(|b| b - b'0')
-        .collect();
+fn parse(input: &str) -> Result<(Machine, Vec<u8>), ParseError> {
+    let mut p = Parser::new(input);
+    p.token("Register A: ")?;
+    let a = p.int()?;
+    p.skip_whitespace();
+    p.token("Register B: ")?;
+    let b = p.int()?;
+    p.skip_whitespace();
+    p.token("Register C: ")?;
+    let c = p.int()?;
+    p.skip_whitespace();
+    p.token("Program: ")?;
+    let program = p.separated(",", Parser::int)?;
+    p.skip_whitespace();
+    p.end()?;
 
     let machine = Machine { a, b, c, pc: 0 };
 
-    Some((machine, program))
-}
-
-fn take_token<'a>(token: &str, s: &'a str) -> Option<&'a str> {
-    if s.starts_with(token) {
-        Some(&s[token.len()..])
-    } else {
-        None
-    }
+    Ok((machine, program))
 }
 
 #[test]
@@ -170,7 +155,7 @@ Register C: 0
 
 Program: 0,1,5,4,3,0"#;
 
-    assert_eq!("4,6,3,5,6,3,5,2,1,0", part_one(input));
+    assert_eq!(Ok("4,6,3,5,6,3,5,2,1,0".to_string()), part_one(input));
 
     let input = r#"Register A: 2024
 Register B: 0
@@ -178,5 +163,5 @@ Register C: 0
  
 Program: 0,3,5,4,3,0"#;
 
-    assert_eq!(117440, part_two(input));
+    assert_eq!(Ok(117440), part_two(input));
 }
@@ -1,29 +1,30 @@
+use crate::search::Search;
+
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> u64 {
     let mut lines = input.trim().lines();
     let patterns = Patterns::new(lines.next().unwrap());
 
     let _ = lines.next().unwrap();
 
+    let mut search = Search::<usize>::new();
     let mut total = 0;
-    let mut search = std::collections::BinaryHeap::new();
     for design in lines {
         let design = design.trim().as_bytes();
-        search.clear();
-        search.push(0);
-
-        while let Some(len) = search.pop() {
-            if len == design.len() {
-                total += 1;
-                break;
-            }
-
-            for p in patterns.patterns() {
-                let end = len + p.len();
-                let range = len..end;
-                if Some(p) == design.get(range) {
-                    search.push(end);
-                }
-            }
+        let arranged = search.bfs(
+            0,
+            |&len| {
+                patterns
+                    .patterns()
+                    .filter(move |p| Some(*p) == design.get(len..len + p.len()))
+                    .map(move |p| len + p.len())
+            },
+            |&len| len == design.len(),
+        );
+
+        if arranged.is_some() {
+            total += 1;
         }
     }
 
@@ -1,3 +1,7 @@
+use crate::Answer;
+
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> usize {
     let mut lines = input.trim().lines();
 
@@ -35,8 +39,8 @@ pub fn part_one(input: &str) -> usize {
         .sum()
 }
 
-pub fn part_two(_input: &str) -> &'static str {
-    "Almost there..."
+pub fn part_two(_input: &str) -> Answer {
+    Answer::NotApplicable
 }
 
 #[derive(Debug, Copy, Clone)]
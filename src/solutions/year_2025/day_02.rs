@@ -1,5 +1,10 @@
-pub fn part_one(input: &str) -> u64 {
-    let items = parse_input(input);
+use crate::ParseError;
+use crate::parse::Parser;
+
+crate::solution!(part_one, part_two);
+
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
+    let items = parse_input(input)?;
 
     let mut invalids = 0;
 
@@ -27,11 +32,11 @@ pub fn part_one(input: &str) -> u64 {
         }
     }
 
-    invalids
+    Ok(invalids)
 }
 
-pub fn part_two(input: &str) -> u64 {
-    let items = parse_input(input);
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
+    let items = parse_input(input)?;
 
     let mut invalids = 0;
 
@@ -57,15 +62,20 @@ pub fn part_two(input: &str) -> u64 {
         }
     }
 
-    invalids
+    Ok(invalids)
 }
 
-fn parse_input(input: &str) -> impl Iterator<Item = (u64, u64)> {
-    input
-        .trim()
-        .split(',')
-        .filter_map(|i| i.split_once('-'))
-        .filter_map(|(s, e)| s.parse::<u64>().ok().zip(e.parse::<u64>().ok()))
+fn parse_input(input: &str) -> Result<Vec<(u64, u64)>, ParseError> {
+    let mut p = Parser::new(input);
+    let ranges = p.separated(",", |p| {
+        let start = p.int()?;
+        p.token("-")?;
+        Ok((start, p.int()?))
+    })?;
+    p.skip_whitespace();
+    p.end()?;
+
+    Ok(ranges)
 }
 
 fn digit_iter(mut num: u64) -> impl Iterator<Item = u64> + Clone {
@@ -85,6 +95,6 @@ fn test() {
     let input = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,\
         446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
 
-    assert_eq!(1227775554, part_one(input));
-    assert_eq!(4174379265, part_two(input));
+    assert_eq!(Ok(1227775554), part_one(input));
+    assert_eq!(Ok(4174379265), part_two(input));
 }
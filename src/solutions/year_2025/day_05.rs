@@ -1,25 +1,36 @@
 use std::cmp::Ordering;
 
-pub fn part_one(input: &str) -> usize {
-    let (ranges, items) = input.split_once("\n\n").expect("valid input");
+use crate::ParseError;
+use crate::parse::Parser;
+
+crate::solution!(part_one, part_two);
+
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    let (ranges, items) = parse(input)?;
     let ranges = OrderedRanges::new(ranges);
 
-    items
-        .lines()
-        .filter_map(|l| l.parse::<u64>().ok())
-        .filter(|&i| ranges.contains(i))
-        .count()
+    Ok(items.into_iter().filter(|&i| ranges.contains(i)).count())
 }
 
-pub fn part_two(input: &str) -> u64 {
-    let (ranges, _items) = input.split_once("\n\n").expect("valid input");
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
+    let (ranges, _items) = parse(input)?;
     let ranges = compact_and_sort_ranges(ranges);
 
-    ranges.iter().map(|r| r.len()).sum()
+    Ok(ranges.iter().map(|r| r.len()).sum())
+}
+
+fn parse(input: &str) -> Result<(Vec<Range>, Vec<u64>), ParseError> {
+    let mut p = Parser::new(input);
+    let ranges = p.section().ok_or_else(|| p.error("expected the ranges"))?;
+    let items = p.section().ok_or_else(|| p.error("expected the ids"))?;
+
+    Ok((
+        ranges.parse_lines(Range::parse)?,
+        items.parse_lines(Parser::int)?,
+    ))
 }
 
-fn compact_and_sort_ranges(range_input: &str) -> Vec<Range> {
-    let mut ranges: Vec<_> = range_input.lines().filter_map(Range::parse).collect();
+fn compact_and_sort_ranges(mut ranges: Vec<Range>) -> Vec<Range> {
     ranges.sort_unstable_by(|a, b| a.cmp(b).reverse());
 
     let mut results = Vec::new();
@@ -48,12 +59,12 @@ struct Range {
 }
 
 impl Range {
-    fn parse(line: &str) -> Option<Self> {
-        let (start, end) = line.split_once("-")?;
-        let start = start.parse().ok()?;
-        let end = end.parse().ok()?;
+    fn parse(p: &mut Parser) -> Result<Self, ParseError> {
+        let start = p.int()?;
+        p.token("-")?;
+        let end = p.int()?;
 
-        Some(Range { start, end })
+        Ok(Range { start, end })
     }
 
     fn compare(&self, item: u64) -> Ordering {
@@ -88,9 +99,9 @@ struct OrderedRanges {
 }
 
 impl OrderedRanges {
-    fn new(range_input: &str) -> Self {
+    fn new(ranges: Vec<Range>) -> Self {
         Self {
-            ranges: compact_and_sort_ranges(range_input),
+            ranges: compact_and_sort_ranges(ranges),
         }
     }
 
@@ -116,6 +127,6 @@ fn test() {
 32
 "#;
 
-    assert_eq!(3, part_one(input));
-    assert_eq!(14, part_two(input));
+    assert_eq!(Ok(3), part_one(input));
+    assert_eq!(Ok(14), part_two(input));
 }
@@ -1,144 +1,113 @@
-use std::{cmp::Reverse, collections::BinaryHeap};
+use std::cmp::Reverse;
 
-use crate::HashMap;
+use crate::geometry::Point3;
+use crate::search;
+use crate::{HashMap, HashSet};
+
+crate::solution!(part_one, part_two);
+
+type Point = Point3<u64>;
 
 pub fn part_one(input: &str) -> u64 {
     solve_part_one::<1000>(input)
 }
 
 fn solve_part_one<const CONNECTIONS: usize>(input: &str) -> u64 {
-    let points: Vec<_> = input.trim().lines().filter_map(Point::parse).collect();
-    let mut distances = order_by_distance(&points);
+    let points: Vec<_> = input.trim().lines().filter_map(parse_point).collect();
+    let mut distances = distances(&points);
+    let connections = CONNECTIONS.min(distances.len());
+    if connections < distances.len() {
+        distances.select_nth_unstable(connections);
+    }
 
-    let mut groups = Vec::new();
-    let mut connected = HashMap::new();
-    let mut count = 0;
+    let mut circuits: HashMap<u32, Vec<u32>> = HashMap::new();
+    for &(_, a, b) in &distances[..connections] {
+        circuits.entry(a).or_default().push(b);
+        circuits.entry(b).or_default().push(a);
+    }
 
-    while let Some((_, a, b)) = distances.pop() {
-        if count >= CONNECTIONS {
-            break;
+    let mut connected = HashSet::new();
+    let mut sizes = Vec::new();
+    for &junction in circuits.keys() {
+        if connected.contains(&junction) {
+            continue;
         }
-        count += 1;
 
-        let has_a = connected.get(&a).copied();
-        let has_b = connected.get(&b).copied();
-
-        match (has_a, has_b) {
-            (None, None) => {
-                connected.insert(a, groups.len());
-                connected.insert(b, groups.len());
-                groups.push(2);
-            }
-            (Some(a_id), Some(b_id)) if a_id == b_id => (),
-            (Some(a_id), Some(b_id)) => {
-                for current_id in connected.values_mut() {
-                    if *current_id == a_id {
-                        *current_id = b_id;
-                        groups[b_id] += 1;
-                    }
-                }
-                groups[a_id] = 0;
-            }
-            (Some(a_id), None) => {
-                connected.insert(b, a_id);
-                groups[a_id] += 1;
-            }
-            (None, Some(b_id)) => {
-                connected.insert(a, b_id);
-                groups[b_id] += 1;
-            }
-        }
+        let circuit = search::bfs_reachable(junction, |b| circuits[b].iter().copied());
+        sizes.push(circuit.len() as u64);
+        connected.extend(circuit.into_keys());
     }
 
-    groups.sort_unstable_by_key(|&g| Reverse(g));
-    groups.into_iter().take(3).filter(|&g| g != 0).product()
+    sizes.sort_unstable_by_key(|&size| Reverse(size));
+    sizes.into_iter().take(3).product()
 }
 
 pub fn part_two(input: &str) -> u64 {
-    let points: Vec<_> = input.trim().lines().filter_map(Point::parse).collect();
-    let mut distances = order_by_distance(&points);
-
-    let mut group_id = 0;
-    let mut empty_groups = 0;
-    let mut connected = HashMap::new();
-
-    while let Some((_, a, b)) = distances.pop() {
-        let has_a = connected.get(&a).copied();
-        let has_b = connected.get(&b).copied();
-
-        match (has_a, has_b) {
-            (None, None) => {
-                connected.insert(a, group_id);
-                connected.insert(b, group_id);
-                group_id += 1;
-            }
-            (Some(a_id), Some(b_id)) if a_id == b_id => (),
-            (Some(a_id), Some(b_id)) => {
-                empty_groups += 1;
-                for current_id in connected.values_mut() {
-                    if *current_id == a_id {
-                        *current_id = b_id;
-                    }
-                }
-            }
-            (Some(a_id), None) => {
-                connected.insert(b, a_id);
-            }
-            (None, Some(b_id)) => {
-                connected.insert(a, b_id);
-            }
-        }
-
-        if group_id - empty_groups == 1 && connected.len() == points.len() {
-            return points[a as usize].0 * points[b as usize].0;
-        }
+    let points: Vec<_> = input.trim().lines().filter_map(parse_point).collect();
+    if points.len() < 2 {
+        return 0;
     }
 
-    0
+    // the connection that joins the last two circuits is the longest one of the minimum
+    // spanning tree, which is the longest of the bottlenecks from any junction box
+    let bottlenecks = search::dijkstra_reachable([0], |&a: &usize| {
+        let a = points[a];
+        points
+            .iter()
+            .enumerate()
+            .map(move |(b, &p)| (b, Longest(a.euclidean_squared(p))))
+    });
+    let longest = bottlenecks.into_values().max().unwrap_or_default();
+
+    distances(&points)
+        .into_iter()
+        .find(|&(distance, _, _)| Longest(distance) == longest)
+        .map_or(0, |(_, a, b)| points[a as usize].x * points[b as usize].x)
 }
 
-fn order_by_distance(points: &[Point]) -> BinaryHeap<(Reverse<u64>, u32, u32)> {
-    let mut distances = BinaryHeap::with_capacity(points.len() * points.len() / 2);
+/// Cost of a path as its longest connection, `dijkstra` then finds the paths whose longest
+/// connection is as short as possible.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Longest(u64);
+
+impl std::ops::Add for Longest {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Longest(self.0.max(other.0))
+    }
+}
+
+fn distances(points: &[Point]) -> Vec<(u64, u32, u32)> {
+    let mut distances = Vec::with_capacity(points.len() * points.len() / 2);
 
     for (a_idx, &a) in points.iter().enumerate() {
         for (b_idx, &b) in points.iter().enumerate().skip(a_idx + 1) {
-            let dist = a.distance(b);
-
-            distances.push((Reverse(dist), a_idx as u32, b_idx as u32));
+            distances.push((a.euclidean_squared(b), a_idx as u32, b_idx as u32));
         }
     }
 
     distances
 }
 
-#[derive(Debug, Copy, Clone)]
-struct Point(u64, u64, u64);
-
-impl Point {
-    fn parse(line: &str) -> Option<Self> {
-        let mut parts = [0; 3];
-
-        let mut idx = 0;
-        for &b in line.as_bytes() {
-            match b {
-                b',' => {
-                    idx += 1;
-                }
-                b'0'..=b'9' => {
-                    parts[idx] *= 10;
-                    parts[idx] += (b - b'0') as u64;
-                }
-                _ => return None,
+fn parse_point(line: &str) -> Option<Point> {
+    let mut parts = [0; 3];
+
+    let mut idx = 0;
+    for &b in line.as_bytes() {
+        match b {
+            b',' => {
+                idx += 1;
             }
+            b'0'..=b'9' => {
+                parts[idx] *= 10;
+                parts[idx] += (b - b'0') as u64;
+            }
+            _ => return None,
         }
-
-        Some(Self(parts[0], parts[1], parts[2]))
     }
 
-    fn distance(self, Point(x2, y2, z2): Self) -> u64 {
-        let Point(x1, y1, z1) = self;
-        (x1.abs_diff(x2)).pow(2) + (y1.abs_diff(y2)).pow(2) + (z1.abs_diff(z2)).pow(2)
-    }
+    Some(Point::new(parts[0], parts[1], parts[2]))
 }
 
 #[test]
@@ -1,12 +1,18 @@
 use std::cmp::Reverse;
 
+use crate::geometry::{Point2, Rect};
+
+crate::solution!(part_one, part_two);
+
+type Point = Point2<i64>;
+
 pub fn part_one(input: &str) -> u64 {
-    let points: Vec<_> = input.trim().lines().filter_map(Point::parse).collect();
+    let points: Vec<_> = input.trim().lines().filter_map(parse_point).collect();
     let mut max = 0;
 
     for (a_idx, a) in points.iter().enumerate() {
         for b in points.iter().skip(a_idx + 1) {
-            max = a.area(b).max(max);
+            max = area(*a, *b).max(max);
         }
     }
 
@@ -14,7 +20,7 @@ pub fn part_one(input: &str) -> u64 {
 }
 
 pub fn part_two(input: &str) -> u64 {
-    let points: Vec<_> = input.trim().lines().filter_map(Point::parse).collect();
+    let points: Vec<_> = input.trim().lines().filter_map(parse_point).collect();
     let mut lines = Vec::with_capacity(points.len());
 
     for pair in points.windows(2) {
@@ -35,7 +41,7 @@ pub fn part_two(input: &str) -> u64 {
 
     for (a_idx, &a) in points.iter().enumerate() {
         'next: for &b in points.iter().skip(a_idx + 1) {
-            let area = a.area(&b);
+            let area = area(a, b);
             if area <= max {
                 continue;
             }
@@ -43,7 +49,7 @@ pub fn part_two(input: &str) -> u64 {
             let rect = Rect::new(a, b);
 
             for line in lines.iter() {
-                if rect.intersects(line) {
+                if intersects(&rect, line) {
                     continue 'next;
                 }
             }
@@ -55,59 +61,33 @@ pub fn part_two(input: &str) -> u64 {
     max
 }
 
-#[derive(Debug, Copy, Clone)]
-struct Point {
-    x: i64,
-    y: i64,
-}
-
-impl Point {
-    fn new(x: i64, y: i64) -> Self {
-        Point { x, y }
-    }
+fn parse_point(line: &str) -> Option<Point> {
+    let mut parts = [0; 2];
+    let mut idx = 0;
 
-    fn parse(line: &str) -> Option<Self> {
-        let mut parts = [0; 2];
-        let mut idx = 0;
-
-        for &b in line.as_bytes() {
-            match b {
-                b',' => {
-                    if idx == 1 {
-                        return None;
-                    }
-                    idx += 1
-                }
-                b'0'..=b'9' => {
-                    parts[idx] *= 10;
-                    parts[idx] += (b - b'0') as i64;
-                }
-                _ => {
+    for &b in line.as_bytes() {
+        match b {
+            b',' => {
+                if idx == 1 {
                     return None;
                 }
+                idx += 1
+            }
+            b'0'..=b'9' => {
+                parts[idx] *= 10;
+                parts[idx] += (b - b'0') as i64;
+            }
+            _ => {
+                return None;
             }
-        }
-
-        Some(Point::new(parts[0], parts[1]))
-    }
-
-    fn min(&self, other: &Self) -> Self {
-        Point {
-            x: self.x.min(other.x),
-            y: self.y.min(other.y),
         }
     }
 
-    fn max(&self, other: &Self) -> Self {
-        Point {
-            x: self.x.max(other.x),
-            y: self.y.max(other.y),
-        }
-    }
+    Some(Point::new(parts[0], parts[1]))
+}
 
-    fn area(&self, other: &Self) -> u64 {
-        (self.x.abs_diff(other.x) + 1) * (self.y.abs_diff(other.y) + 1)
-    }
+fn area(a: Point, b: Point) -> u64 {
+    Rect::new(a, b).area() as u64
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -145,52 +125,37 @@ impl Line {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
-struct Rect {
-    min: Point,
-    max: Point,
-}
-
-impl Rect {
-    fn new(a: Point, b: Point) -> Self {
-        let min = a.min(&b);
-        let max = a.max(&b);
-
-        Self { min, max }
-    }
-
-    fn intersects(&self, line: &Line) -> bool {
-        match line {
-            &Line::Vert { x, y0, y1 } => {
-                let in_x = x > self.min.x && x < self.max.x;
-                let around_y = y0 < self.min.y && y1 > self.max.y;
-                let inside_y = y0 > self.min.y && y1 < self.max.y;
-                let in_min = self.min.y > y0 && self.min.y < y1;
-                let in_max = self.max.y > y0 && self.max.y < y1;
-
-                let edge = if y0 == self.min.y || y1 == self.max.y {
-                    in_x
-                } else {
-                    false
-                };
-
-                edge || in_x && (around_y || inside_y || in_min || in_max)
-            }
-            &Line::Horz { y, x0, x1 } => {
-                let in_y = y > self.min.y && y < self.max.y;
-                let around_x = x0 < self.min.x && x1 > self.max.x;
-                let inside_x = x0 > self.min.x && x1 < self.max.x;
-                let in_min = self.min.x > x0 && self.min.x < x1;
-                let in_max = self.max.x > x0 && self.max.x < x1;
-
-                let edge = if x0 == self.min.x || x1 == self.max.x {
-                    in_y
-                } else {
-                    false
-                };
-
-                edge || in_y && (around_x || inside_x || in_min || in_max)
-            }
+fn intersects(rect: &Rect<i64>, line: &Line) -> bool {
+    match line {
+        &Line::Vert { x, y0, y1 } => {
+            let in_x = x > rect.min.x && x < rect.max.x;
+            let around_y = y0 < rect.min.y && y1 > rect.max.y;
+            let inside_y = y0 > rect.min.y && y1 < rect.max.y;
+            let in_min = rect.min.y > y0 && rect.min.y < y1;
+            let in_max = rect.max.y > y0 && rect.max.y < y1;
+
+            let edge = if y0 == rect.min.y || y1 == rect.max.y {
+                in_x
+            } else {
+                false
+            };
+
+            edge || in_x && (around_y || inside_y || in_min || in_max)
+        }
+        &Line::Horz { y, x0, x1 } => {
+            let in_y = y > rect.min.y && y < rect.max.y;
+            let around_x = x0 < rect.min.x && x1 > rect.max.x;
+            let inside_x = x0 > rect.min.x && x1 < rect.max.x;
+            let in_min = rect.min.x > x0 && rect.min.x < x1;
+            let in_max = rect.max.x > x0 && rect.max.x < x1;
+
+            let edge = if x0 == rect.min.x || x1 == rect.max.x {
+                in_y
+            } else {
+                false
+            };
+
+            edge || in_y && (around_x || inside_x || in_min || in_max)
         }
     }
 }
@@ -1,6 +1,8 @@
 use std::{collections::VecDeque, hash::Hash};
 
-use crate::HashSet;
+use crate::{Answer, HashSet};
+
+crate::solution!(part_one, part_two);
 
 pub fn part_one(input: &str) -> u32 {
     let mut machine = Machine::new();
@@ -15,8 +17,8 @@ pub fn part_one(input: &str) -> u32 {
     sum
 }
 
-pub fn part_two(_input: &str) -> &'static str {
-    "incomplete"
+pub fn part_two(_input: &str) -> Answer {
+    Answer::Unsolved
 }
 
 struct Machine {
@@ -1,5 +1,7 @@
 use crate::{HashMap, HashSet};
 
+crate::solution!(part_one, part_two);
+
 pub fn part_one(input: &str) -> u64 {
     let graph = Graph::new(input).expect("could not parse");
     graph.count_routes(Node("you"), Node("out"))
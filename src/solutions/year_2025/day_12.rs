@@ -1,3 +1,11 @@
+use crate::Answer;
+use crate::geometry::{Point2, Transform};
+use crate::grid::Grid;
+
+crate::solution!(part_one, part_two);
+
+type Point = Point2<i32>;
+
 pub fn part_one(input: &str) -> u32 {
     let blocks = input.trim().split("\n\n");
     let mut shapes = Vec::with_capacity(6);
@@ -21,8 +29,8 @@ pub fn part_one(input: &str) -> u32 {
     fit_count
 }
 
-pub fn part_two(_input: &str) -> &'static str {
-    "Almost there..."
+pub fn part_two(_input: &str) -> Answer {
+    Answer::NotApplicable
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -59,21 +67,10 @@ impl Shape {
     }
 
     fn permutations(self) -> [ShapeView; 8] {
-        use Rotation as R;
-        use Transform as T;
-        [
-            T::Normal(R::Zero).view(self),
-            T::Normal(R::Ninty).view(self),
-            T::Normal(R::OneEighty).view(self),
-            T::Normal(R::TwoSeventy).view(self),
-            T::Flipped(R::Zero).view(self),
-            T::Flipped(R::Ninty).view(self),
-            T::Flipped(R::OneEighty).view(self),
-            T::Flipped(R::TwoSeventy).view(self),
-        ]
+        Transform::ALL.map(|transform| ShapeView(transform, self))
     }
 
-    fn get(&self, Point(x, y): Point) -> bool {
+    fn get(&self, Point2 { x, y }: Point) -> bool {
         assert!(x >= 0 && x < 3 && y >= 0 && y < 3);
         self.cells[y as usize][x as usize]
     }
@@ -91,103 +88,19 @@ impl ShapeView {
             if y == 3 {
                 return None;
             }
-            let p = Point(x, y);
+            let p = Point::new(x, y);
             x += 1;
             if x == 3 {
                 y += 1;
                 x = 0;
             }
 
-            let remap = self.0.apply(p);
+            let remap = self.0.apply_within(p, 3);
             Some((self.1.get(remap), p))
         })
     }
 }
 
-#[derive(Debug, Copy, Clone)]
-struct Point(i32, i32);
-
-impl Point {
-    fn idx(&self, width: usize, height: usize) -> Option<usize> {
-        let &Point(x, y) = self;
-        if x < 0 || y < 0 {
-            return None;
-        }
-
-        let x = x as usize;
-        let y = y as usize;
-
-        if x >= width || y >= height {
-            return None;
-        }
-
-        Some(y * width + x)
-    }
-
-    fn rotate(self) -> Point {
-        let Point(x, y) = self;
-        assert!(x >= 0 && x < 3 && y >= 0 && y < 3);
-        match (x, y) {
-            (1, 1) => Point(1, 1),
-            (0, 0) => Point(0, 2),
-            (0, 2) => Point(2, 2),
-            (2, 2) => Point(2, 0),
-            (2, 0) => Point(0, 0),
-            (1, 2) => Point(2, 1),
-            (2, 1) => Point(1, 0),
-            (1, 0) => Point(0, 1),
-            (0, 1) => Point(1, 2),
-            _ => unreachable!(),
-        }
-    }
-}
-
-impl std::ops::Add for Point {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Point(self.0 + rhs.0, self.1 + rhs.1)
-    }
-}
-
-#[derive(Debug, Copy, Clone)]
-enum Transform {
-    Normal(Rotation),
-    Flipped(Rotation),
-}
-
-impl Transform {
-    fn apply(&self, Point(x, y): Point) -> Point {
-        match self {
-            Transform::Normal(rotation) => rotation.apply(Point(x, y)),
-            Transform::Flipped(rotation) => rotation.apply(Point(x, 2 - y)),
-        }
-    }
-
-    fn view(self, shape: Shape) -> ShapeView {
-        ShapeView(self, shape)
-    }
-}
-
-#[derive(Debug, Copy, Clone)]
-enum Rotation {
-    Zero,
-    Ninty,
-    OneEighty,
-    TwoSeventy,
-}
-
-impl Rotation {
-    fn apply(&self, p: Point) -> Point {
-        match self {
-            Rotation::Zero => p,
-            Rotation::Ninty => p.rotate(),
-            Rotation::OneEighty => p.rotate().rotate(),
-            Rotation::TwoSeventy => p.rotate().rotate().rotate(),
-        }
-    }
-}
-
 #[derive(Debug, Clone)]
 struct Region {
     width: usize,
@@ -250,7 +163,7 @@ impl Region {
             for (idx, &shape) in shapes.iter().enumerate() {
                 if counts[idx] > 0 {
                     for perm in shape.permutations() {
-                        if map.try_place(Point(x, y), perm) {
+                        if map.try_place(Point::new(x, y), perm) {
                             counts[idx] -= 1;
                             continue 'outer;
                         }
@@ -273,41 +186,26 @@ impl Region {
 }
 
 struct RegionMap {
-    cells: Vec<bool>,
-    width: usize,
-    height: usize,
+    cells: Grid<bool>,
 }
 
 impl RegionMap {
     fn new() -> Self {
         Self {
-            cells: Vec::new(),
-            width: 0,
-            height: 0,
+            cells: Grid::new(0, 0, false),
         }
     }
 
     fn resize(&mut self, width: usize, height: usize) {
-        self.cells.clear();
-        self.cells.resize(width * height, false);
-        self.width = width;
-        self.height = height;
+        self.cells = Grid::new(width, height, false);
     }
 
-    fn get(&mut self, point: Point) -> Option<bool> {
-        let Some(idx) = point.idx(self.width, self.height) else {
-            return None;
-        };
-
-        self.cells.get(idx).copied()
+    fn get(&self, point: Point) -> Option<bool> {
+        self.cells.get(point).copied()
     }
 
     fn set(&mut self, point: Point, value: bool) {
-        let Some(idx) = point.idx(self.width, self.height) else {
-            return;
-        };
-
-        self.cells[idx] = value;
+        self.cells.set(point, value);
     }
 
     fn try_place(&mut self, origin: Point, shape: ShapeView) -> bool {
@@ -334,16 +232,7 @@ impl RegionMap {
 
     #[allow(dead_code)]
     fn display(&self) {
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let idx = y * self.width + x;
-                let c = if self.cells[idx] { '#' } else { '.' };
-
-                eprint!("{c}");
-            }
-            eprintln!();
-        }
-        eprintln!();
+        eprintln!("{}", self.cells.render(|&c| if c { '#' } else { '.' }));
     }
 }
 